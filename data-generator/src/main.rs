@@ -3,15 +3,25 @@ use std::error::Error;
 use std::fs;
 use std::fs::File;
 use std::io::BufWriter;
+use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 
+use arrow::datatypes::FieldRef;
+use arrow::datatypes::Schema;
+use arrow::ipc::writer::FileWriter;
 use clap::Parser;
+use clap::ValueEnum;
 use csv::WriterBuilder;
 use nexmark::config::NexmarkConfig;
 use nexmark::event::Event;
 use nexmark::event::EventType;
+use parquet::arrow::ArrowWriter;
 use rand::seq::IndexedRandom;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_arrow::schema::SchemaLike;
+use serde_arrow::schema::TracingOptions;
 use base64::{engine::general_purpose::STANDARD, Engine};
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -65,6 +75,68 @@ impl WasmComponent {
     }
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Format {
+    Csv,
+    Parquet,
+    Arrow,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Parquet => "parquet",
+            Format::Arrow => "arrow",
+        }
+    }
+}
+
+/// Number of rows per record batch of Parquet and Arrow files.
+const BATCH_SIZE: usize = 8192;
+
+/// Writes rows to `path` in the given format.
+fn write<T: Serialize + DeserializeOwned>(
+    path: &Path,
+    format: Format,
+    mut rows: impl Iterator<Item = T>,
+) -> Result<(), Box<dyn Error>> {
+    let file = File::create(path)?;
+    if let Format::Csv = format {
+        let mut writer = WriterBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_writer(BufWriter::new(file));
+        return rows.try_for_each(|row| writer.serialize(&row)).map_err(Into::into);
+    }
+    let fields = Vec::<FieldRef>::from_type::<T>(TracingOptions::default())?;
+    let schema = Arc::new(Schema::new(fields.clone()));
+    let mut batches = std::iter::from_fn(|| {
+        let batch = rows.by_ref().take(BATCH_SIZE).collect::<Vec<_>>();
+        (!batch.is_empty()).then_some(batch)
+    });
+    match format {
+        Format::Parquet => {
+            let mut writer = ArrowWriter::try_new(BufWriter::new(file), schema, None)?;
+            batches.try_for_each(|batch| {
+                writer.write(&serde_arrow::to_record_batch(&fields, &batch)?)?;
+                Ok::<_, Box<dyn Error>>(())
+            })?;
+            writer.close()?;
+        }
+        Format::Arrow => {
+            let mut writer = FileWriter::try_new(BufWriter::new(file), &schema)?;
+            batches.try_for_each(|batch| {
+                writer.write(&serde_arrow::to_record_batch(&fields, &batch)?)?;
+                Ok::<_, Box<dyn Error>>(())
+            })?;
+            writer.finish()?;
+        }
+        Format::Csv => unreachable!(),
+    }
+    Ok(())
+}
+
 #[derive(Parser, Clone, Debug)]
 struct Args {
     /// Number of events to generate.
@@ -88,6 +160,9 @@ struct Args {
     each: usize,
     #[clap(long, default_value = ".")]
     dir: PathBuf,
+    /// Format of the generated files.
+    #[clap(long, value_enum, default_value_t = Format::Csv)]
+    format: Format,
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -142,11 +217,8 @@ fn main() -> Result<(), Box<dyn Error>> {
 
         let mut components_event_queue: VecDeque<Event> = VecDeque::new();
 
-        let file = File::create(args.dir.join(name).with_extension("csv"))?;
-        let mut writer = WriterBuilder::new()
-            .has_headers(false)
-            .from_writer(BufWriter::new(file));
-        nexmark::EventGenerator::new(conf.clone())
+        let path = args.dir.join(name).with_extension(args.format.extension());
+        let events = nexmark::EventGenerator::new(conf.clone())
             .with_type_filter(ty)
             .take(n)
             .enumerate()
@@ -161,22 +233,30 @@ fn main() -> Result<(), Box<dyn Error>> {
                     components_event_queue.push_back(e.clone());
                 }
             })
-            .try_for_each(|(_, event)| match event {
-                Event::Person(row) if ty == EventType::Person => writer.serialize(&row),
-                Event::Auction(row) if ty == EventType::Auction => writer.serialize(&row),
-                Event::Bid(row) if ty == EventType::Bid => writer.serialize(&row),
+            .map(|(_, event)| event);
+        match ty {
+            EventType::Person => write(&path, args.format, events.map(|event| match event {
+                Event::Person(row) => row,
                 _ => unreachable!(),
-            })?;
+            }))?,
+            EventType::Auction => write(&path, args.format, events.map(|event| match event {
+                Event::Auction(row) => row,
+                _ => unreachable!(),
+            }))?,
+            EventType::Bid => write(&path, args.format, events.map(|event| match event {
+                Event::Bid(row) => row,
+                _ => unreachable!(),
+            }))?,
+        }
 
-        let component_file = File::create(args.dir.join(format!("component_{}", name)).with_extension("csv")).unwrap();
-        let mut component_writer = WriterBuilder::new()
-            .has_headers(false)
-            .flexible(true)
-            .from_writer(BufWriter::new(component_file));
+        let component_path = args
+            .dir
+            .join(format!("component_{}", name))
+            .with_extension(args.format.extension());
         // Generate and write component events
         let n = components_event_queue.len();
         eprintln!("{:?}", wasm_files);
-        components_event_queue
+        let components = components_event_queue
         .into_iter()
         .enumerate()
         .inspect(|(i, _)| {
@@ -199,10 +279,8 @@ fn main() -> Result<(), Box<dyn Error>> {
                 }
             }
         })
-        .try_for_each(|component| match component.empty() {
-            false => component_writer.serialize(&component),
-            _ => unreachable!(),
-        })?;
+        .inspect(|component| assert!(!component.empty()));
+        write(&component_path, args.format, components)?;
     }
 
     Ok(())
@@ -3,6 +3,7 @@ wit_bindgen::generate!({
 });
 
 use exports::pkg::component::nexmark::{Bid, Guest as NexmarkGuest, EitherData};
+use exports::pkg::component::nexmark_batch::Guest as NexmarkBatchGuest;
 // use pkg::component::data_type::{Auction, Person};
 
 struct Component;
@@ -34,4 +35,14 @@ impl NexmarkGuest for Component {
             d => d,
         })
     }
-}
\ No newline at end of file
+}
+
+impl NexmarkBatchGuest for Component {
+    fn qs(bids: Vec<Bid>,) -> Vec<Option<Bid>> {
+        bids.into_iter().map(<Component as NexmarkGuest>::qs).collect()
+    }
+
+    fn qs_g(data: Vec<EitherData>,) -> Vec<Option<EitherData>> {
+        data.into_iter().map(<Component as NexmarkGuest>::qs_g).collect()
+    }
+}
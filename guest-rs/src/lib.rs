@@ -3,6 +3,7 @@ wit_bindgen::generate!({
 });
 
 use exports::pkg::component::nexmark::{Bid, Guest as NexmarkGuest, EitherData};
+use exports::pkg::component::nexmark_batch::Guest as NexmarkBatchGuest;
 
 struct Component;
 
@@ -30,3 +31,13 @@ impl NexmarkGuest for Component {
         }
     }
 }
+
+impl NexmarkBatchGuest for Component {
+    fn qs(bids: Vec<Bid>,) -> Vec<Option<Bid>> {
+        bids.into_iter().map(<Component as NexmarkGuest>::qs).collect()
+    }
+
+    fn qs_g(data: Vec<EitherData>,) -> Vec<Option<EitherData>> {
+        data.into_iter().map(<Component as NexmarkGuest>::qs_g).collect()
+    }
+}
@@ -3,6 +3,7 @@ wit_bindgen::generate!({
 });
 
 use exports::pkg::component::nexmark::{Bid, Guest as NexmarkGuest, PrunedBid};
+use exports::pkg::component::nexmark_batch::Guest as NexmarkBatchGuest;
 
 struct Component;
 
@@ -57,4 +58,18 @@ impl NexmarkGuest for Component {
         }
         return None
     }
-}
\ No newline at end of file
+}
+
+impl NexmarkBatchGuest for Component {
+    fn e1(prices: Vec<u64>,) -> Vec<bool> {
+        prices.into_iter().map(<Component as NexmarkGuest>::e1).collect()
+    }
+
+    fn all_in_wasm(bids: Vec<Bid>,) -> Vec<Option<PrunedBid>> {
+        bids.into_iter().map(<Component as NexmarkGuest>::all_in_wasm).collect()
+    }
+
+    fn all_in_wasm_not_pruned(bids: Vec<Bid>,) -> Vec<Option<Bid>> {
+        bids.into_iter().map(<Component as NexmarkGuest>::all_in_wasm_not_pruned).collect()
+    }
+}
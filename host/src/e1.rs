@@ -1,5 +1,5 @@
 use runtime::prelude::*;
-use crate::{data::{Bid, PrunedBid}, wasm::WasmFunction};
+use crate::{data::{Bid, PrunedBid}, wasm::{wasm_batch, WasmFunction}};
 
 #[data]
 pub struct Output {
@@ -80,4 +80,13 @@ pub fn run_wasm_e1_all_in_wasm(bids: Stream<Bid>, ctx: &mut Context, wasm_func:
         wasm_func.call((bid.clone(),)).0
     })
     .drain(ctx);
-}
\ No newline at end of file
+}
+
+// Wasm, one call per batch of bids
+pub fn run_wasm_e1_all_in_wasm_batch<O>(bids: Stream<Bid>, ctx: &mut Context, wasm_func: WasmFunction<(Vec<Bid>,), (Vec<Option<O>>,)>, batch_size: usize)
+where
+O: Data + wasmtime::component::ComponentType + wasmtime::component::Lift
+{
+    wasm_batch(bids, ctx, batch_size, wasm_func)
+    .drain(ctx);
+}
@@ -51,6 +51,25 @@ pub fn run_wasm_e1_func(input: Bid, wasm_func_call: impl Fn((u64,)) -> (bool,),)
     }
 }
 
+// Wasm, one call per batch of bids. The i-th result belongs to the i-th bid.
+pub fn run_wasm_e1_batch_func(input: Vec<Bid>, wasm_func_call: impl Fn((Vec<u64>,)) -> Result<(Vec<bool>,), Error>,) -> Result<Vec<Output>, Error> {
+    let (results,) = wasm_func_call((input.iter().map(|bid| bid.auction).collect(),))?;
+    if results.len() != input.len() {
+        return Err(Error::custom(format!(
+            "Expected {} results, but the batch returned {}",
+            input.len(),
+            results.len()
+        )));
+    }
+
+    Ok(input
+        .into_iter()
+        .zip(results)
+        .filter(|(_, result)| *result)
+        .map(|(bid, _)| Output::new(bid.auction, bid.price))
+        .collect())
+}
+
 pub fn run_wasm_e1_all_in_wasm_g<T>(bids: Stream<T>, ctx: &mut Context, wasm_func: WasmFunction<(T,), (Option<T>,)>)
 where 
 T: Clone + Unpin + for<'a> runtime::prelude::serde::Deserialize<'a> + runtime::prelude::serde::Serialize + std::fmt::Debug + std::marker::Send+ std::marker::Sync + wasmtime::component::Lower + wasmtime::component::ComponentType + wasmtime::component::Lift + 'static
@@ -7,15 +7,21 @@ pub mod e3;
 pub mod experiment_framework;
 
 use std::fs::File;
-use runtime::prelude::Context;
-use experiment_framework::{iter_with, iter, stream, stream_with, timed, ExperimentResult, WATERMARK_FREQUENCY};
+use runtime::formats::columnar;
+use runtime::prelude::serde::de::DeserializeOwned;
+use runtime::prelude::{stream, Context, Data, Duration, Error, Stream};
+use runtime::traits::Timestamp;
+use experiment_framework::{iter_with, iter, timed, ExperimentResult, WATERMARK_FREQUENCY};
 use data::{Bid, PrunedBid};
 use wasm::{Host, WasmComponent, WasmFunction};
 use wasmtime::{component::Linker, Config, Engine};
 use wasmtime_wasi::WasiImpl;
 use std::hint::black_box;
 
-const USAGE: &str = "Usage: cargo run <data-dir> <experiment> <variant> <measure-experiment-num> <warmup-num> <output-path> [wasm-type]";
+/// Records per call of the batched WASM variants, unless given on the command line.
+const DEFAULT_BATCH_SIZE: usize = 1000;
+
+const USAGE: &str = "Usage: cargo run <data-dir> <experiment> <variant> <measure-experiment-num> <warmup-num> <output-path> [wasm-type] [batch-size]";
 
 fn main() {
     let mut args = std::env::args().skip(1);
@@ -54,6 +60,16 @@ fn main() {
     
     // 获取 WASM 类型参数（可选）
     let wasm_type = args.next().unwrap_or_else(|| "default".to_string());
+    let batch_size = match args.next() {
+        Some(batch_size) => match batch_size.parse() {
+            Ok(batch_size) if batch_size > 0 => batch_size,
+            _ => {
+                eprintln!("Invalid batch size: {batch_size}");
+                return;
+            }
+        },
+        None => DEFAULT_BATCH_SIZE,
+    };
     
     let mut guest_rs_wasi_module: &[u8] = include_bytes!(concat!(
         env!("CARGO_MANIFEST_DIR"),
@@ -88,9 +104,9 @@ fn main() {
 
     // 根据 experiment 和 variant 分发
     match experiment.as_str() {
-        "e1" => run_e1_variant(&variant, &dir, total, warmup, &output_dir, &linker, &engine, guest_rs_wasi_module),
+        "e1" => run_e1_variant(&variant, &dir, total, warmup, &output_dir, &linker, &engine, guest_rs_wasi_module, batch_size),
         "e2" => run_e2_variant(&variant, &dir, total, warmup, &output_dir, &linker, &engine, guest_rs_wasi_module),
-        "e3" => run_e3_variant(&variant, &dir, total, warmup, &output_dir, &linker, &engine, guest_rs_wasi_module),
+        "e3" => run_e3_variant(&variant, &dir, total, warmup, &output_dir, &linker, &engine, guest_rs_wasi_module, batch_size),
         "e4" => run_e4_variant(&variant, &dir, total, warmup, &output_dir, &linker, &engine, guest_rs_wasi_module),
         _ => panic!("unknown experiment: {}", experiment),
     }
@@ -105,6 +121,7 @@ fn run_e1_variant(
     linker: &Linker<WasiImpl<Host>>,
     engine: &Engine,
     guest_module: &[u8],
+    batch_size: usize,
 ) {
     let sizes = vec![100, 1000, 10000, 100000, 1000000];
     
@@ -115,9 +132,8 @@ fn run_e1_variant(
             "io" => {
                 let mut result = ExperimentResult::new("io", warmup, &output_dir.to_string());
                 for _ in 0..total {
-                    let bids = File::open(&format!("{dir}/bids.csv"))
-                        .map(|file| iter_with::<Bid>(file, size));
-                    let r = timed(move |ctx| stream(ctx, bids).drain(ctx));
+                    let bids = open(dir, "bids", |file| iter_with::<Bid>(file, size)).map(|bids| bids.take(size));
+                    let r = timed(move |ctx| try_stream(ctx, bids).drain(ctx));
                     result.add(r);
                 }
                 result.print();
@@ -127,9 +143,8 @@ fn run_e1_variant(
             "native_opt" => {
                 let mut result = ExperimentResult::new("native_opt", warmup, &output_dir.to_string());
                 for _ in 0..total {
-                    let bids = File::open(&format!("{dir}/bids.csv"))
-                        .map(|file| iter_with::<Bid>(file, size));
-                    let r = timed(move |ctx| e1::run_opt(stream(ctx, bids), ctx));
+                    let bids = open(dir, "bids", |file| iter_with::<Bid>(file, size)).map(|bids| bids.take(size));
+                    let r = timed(move |ctx| e1::run_opt(try_stream(ctx, bids), ctx));
                     result.add(r);
                 }
                 result.print();
@@ -139,12 +154,11 @@ fn run_e1_variant(
             "wasm_pass_all" => {
                 let mut result = ExperimentResult::new("wasm_pass_all", warmup, &output_dir.to_string());
                 for _ in 0..total {
-                    let bids = File::open(&format!("{dir}/bids.csv"))
-                        .map(|file| iter_with::<Bid>(file, size));
+                    let bids = open(dir, "bids", |file| iter_with::<Bid>(file, size)).map(|bids| bids.take(size));
                     let wasm_func_q2 = WasmFunction::<(u64, u64, Vec<u64>,), (Option<(u64, u64)>,)>::new(
                         linker, engine, guest_module, "pkg:component/nexmark", "q2"
                     );
-                    let r = timed(move |ctx| e1::run_wasm(stream(ctx, bids), ctx, wasm_func_q2));
+                    let r = timed(move |ctx| e1::run_wasm(try_stream(ctx, bids), ctx, wasm_func_q2));
                     result.add(r);
                 }
                 result.print();
@@ -154,12 +168,11 @@ fn run_e1_variant(
             "wasm_opt_pruned" => {
                 let mut result = ExperimentResult::new("wasm_opt_pruned", warmup, &output_dir.to_string());
                 for _ in 0..total {
-                    let bids = File::open(&format!("{dir}/bids.csv"))
-                        .map(|file| iter_with::<Bid>(file, size));
+                    let bids = open(dir, "bids", |file| iter_with::<Bid>(file, size)).map(|bids| bids.take(size));
                     let wasm_func_single_filter = WasmFunction::<(u64, Vec<u64>,), (bool,)>::new(
                         linker, engine, guest_module, "pkg:component/nexmark", "single-filter"
                     );
-                    let r = timed(move |ctx| e1::run_wasm_sf(stream(ctx, bids), ctx, wasm_func_single_filter));
+                    let r = timed(move |ctx| e1::run_wasm_sf(try_stream(ctx, bids), ctx, wasm_func_single_filter));
                     result.add(r);
                 }
                 result.print();
@@ -169,12 +182,11 @@ fn run_e1_variant(
             "wasm_opt2" => {
                 let mut result = ExperimentResult::new("wasm_opt2", warmup, &output_dir.to_string());
                 for _ in 0..total {
-                    let bids = File::open(&format!("{dir}/bids.csv"))
-                        .map(|file| iter_with::<Bid>(file, size));
+                    let bids = open(dir, "bids", |file| iter_with::<Bid>(file, size)).map(|bids| bids.take(size));
                     let wasm_func_e1 = WasmFunction::<(u64,), (bool,)>::new(
                         linker, engine, guest_module, "pkg:component/nexmark", "e1"
                     );
-                    let r = timed(move |ctx| e1::run_wasm_e1(stream(ctx, bids), ctx, wasm_func_e1));
+                    let r = timed(move |ctx| e1::run_wasm_e1(try_stream(ctx, bids), ctx, wasm_func_e1));
                     result.add(r);
                 }
                 result.print();
@@ -184,27 +196,53 @@ fn run_e1_variant(
             "wasm_opt3" => {
                 let mut result = ExperimentResult::new("wasm_opt3", warmup, &output_dir.to_string());
                 for _ in 0..total {
-                    let bids = File::open(&format!("{dir}/bids.csv"))
-                        .map(|file| iter_with::<Bid>(file, size));
+                    let bids = open(dir, "bids", |file| iter_with::<Bid>(file, size)).map(|bids| bids.take(size));
                     let wasm_func_e1 = WasmFunction::<(Bid,), (Option<Bid>,)>::new(
                         linker, engine, guest_module, "pkg:component/nexmark", "all-in-wasm-not-pruned"
                     );
-                    let r = timed(move |ctx| e1::run_wasm_e1_all_in_wasm_g::<Bid>(stream(ctx, bids), ctx, wasm_func_e1));
+                    let r = timed(move |ctx| e1::run_wasm_e1_all_in_wasm_g::<Bid>(try_stream(ctx, bids), ctx, wasm_func_e1));
                     result.add(r);
                 }
                 result.print();
                 result.in_file("e1", size);
             }
             
+            "wasm_opt3_batch" => {
+                let mut result = ExperimentResult::new("wasm_opt3_batch", warmup, &output_dir.to_string());
+                for _ in 0..total {
+                    let bids = open(dir, "bids", |file| iter_with::<Bid>(file, size)).map(|bids| bids.take(size));
+                    let wasm_func_e1 = WasmFunction::<(Vec<Bid>,), (Vec<Option<Bid>>,)>::new(
+                        linker, engine, guest_module, "pkg:component/nexmark-batch", "all-in-wasm-not-pruned"
+                    );
+                    let r = timed(move |ctx| e1::run_wasm_e1_all_in_wasm_batch(try_stream(ctx, bids), ctx, wasm_func_e1, batch_size));
+                    result.add(r);
+                }
+                result.print();
+                result.in_file("e1", size);
+            }
+
             "wasm_opt4" => {
                 let mut result = ExperimentResult::new("wasm_opt4", warmup, &output_dir.to_string());
                 for _ in 0..total {
-                    let bids = File::open(&format!("{dir}/bids.csv"))
-                        .map(|file| iter_with::<Bid>(file, size));
+                    let bids = open(dir, "bids", |file| iter_with::<Bid>(file, size)).map(|bids| bids.take(size));
                     let wasm_func_e1 = WasmFunction::<(Bid,), (Option<PrunedBid>,)>::new(
                         linker, engine, guest_module, "pkg:component/nexmark", "all-in-wasm"
                     );
-                    let r = timed(move |ctx| e1::run_wasm_e1_all_in_wasm(stream(ctx, bids), ctx, wasm_func_e1));
+                    let r = timed(move |ctx| e1::run_wasm_e1_all_in_wasm(try_stream(ctx, bids), ctx, wasm_func_e1));
+                    result.add(r);
+                }
+                result.print();
+                result.in_file("e1", size);
+            }
+
+            "wasm_opt4_batch" => {
+                let mut result = ExperimentResult::new("wasm_opt4_batch", warmup, &output_dir.to_string());
+                for _ in 0..total {
+                    let bids = open(dir, "bids", |file| iter_with::<Bid>(file, size)).map(|bids| bids.take(size));
+                    let wasm_func_e1 = WasmFunction::<(Vec<Bid>,), (Vec<Option<PrunedBid>>,)>::new(
+                        linker, engine, guest_module, "pkg:component/nexmark-batch", "all-in-wasm"
+                    );
+                    let r = timed(move |ctx| e1::run_wasm_e1_all_in_wasm_batch(try_stream(ctx, bids), ctx, wasm_func_e1, batch_size));
                     result.add(r);
                 }
                 result.print();
@@ -231,15 +269,14 @@ fn run_e2_variant(
     
     match variant {
         "io" => {
-            let component_len = File::open(&format!("{dir}/component_bids.csv"))
-                .map(iter::<WasmComponent>).unwrap().count();
+            let component_len = open(dir, "component_bids", iter::<WasmComponent>).unwrap().count();
             let mut result = ExperimentResult::new("io", warmup, &output_dir.to_string());
             for _ in 0..total {
-                let bids = File::open(&format!("{dir}/bids.csv")).map(iter::<Bid>);
-                let components_bids = File::open(&format!("{dir}/component_bids.csv")).map(iter::<WasmComponent>);
+                let bids = open(dir, "bids", iter::<Bid>);
+                let components_bids = open(dir, "component_bids", iter::<WasmComponent>);
                 let r = timed(move |ctx| {
-                    stream(ctx, bids).drain(ctx);
-                    stream_with(ctx, components_bids, 1).drain(ctx);
+                    try_stream(ctx, bids).drain(ctx);
+                    try_stream_with(ctx, components_bids, 1).drain(ctx);
                 });
                 result.add(r);
             }
@@ -248,17 +285,16 @@ fn run_e2_variant(
         }
         
         "wasm_opt2" => {
-            let component_len = File::open(&format!("{dir}/component_bids.csv"))
-                .map(iter::<WasmComponent>).unwrap().count();
+            let component_len = open(dir, "component_bids", iter::<WasmComponent>).unwrap().count();
             let mut result = ExperimentResult::new("wasm_opt2_dynamic", warmup, &output_dir.to_string());
             for _ in 0..total {
-                let bids = File::open(&format!("{dir}/bids.csv")).map(iter::<Bid>);
-                let components_bids = File::open(&format!("{dir}/component_bids.csv")).map(iter::<WasmComponent>);
+                let bids = open(dir, "bids", iter::<Bid>);
+                let components_bids = open(dir, "component_bids", iter::<WasmComponent>);
                 let wasm_func_e1 = WasmFunction::<(u64,), (bool,)>::new_empty_with_name(
                     linker, engine, "pkg:component/nexmark", "e1"
                 );
                 let r = timed(move |ctx| {
-                    e2::run_wasm_e2(stream(ctx, bids), stream_with(ctx, components_bids, 1), ctx, wasm_func_e1)
+                    e2::run_wasm_e2(try_stream(ctx, bids), try_stream_with(ctx, components_bids, 1), ctx, wasm_func_e1)
                 });
                 result.add(r);
             }
@@ -280,6 +316,7 @@ fn run_e3_variant(
     linker: &Linker<WasiImpl<Host>>,
     engine: &Engine,
     guest_module: &[u8],
+    batch_size: usize,
 ) {
     let sizes = vec![100, 1000, 10000, 100000, 1000000];
     
@@ -290,10 +327,10 @@ fn run_e3_variant(
             "io" => {
                 let mut result = ExperimentResult::new("io", warmup, &output_dir.to_string());
                 for _ in 0..total {
-                    let bids = File::open(&format!("{dir}/bids.csv")).map(iter::<Bid>);
+                    let bids = open(dir, "bids", iter::<Bid>);
                     let r = timed(move |_ctx| {
                         for bid in bids.unwrap().take(size) {
-                            let _input = black_box(bid);
+                            let _input = black_box(bid.unwrap());
                         }
                     });
                     result.add(r);
@@ -305,10 +342,10 @@ fn run_e3_variant(
             "native_opt" => {
                 let mut result = ExperimentResult::new("native_opt", warmup, &output_dir.to_string());
                 for _ in 0..total {
-                    let bids = File::open(&format!("{dir}/bids.csv")).map(iter::<Bid>);
+                    let bids = open(dir, "bids", iter::<Bid>);
                     let r = timed(move |_ctx| {
                         for bid in bids.unwrap().take(size) {
-                            let input = black_box(bid);
+                            let input = black_box(bid.unwrap());
                             let _output = black_box(e3::opt_func(input));
                         }
                     });
@@ -321,13 +358,13 @@ fn run_e3_variant(
             "wasm_pass_all" => {
                 let mut result = ExperimentResult::new("wasm_pass_all", warmup, &output_dir.to_string());
                 for _ in 0..total {
-                    let bids = File::open(&format!("{dir}/bids.csv")).map(iter::<Bid>);
+                    let bids = open(dir, "bids", iter::<Bid>);
                     let wasm_func_q2 = WasmFunction::<(u64, u64, Vec<u64>,), (Option<(u64, u64)>,)>::new(
                         linker, engine, guest_module, "pkg:component/nexmark", "q2"
                     );
                     let r = timed(move |_ctx| {
                         for bid in bids.unwrap().take(size) {
-                            let input = black_box(bid);
+                            let input = black_box(bid.unwrap());
                             let _output = black_box(e3::run_wasm_func(input, |args| wasm_func_q2.call(args)));
                         }
                     });
@@ -337,6 +374,31 @@ fn run_e3_variant(
                 result.in_file("e3", size);
             }
             
+            "wasm_opt2_batch" => {
+                let mut result = ExperimentResult::new("wasm_opt2_batch", warmup, &output_dir.to_string());
+                for _ in 0..total {
+                    let bids = open(dir, "bids", iter::<Bid>);
+                    let wasm_func_e1 = WasmFunction::<(Vec<u64>,), (Vec<bool>,)>::new(
+                        linker, engine, guest_module, "pkg:component/nexmark-batch", "e1"
+                    );
+                    // Runs in an operator, so that a failed batch fails the dataflow.
+                    let r = timed(move |ctx| {
+                        ctx.operator(move |_: stream::Collector<()>| async move {
+                            let bids = bids?.take(size).collect::<Result<Vec<_>, _>>()?;
+                            for chunk in bids.chunks(batch_size) {
+                                let input = black_box(chunk.to_vec());
+                                let _output = black_box(e3::run_wasm_e1_batch_func(input, |args| wasm_func_e1.try_call(args))?);
+                            }
+                            Ok(())
+                        })
+                        .drain(ctx);
+                    });
+                    result.add(r);
+                }
+                result.print();
+                result.in_file("e3", size);
+            }
+
             // ... 其他 e3 variants
             _ => panic!("unknown e3 variant: {}", variant),
         }
@@ -362,10 +424,10 @@ fn run_e4_variant(
             "io" => {
                 let mut result = ExperimentResult::new("io", warmup, &output_dir.to_string());
                 for _ in 0..total {
-                    let components_bids = File::open(&format!("{dir}/component_bids.csv")).map(iter::<WasmComponent>);
+                    let components_bids = open(dir, "component_bids", iter::<WasmComponent>);
                     let r = timed(move |_ctx| {
                         for components in components_bids.unwrap().take(size) {
-                            let _input = black_box(components);
+                            let _input = black_box(components.unwrap());
                         }
                     });
                     result.add(r);
@@ -378,4 +440,38 @@ fn run_e4_variant(
             _ => panic!("unknown e4 variant: {}", variant),
         }
     }
-}
\ No newline at end of file
+}
+
+// Prefers the columnar files written by `data-generator --format parquet|arrow`,
+// and otherwise reads `<name>.csv` with `csv`.
+fn open<T, I>(
+    dir: &str,
+    name: &str,
+    csv: impl FnOnce(File) -> I,
+) -> std::io::Result<Box<dyn Iterator<Item = Result<T, Error>> + Send>>
+where
+    T: DeserializeOwned + Send + 'static,
+    I: Iterator<Item = T> + Send + 'static,
+{
+    if let Some(records) = columnar::find::<T>(std::path::Path::new(dir), name) {
+        return records.map(|iter| Box::new(iter) as _);
+    }
+    File::open(format!("{dir}/{name}.csv")).map(|file| Box::new(csv(file).map(Ok)) as _)
+}
+
+// Stream from the records of `open`, which fails at the first record that
+// cannot be read.
+fn try_stream_with<T: Data + Timestamp>(
+    ctx: &mut Context,
+    iter: std::io::Result<impl Iterator<Item = Result<T, Error>> + Send + 'static>,
+    frequency: usize,
+) -> Stream<T> {
+    Stream::try_from_iter(ctx, iter.unwrap(), T::timestamp, frequency, Duration::zero())
+}
+
+fn try_stream<T: Data + Timestamp>(
+    ctx: &mut Context,
+    iter: std::io::Result<impl Iterator<Item = Result<T, Error>> + Send + 'static>,
+) -> Stream<T> {
+    try_stream_with(ctx, iter, WATERMARK_FREQUENCY)
+}
@@ -1,15 +1,17 @@
-use std::{cell::RefCell, fmt::Debug, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, fmt::Debug, path::PathBuf, rc::Rc, sync::{Arc, Mutex}};
 
 use runtime::prelude::*;
-use wasmtime::{component::{Component, Linker, TypedFunc}, Engine as WasmEngine, Store};
+use wasmtime::{component::{Component, Linker, TypedFunc}, Config, Engine as WasmEngine, Store, StoreLimits, StoreLimitsBuilder};
 use wasmtime_wasi::{ResourceTable, WasiImpl};
 use base64::{engine::general_purpose::STANDARD, Engine};
 use runtime::prelude::serde::Deserialize;
+use sha2::{Digest, Sha256};
 
 // host
 pub struct Host {
         ctx: wasmtime_wasi::WasiCtx,
         table: ResourceTable,
+        limits: StoreLimits,
     }
     
     impl wasmtime_wasi::WasiView for Host {
@@ -28,10 +30,146 @@ pub struct Host {
         pub fn new() -> Self {
             let ctx = wasmtime_wasi::WasiCtxBuilder::new().inherit_stdio().build();
             let table = ResourceTable::new();
-            Self { ctx, table }
+            Self { ctx, table, limits: StoreLimits::default() }
         }
     }
 
+/// How often the epoch of an engine from `WasmLimits::engine` is incremented.
+pub const EPOCH_TICK: std::time::Duration = std::time::Duration::from_millis(10);
+
+/// What a `WasmFunction` does when a call exceeds its limits or traps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnViolation {
+    /// Drop the record, and keep the current component.
+    #[default]
+    Skip,
+    /// Return the violation, so that the operator can emit it to an error stream.
+    Error,
+    /// Drop the record, and switch back to the component before the last switch.
+    Revert,
+}
+
+/// Budgets of a `WasmFunction`. Fuel and the epoch deadline apply to each
+/// call, and memory and table sizes to each instance. Limits which are `None`
+/// are not enforced.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WasmLimits {
+    pub fuel: Option<u64>,
+    pub epoch_deadline: Option<std::time::Duration>,
+    pub max_memory: Option<usize>,
+    pub max_table_elements: Option<usize>,
+    pub on_violation: OnViolation,
+}
+
+impl WasmLimits {
+    /// Builds an engine which can enforce these limits. If the limits have an
+    /// epoch deadline, a background thread increments the epoch of the engine
+    /// every `EPOCH_TICK` for as long as the engine is alive.
+    pub fn engine(&self) -> WasmEngine {
+        let mut config = Config::new();
+        config.consume_fuel(self.fuel.is_some());
+        config.epoch_interruption(self.epoch_deadline.is_some());
+        let engine = WasmEngine::new(&config).unwrap();
+        if self.epoch_deadline.is_some() {
+            let weak = engine.weak();
+            std::thread::spawn(move || {
+                while let Some(engine) = weak.upgrade() {
+                    engine.increment_epoch();
+                    drop(engine);
+                    std::thread::sleep(EPOCH_TICK);
+                }
+            });
+        }
+        engine
+    }
+
+    fn store_limits(&self) -> StoreLimits {
+        let mut builder = StoreLimitsBuilder::new();
+        if let Some(max_memory) = self.max_memory {
+            builder = builder.memory_size(max_memory);
+        }
+        if let Some(max_table_elements) = self.max_table_elements {
+            builder = builder.table_elements(max_table_elements);
+        }
+        builder.trap_on_grow_failure(true).build()
+    }
+
+    fn epoch_ticks(&self) -> Option<u64> {
+        self.epoch_deadline
+            .map(|deadline| (deadline.as_millis() / EPOCH_TICK.as_millis()).max(1) as u64)
+    }
+}
+
+/// Compiled components, addressed by the SHA-256 digest of their bytes, so
+/// that switching back to a component does not compile it again. Clones share
+/// the same cache. With a directory, compiled components are also stored on
+/// disk, and survive restarts.
+#[derive(Clone)]
+pub struct ComponentCache {
+    engine: WasmEngine,
+    components: Arc<Mutex<HashMap<[u8; 32], Component>>>,
+    dir: Option<PathBuf>,
+}
+
+impl ComponentCache {
+    pub fn new(engine: &WasmEngine) -> Self {
+        Self {
+            engine: engine.clone(),
+            components: Arc::new(Mutex::new(HashMap::new())),
+            dir: None,
+        }
+    }
+
+    pub fn with_dir(engine: &WasmEngine, dir: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir: Some(dir), ..Self::new(engine) })
+    }
+
+    /// Returns the compiled component of `bytes`, and compiles it on a miss.
+    pub fn get(&self, bytes: &[u8]) -> wasmtime::Result<Component> {
+        let key: [u8; 32] = Sha256::digest(bytes).into();
+        if let Some(component) = self.components.lock().unwrap().get(&key) {
+            return Ok(component.clone());
+        }
+        let component = match &self.dir {
+            Some(dir) => {
+                let name = key.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+                self.load(dir.join(format!("{}.cwasm", name)), bytes)?
+            }
+            None => Component::from_binary(&self.engine, bytes)?,
+        };
+        self.components.lock().unwrap().insert(key, component.clone());
+        Ok(component)
+    }
+
+    fn load(&self, path: PathBuf, bytes: &[u8]) -> wasmtime::Result<Component> {
+        if path.exists() {
+            // SAFETY: The file was written by `Component::serialize` below. Wasmtime
+            // rejects files which were compiled by another version or configuration.
+            match unsafe { Component::deserialize_file(&self.engine, &path) } {
+                Ok(component) => return Ok(component),
+                Err(e) => tracing::warn!("Recompiling {}: {}", path.display(), e),
+            }
+        }
+        let component = Component::from_binary(&self.engine, bytes)?;
+        // A file is only ever seen complete, even if another process loads it
+        // while it is written, or the write is interrupted.
+        let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+        std::fs::write(&tmp, component.serialize()?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(component)
+    }
+}
+
+/// A call of a `WasmFunction` which exceeded its limits or trapped.
+#[data]
+pub struct WasmViolation {
+    pub pkg_name: String,
+    pub name: String,
+    pub error: String,
+}
+
 #[derive(Clone, Send, Sync, Timestamp)]
 pub struct WasmFunction<I, O> {
     store: Option<Rc<RefCell<Store<WasiImpl<Host>>>>>,
@@ -40,6 +178,12 @@ pub struct WasmFunction<I, O> {
     engine: WasmEngine,
     pkg_name: Option<String>,
     name: Option<String>,
+    limits: WasmLimits,
+    // The components before and after the last switch, to re-instantiate
+    // after a violation.
+    current: Option<(Component, String, String)>,
+    previous: Option<(Component, String, String)>,
+    cache: ComponentCache,
 }
 
 impl<I, O> Debug for WasmFunction<I, O> {
@@ -49,6 +193,7 @@ impl<I, O> Debug for WasmFunction<I, O> {
         .field("store", &self.store.iter().len())
         .field("pkg_name", &self.pkg_name)
         .field("name", &self.name)
+        .field("limits", &self.limits)
         .finish()
     }
 }
@@ -60,18 +205,24 @@ where
 {
     pub fn new(linker: &Linker<WasiImpl<Host>>, engine: &WasmEngine, guest_wasi_module: &[u8], pkg_name: &str, name: &str) -> Self {
         // eprintln!("{}", guest_wasi_module.len()); // 92192
-        let component = Component::from_binary(engine, guest_wasi_module).unwrap();
+        let cache = ComponentCache::new(engine);
+        let component = cache.get(guest_wasi_module).unwrap();
         // let a = component.serialize().unwrap();
         // eprintln!("{}", a.len()); // 341360
         // let clone_store_wrapper = store_wrapper.clone();
-        let (func, store) = Self::_get_func_from_component(linker, engine, &component, pkg_name, name);
+        let limits = WasmLimits::default();
+        let (func, store) = Self::_get_func_from_component(linker, engine, &component, pkg_name, name, &limits).unwrap();
         WasmFunction {
-            func,
-            store,
+            func: Some(func),
+            store: Some(store),
             linker: linker.clone(),
             engine: engine.clone(),
             pkg_name: Some(pkg_name.to_string()),
             name: Some(name.to_string()),
+            limits,
+            current: Some((component, pkg_name.to_string(), name.to_string())),
+            previous: None,
+            cache,
         }
     }
 
@@ -84,6 +235,10 @@ where
             engine: engine.clone(),
             pkg_name: None,
             name: None,
+            limits: WasmLimits::default(),
+            current: None,
+            previous: None,
+            cache: ComponentCache::new(engine),
         }
     }
 
@@ -96,24 +251,97 @@ where
             engine: engine.clone(),
             pkg_name: Some(pkg_name.to_string()),
             name: Some(name.to_string()),
+            limits: WasmLimits::default(),
+            current: None,
+            previous: None,
+            cache: ComponentCache::new(engine),
         }
     }
 
+    /// Enforces `limits` on this function and on the components it switches
+    /// to. The engine must come from `WasmLimits::engine`.
+    pub fn with_limits(mut self, limits: WasmLimits) -> Self {
+        self.limits = limits;
+        if let Err(e) = self.reinstantiate() {
+            panic!("Failed to instantiate {:?}: {}", self, e);
+        }
+        self
+    }
+
+    /// Compiles the components of later switches through `cache`, which may be
+    /// shared with other functions.
+    pub fn with_cache(mut self, cache: ComponentCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
     pub fn is_empty(&self) -> bool {
         self.func.is_none()
     }
 
     pub fn call(&self, input: I) -> O {
+        match self.try_call(input) {
+            Ok(result) => result,
+            Err(e) => panic!("Failed to call {:?}: {}", self, e),
+        }
+    }
+
+    /// Like `call`, but returns a trap of the guest as an error, which an
+    /// operator can return to cancel the dataflow.
+    pub fn try_call(&self, input: I) -> Result<O, Error> {
         match (self.func, self.store.clone()) {
             (Some(f), Some(s)) => {
-                let result = f.call(&mut *s.borrow_mut(), input).unwrap();
-                f.post_return(&mut *s.borrow_mut()).unwrap();
-                result
+                if let Some(fuel) = self.limits.fuel {
+                    s.borrow_mut().set_fuel(fuel).map_err(Error::custom)?;
+                }
+                if let Some(ticks) = self.limits.epoch_ticks() {
+                    s.borrow_mut().set_epoch_deadline(ticks);
+                }
+                let result = f.call(&mut *s.borrow_mut(), input).map_err(Error::custom)?;
+                f.post_return(&mut *s.borrow_mut()).map_err(Error::custom)?;
+                Ok(result)
             },
             _ => panic!("Function or/and store not found: {:?}", self),
         }
     }
 
+    /// Like `try_call`, but handles a violation of the limits, or any other
+    /// trap, according to `WasmLimits::on_violation`. Returns `Ok(None)` if the
+    /// record is dropped. An instance which has trapped cannot be entered
+    /// again, so it is replaced by a fresh instance.
+    pub fn call_limited(&mut self, input: I) -> Result<Option<O>, WasmViolation> {
+        match self.try_call(input) {
+            Ok(result) => Ok(Some(result)),
+            Err(e) => {
+                let violation = WasmViolation::new(
+                    self.pkg_name.clone().unwrap_or_default(),
+                    self.name.clone().unwrap_or_default(),
+                    e.to_string(),
+                );
+                if self.limits.on_violation == OnViolation::Revert && self.previous.is_some() {
+                    self.current = self.previous.take();
+                }
+                // Without a fresh instance, every later call would fail too.
+                if let Err(e) = self.reinstantiate() {
+                    let error = format!("{}, and failed to instantiate again: {}", violation.error, e);
+                    return Err(WasmViolation { error, ..violation });
+                }
+                match self.limits.on_violation {
+                    OnViolation::Error => Err(violation),
+                    OnViolation::Skip | OnViolation::Revert => Ok(None),
+                }
+            }
+        }
+    }
+
+    fn reinstantiate(&mut self) -> wasmtime::Result<()> {
+        if let Some((component, pkg_name, name)) = &self.current {
+            let (func, store) = Self::_get_func_from_component(&self.linker, &self.engine, component, pkg_name, name, &self.limits)?;
+            (self.func, self.store) = (Some(func), Some(store));
+        }
+        Ok(())
+    }
+
     pub fn switch_default(&mut self, guest_wasi_module: &[u8]) {
         let default_pkg_name = match self.pkg_name {
             Some(ref pkg_name) => pkg_name.clone(),
@@ -127,29 +355,114 @@ where
     }
 
     pub fn switch(&mut self, guest_wasi_module: &[u8], pkg_name: &str, name: &str) {
-        let component = Component::from_binary(&self.engine, guest_wasi_module).unwrap();
-        (self.func, self.store) = Self::_get_func_from_component(&self.linker, &self.engine, &component, pkg_name, name);
+        let component = self.cache.get(guest_wasi_module).unwrap();
+        let (func, store) = Self::_get_func_from_component(&self.linker, &self.engine, &component, pkg_name, name, &self.limits).unwrap();
+        (self.func, self.store) = (Some(func), Some(store));
+        self.previous = self.current.replace((component, pkg_name.to_string(), name.to_string()));
     }
 
-    fn _get_func_from_component(linker: &Linker<WasiImpl<Host>>, engine: &WasmEngine, component: &Component, pkg_name: &str, name: &str) -> (Option<wasmtime::component::TypedFunc<I, O>>, Option<Rc<RefCell<Store<WasiImpl<Host>>>>>) {
-        let host = Host::new();
+    fn _get_func_from_component(linker: &Linker<WasiImpl<Host>>, engine: &WasmEngine, component: &Component, pkg_name: &str, name: &str, limits: &WasmLimits) -> wasmtime::Result<(wasmtime::component::TypedFunc<I, O>, Rc<RefCell<Store<WasiImpl<Host>>>>)> {
+        let mut host = Host::new();
+        host.limits = limits.store_limits();
         let wi: WasiImpl<Host> = WasiImpl(wasmtime_wasi::IoImpl::<Host>(host));
         let store_wrapper: Rc<RefCell<Store<WasiImpl<Host>>>> = Rc::new(RefCell::new(Store::new(&engine, wi)));
         let n = store_wrapper.clone();
         let mut store = n.borrow_mut();
-        let instance = linker.instantiate(&mut *store, component).unwrap();
+        store.limiter(|wi| &mut wi.0.0.limits);
+        if let Some(fuel) = limits.fuel {
+            // Instantiation runs guest code too.
+            store.set_fuel(fuel)?;
+        }
+        if let Some(ticks) = limits.epoch_ticks() {
+            store.set_epoch_deadline(ticks);
+        }
+        let instance = linker.instantiate(&mut *store, component)?;
         let intf_export = instance
             .get_export(&mut *store, None, pkg_name)
-            .unwrap();
+            .ok_or_else(|| anyhow::anyhow!("Export {} not found", pkg_name))?;
         let func_export = instance
             .get_export(&mut *store, Some(&intf_export), name)
-            .unwrap();
-        (Some(instance
-            .get_typed_func::<I, O>(&mut *store, func_export)
-            .unwrap()), Some(store_wrapper))
+            .ok_or_else(|| anyhow::anyhow!("Export {}#{} not found", pkg_name, name))?;
+        let func = instance.get_typed_func::<I, O>(&mut *store, func_export)?;
+        drop(store);
+        Ok((func, store_wrapper))
     }
 }
 
+/// Calls `func` once per batch of records instead of once per record. A batch
+/// holds up to `batch_size` records, and is also flushed before a watermark,
+/// snapshot or the end of the stream. Each output is emitted at the time of
+/// the record at the same position of the batch, and `None` drops the record.
+pub fn wasm_batch<T, O>(
+    mut stream: Stream<T>,
+    ctx: &mut Context,
+    batch_size: usize,
+    func: WasmFunction<(Vec<T>,), (Vec<Option<O>>,)>,
+) -> Stream<O>
+where
+    T: Data + wasmtime::component::ComponentType + wasmtime::component::Lower,
+    O: Data + wasmtime::component::ComponentType + wasmtime::component::Lift,
+{
+    ctx.operator(move |tx| async move {
+        let mut times = Vec::with_capacity(batch_size);
+        let mut batch = Vec::with_capacity(batch_size);
+        loop {
+            match stream.recv().await {
+                stream::Event::Data(time, data) => {
+                    times.push(time);
+                    batch.push(data);
+                    if batch.len() >= batch_size {
+                        flush_batch(&func, &mut times, &mut batch, &tx).await?;
+                    }
+                }
+                stream::Event::Watermark(time) => {
+                    flush_batch(&func, &mut times, &mut batch, &tx).await?;
+                    tx.send(stream::Event::Watermark(time)).await?;
+                }
+                stream::Event::Snapshot(id) => {
+                    flush_batch(&func, &mut times, &mut batch, &tx).await?;
+                    tx.send(stream::Event::Snapshot(id)).await?;
+                }
+                stream::Event::Sentinel => {
+                    flush_batch(&func, &mut times, &mut batch, &tx).await?;
+                    tx.send(stream::Event::Sentinel).await?;
+                    break;
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+async fn flush_batch<T, O>(
+    func: &WasmFunction<(Vec<T>,), (Vec<Option<O>>,)>,
+    times: &mut Vec<Time>,
+    batch: &mut Vec<T>,
+    tx: &stream::Collector<O>,
+) -> Result<(), Error>
+where
+    T: Data + wasmtime::component::ComponentType + wasmtime::component::Lower,
+    O: Data + wasmtime::component::ComponentType + wasmtime::component::Lift,
+{
+    if batch.is_empty() {
+        return Ok(());
+    }
+    let (outputs,) = func.try_call((std::mem::take(batch),))?;
+    if outputs.len() != times.len() {
+        return Err(Error::custom(format!(
+            "Expected {} outputs, but the batch returned {}",
+            times.len(),
+            outputs.len()
+        )));
+    }
+    for (time, output) in times.drain(..).zip(outputs) {
+        if let Some(output) = output {
+            tx.send(stream::Event::Data(time, output)).await?;
+        }
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, Send, DeepClone, serde::Serialize, serde::Deserialize, Timestamp, New)]
 #[serde(crate = "runtime::prelude::serde")]
 pub struct WasmComponent {
@@ -177,3 +490,214 @@ where
     let encoded: String = Deserialize::deserialize(deserializer)?;
     STANDARD.decode(&encoded).map_err(serde::de::Error::custom)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `spin` loops forever unless its argument is zero, and `grow` grows the
+    // memory by its argument in pages.
+    const LIMITS: &str = r#"
+(component
+  (core module $m
+    (memory (export "memory") 1)
+    (func (export "spin") (param i32) (result i32)
+      (block $done
+        (loop $l
+          (br_if $done (i32.eqz (local.get 0)))
+          (br $l)))
+      (i32.const 0))
+    (func (export "grow") (param i32) (result i32)
+      (memory.grow (local.get 0))))
+  (core instance $i (instantiate $m))
+  (func $spin (param "x" u32) (result u32) (canon lift (core func $i "spin")))
+  (func $grow (param "x" u32) (result u32) (canon lift (core func $i "grow")))
+  (instance $funcs (export "spin" (func $spin)) (export "grow" (func $grow)))
+  (export "test:limits/funcs" (instance $funcs))
+)
+"#;
+
+    // Like `LIMITS`, but every call traps.
+    const TRAP: &str = r#"
+(component
+  (core module $m
+    (func (export "spin") (param i32) (result i32)
+      unreachable))
+  (core instance $i (instantiate $m))
+  (func $spin (param "x" u32) (result u32) (canon lift (core func $i "spin")))
+  (instance $funcs (export "spin" (func $spin)))
+  (export "test:limits/funcs" (instance $funcs))
+)
+"#;
+
+    // `keep` returns every element of its list, and `drop` returns an empty
+    // list whatever its input.
+    const BATCH: &str = r#"
+(component
+  (core module $m
+    (memory (export "memory") 1)
+    (global $next (mut i32) (i32.const 32768))
+    (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+      (local $p i32)
+      (local.set $p (i32.and (i32.add (global.get $next) (i32.const 7)) (i32.const -8)))
+      (global.set $next (i32.add (local.get $p) (local.get 3)))
+      (local.get $p))
+    (func (export "keep") (param $xs i32) (param $n i32) (result i32)
+      (local $i i32)
+      (block $done
+        (loop $l
+          (br_if $done (i32.ge_u (local.get $i) (local.get $n)))
+          (i32.store8 (i32.add (i32.const 1024) (i32.mul (local.get $i) (i32.const 8))) (i32.const 1))
+          (i32.store
+            (i32.add (i32.const 1028) (i32.mul (local.get $i) (i32.const 8)))
+            (i32.load (i32.add (local.get $xs) (i32.mul (local.get $i) (i32.const 4)))))
+          (local.set $i (i32.add (local.get $i) (i32.const 1)))
+          (br $l)))
+      (i32.store (i32.const 16) (i32.const 1024))
+      (i32.store (i32.const 20) (local.get $n))
+      (i32.const 16))
+    (func (export "drop") (param i32 i32) (result i32)
+      (i32.store (i32.const 16) (i32.const 1024))
+      (i32.store (i32.const 20) (i32.const 0))
+      (i32.const 16)))
+  (core instance $i (instantiate $m))
+  (func $keep (param "xs" (list u32)) (result (list (option u32)))
+    (canon lift (core func $i "keep") (memory $i "memory") (realloc (func $i "realloc"))))
+  (func $drop (param "xs" (list u32)) (result (list (option u32)))
+    (canon lift (core func $i "drop") (memory $i "memory") (realloc (func $i "realloc"))))
+  (instance $funcs (export "keep" (func $keep)) (export "drop" (func $drop)))
+  (export "test:batch/funcs" (instance $funcs))
+)
+"#;
+
+    fn function(name: &str, limits: WasmLimits) -> WasmFunction<(u32,), (u32,)> {
+        let engine = limits.engine();
+        let linker = Linker::new(&engine);
+        let bytes = wat::parse_str(LIMITS).unwrap();
+        WasmFunction::new(&linker, &engine, &bytes, "test:limits/funcs", name).with_limits(limits)
+    }
+
+    fn batch(name: &str, n: u32, batch_size: usize) -> (Result<(), Error>, Vec<u32>) {
+        let engine = WasmEngine::default();
+        let linker = Linker::new(&engine);
+        let bytes = wat::parse_str(BATCH).unwrap();
+        let func = WasmFunction::new(&linker, &engine, &bytes, "test:batch/funcs", name);
+        let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+        let result = CurrentThreadRunner::run(|ctx| {
+            let stream = Stream::from_iter(ctx, 0..n, |i| Time::from_seconds(*i as i64), 1, Duration::zero());
+            wasm_batch(stream, ctx, batch_size, func).collect_vec(ctx, tx);
+        });
+        (result, rx.try_recv().unwrap_or_default())
+    }
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn files(dir: &std::path::Path) -> Vec<PathBuf> {
+        std::fs::read_dir(dir).unwrap().map(|entry| entry.unwrap().path()).collect()
+    }
+
+    #[test]
+    fn test_cache_hit() {
+        let cache = ComponentCache::new(&WasmEngine::default());
+        let limits = wat::parse_str(LIMITS).unwrap();
+        let trap = wat::parse_str(TRAP).unwrap();
+        cache.get(&limits).unwrap();
+        cache.get(&limits).unwrap();
+        assert_eq!(cache.components.lock().unwrap().len(), 1);
+        cache.get(&trap).unwrap();
+        assert_eq!(cache.components.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_cache_dir() {
+        let dir = cache_dir("host-test-cache-dir");
+        let engine = WasmEngine::default();
+        let bytes = wat::parse_str(LIMITS).unwrap();
+        ComponentCache::with_dir(&engine, &dir).unwrap().get(&bytes).unwrap();
+        let [path] = files(&dir).try_into().unwrap();
+        assert_eq!(path.extension().unwrap(), "cwasm");
+        // A corrupt file is compiled again and replaced.
+        std::fs::write(&path, b"garbage").unwrap();
+        ComponentCache::with_dir(&engine, &dir).unwrap().get(&bytes).unwrap();
+        assert_eq!(files(&dir), [path.as_path()]);
+        assert!(unsafe { Component::deserialize_file(&engine, &path) }.is_ok());
+    }
+
+    #[test]
+    fn test_cache_dir_error() {
+        let file = cache_dir("host-test-cache-dir-error");
+        std::fs::write(&file, b"").unwrap();
+        assert!(ComponentCache::with_dir(&WasmEngine::default(), file.join("cache")).is_err());
+    }
+
+    #[test]
+    fn test_fuel_exhaustion() {
+        let limits = WasmLimits { fuel: Some(10_000), on_violation: OnViolation::Error, ..Default::default() };
+        let mut f = function("spin", limits);
+        let violation = f.call_limited((1,)).unwrap_err();
+        assert_eq!(violation.name, "spin");
+        // The trapped instance is replaced, and the fuel is refilled for each call.
+        assert_eq!(f.call_limited((0,)).unwrap(), Some((0,)));
+    }
+
+    #[test]
+    fn test_epoch_deadline() {
+        let limits = WasmLimits {
+            epoch_deadline: Some(std::time::Duration::from_millis(50)),
+            on_violation: OnViolation::Error,
+            ..Default::default()
+        };
+        let mut f = function("spin", limits);
+        assert!(f.call_limited((1,)).is_err());
+        assert_eq!(f.call_limited((0,)).unwrap(), Some((0,)));
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        let limits = WasmLimits { max_memory: Some(2 * 65536), on_violation: OnViolation::Error, ..Default::default() };
+        let mut f = function("grow", limits);
+        // `memory.grow` returns the previous size in pages.
+        assert_eq!(f.call_limited((1,)).unwrap(), Some((1,)));
+        assert!(f.call_limited((1,)).is_err());
+        // The fresh instance starts with a single page again.
+        assert_eq!(f.call_limited((1,)).unwrap(), Some((1,)));
+    }
+
+    #[test]
+    fn test_skip() {
+        let limits = WasmLimits { fuel: Some(10_000), ..Default::default() };
+        let mut f = function("spin", limits);
+        assert_eq!(f.call_limited((1,)).unwrap(), None);
+        assert_eq!(f.call_limited((0,)).unwrap(), Some((0,)));
+    }
+
+    #[test]
+    fn test_revert() {
+        let limits = WasmLimits { fuel: Some(10_000), on_violation: OnViolation::Revert, ..Default::default() };
+        let mut f = function("spin", limits);
+        f.switch_default(&wat::parse_str(TRAP).unwrap());
+        assert_eq!(f.call_limited((0,)).unwrap(), None);
+        assert_eq!(f.call_limited((0,)).unwrap(), Some((0,)));
+        // Once reverted, a violation does not switch back to the trapping component.
+        assert_eq!(f.call_limited((1,)).unwrap(), None);
+        assert_eq!(f.call_limited((0,)).unwrap(), Some((0,)));
+    }
+
+    #[test]
+    fn test_batch() {
+        let (result, output) = batch("keep", 5, 2);
+        result.unwrap();
+        assert_eq!(output, [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_batch_length_mismatch() {
+        let (result, output) = batch("drop", 5, 2);
+        assert!(matches!(result, Err(Error::Custom(e)) if e.to_string().contains("Expected 2 outputs")));
+        assert!(output.is_empty());
+    }
+}
@@ -28,6 +28,7 @@ use either::EitherData;
 use runtime::prelude::stream::Event;
 use crate::wasm::WasmComponent;
 use runtime::prelude::serde::de::DeserializeOwned;
+use runtime::formats::columnar;
 use runtime::prelude::*;
 use runtime::traits::Timestamp;
 use wasm::Host;
@@ -61,10 +62,10 @@ fn main() {
         return;
     };
 
-    let bids = std::fs::File::open(&format!("{dir}/bids.csv")).map(iter::<Bid>);
-    let auctions = std::fs::File::open(&format!("{dir}/auctions.csv")).map(iter::<Auction>);
-    let persons = std::fs::File::open(&format!("{dir}/persons.csv")).map(iter::<Person>);
-    let components_bids = std::fs::File::open(&format!("{dir}/component_bids.csv")).map(iter::<WasmComponent>);
+    let bids = open::<Bid>(&dir, "bids");
+    let auctions = open::<Auction>(&dir, "auctions");
+    let persons = open::<Person>(&dir, "persons");
+    let components_bids = open::<WasmComponent>(&dir, "component_bids");
 
     let config = Config::new();
     // config.async_support(true);
@@ -105,7 +106,10 @@ fn main() {
 
     fn timed(f: impl FnOnce(&mut Context) + Send + 'static) {
         let time = std::time::Instant::now();
-        CurrentThreadRunner::run(f);
+        if let Err(e) = CurrentThreadRunner::run(f) {
+            eprintln!("Query failed: {}", e);
+            std::process::exit(1);
+        }
         eprintln!("{}", time.elapsed().as_millis());
     }
 
@@ -269,8 +273,19 @@ fn main() {
     }
 }
 
+// Prefers the columnar files written by `data-generator --format parquet|arrow`
+fn open<T: Data + DeserializeOwned + 'static>(
+    dir: &str,
+    name: &str,
+) -> std::io::Result<Box<dyn Iterator<Item = Result<T, Error>> + Send>> {
+    if let Some(records) = columnar::find::<T>(std::path::Path::new(dir), name) {
+        return records.map(|iter| Box::new(iter) as _);
+    }
+    File::open(format!("{dir}/{name}.csv")).map(|file| Box::new(iter::<T>(file)) as _)
+}
+
 // Buffered CSV reader
-fn iter<T: Data + DeserializeOwned + 'static>(file: File) -> impl Iterator<Item = T> {
+fn iter<T: Data + DeserializeOwned + 'static>(file: File) -> impl Iterator<Item = Result<T, Error>> {
     let reader = BufReader::new(file);
     let csv_reader = ReaderBuilder::new()
         .has_headers(false)
@@ -278,29 +293,22 @@ fn iter<T: Data + DeserializeOwned + 'static>(file: File) -> impl Iterator<Item
         .from_reader(reader);
 
     csv_reader
-        .into_deserialize::<T>() 
-        .map(move |result| match result {
-            Ok(data) => {
-                data
-            },
-            Err(e) => {
-                panic!("CSV deserialization failed: {:?}", e);
-            }
-        })
+        .into_deserialize::<T>()
+        .map(|result| result.map_err(Error::custom))
 }
 
-// Stream from iterator
+// Stream from iterator, which fails at the first record that cannot be read
 fn stream_with<T: Data + Timestamp>(
     ctx: &mut Context,
-    iter: std::io::Result<impl Iterator<Item = T> + Send + 'static>,
+    iter: std::io::Result<impl Iterator<Item = Result<T, Error>> + Send + 'static>,
     frequency: usize,
 ) -> Stream<T> {
-    Stream::from_iter(ctx, iter.unwrap(), T::timestamp, frequency, SLACK)
+    Stream::try_from_iter(ctx, iter.unwrap(), T::timestamp, frequency, SLACK)
 }
 
 fn stream<T: Data + Timestamp>(
     ctx: &mut Context,
-    iter: std::io::Result<impl Iterator<Item = T> + Send + 'static>,
+    iter: std::io::Result<impl Iterator<Item = Result<T, Error>> + Send + 'static>,
 ) -> Stream<T> {
     stream_with(ctx, iter, WATERMARK_FREQUENCY)
 }
@@ -24,9 +24,9 @@ pub fn run_opt(bids: Stream<Bid>, ctx: &mut Context) {
 }
 
 pub fn run_wasm(bids: Stream<Bid>, ctx: &mut Context, wasm_func: WasmFunction<(u64, u64, u64, u64), ((u64, u64, u64, u64),)>) {
-    bids.map(ctx, move |bid| {
-        let ((auction, price, bidder, date_time),) = wasm_func.call((bid.auction, bid.price, bid.bidder, bid.date_time));
-        Output::new(auction, price, bidder, date_time)
+    bids.try_map(ctx, move |bid| {
+        let ((auction, price, bidder, date_time),) = wasm_func.try_call((bid.auction, bid.price, bid.bidder, bid.date_time))?;
+        Ok::<_, Error>(Output::new(auction, price, bidder, date_time))
     })
     .drain(ctx);
 }
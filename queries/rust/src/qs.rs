@@ -27,7 +27,7 @@ pub fn run_wasm_operator(
                             match data {
                                 EitherData::Auction(_auction) => todo!(),
                                 EitherData::Bid(bid) => match func.is_empty() {
-                                    false => tx.send(Event::Data(time, func.call((bid.clone(),)).0)).await?,
+                                    false => tx.send(Event::Data(time, func.try_call((bid.clone(),))?.0)).await?,
                                     true => tx.send(Event::Data(time, None)).await?,
                                 },
                                 EitherData::Person(_person) => todo!(),
@@ -72,7 +72,7 @@ T: Clone + Unpin + for<'a> runtime::prelude::serde::Deserialize<'a> + runtime::p
                         },
                         Either::Data(data) => {
                             match func.is_empty() {
-                                false => tx.send(Event::Data(time, func.call((data.clone(),)).0)).await?,
+                                false => tx.send(Event::Data(time, func.try_call((data.clone(),))?.0)).await?,
                                 true => tx.send(Event::Data(time, None)).await?,
                             }
                         },
@@ -113,7 +113,7 @@ pub fn run_wasm_operator_g(
                         },
                         Either::Data(data) => {
                             match func.is_empty() {
-                                false => tx.send(Event::Data(time, func.call((data.clone(),)).0)).await?,
+                                false => tx.send(Event::Data(time, func.try_call((data.clone(),))?.0)).await?,
                                 true => tx.send(Event::Data(time, None)).await?,
                             }
                         },
@@ -90,11 +90,20 @@ where
     }
 
     pub fn call(&self, input: I) -> O {
+        match self.try_call(input) {
+            Ok(result) => result,
+            Err(e) => panic!("Failed to call {:?}: {}", self, e),
+        }
+    }
+
+    /// Like `call`, but returns a trap of the guest as an error, which an
+    /// operator can return to cancel the dataflow.
+    pub fn try_call(&self, input: I) -> Result<O, Error> {
         match self.func {
             Some(f) => {
-                let result = f.call(&mut *self.store.borrow_mut(), input).unwrap();
-                f.post_return(&mut *self.store.borrow_mut()).unwrap();
-                result
+                let result = f.call(&mut *self.store.borrow_mut(), input).map_err(Error::custom)?;
+                f.post_return(&mut *self.store.borrow_mut()).map_err(Error::custom)?;
+                Ok(result)
             },
             None => panic!("Function not found: {:?}", self),
         }
@@ -0,0 +1,107 @@
+//! Measures how long each format takes to split and decode a stream of
+//! records, which bounds the throughput of the IO-only baselines.
+
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::Throughput;
+use runtime::formats::bincode;
+use runtime::formats::cbor;
+use runtime::formats::csv;
+use runtime::formats::json;
+use runtime::formats::msgpack;
+use runtime::formats::Decode;
+use runtime::formats::Encode;
+use runtime::formats::Framing;
+use serde::Deserialize;
+use serde::Serialize;
+
+const RECORDS: u64 = 10_000;
+
+// Shaped like a Nexmark bid.
+#[derive(Debug, Serialize, Deserialize)]
+struct Bid {
+    auction: u64,
+    bidder: u64,
+    price: u64,
+    channel: String,
+    url: String,
+    date_time: u64,
+    extra: String,
+}
+
+fn bids() -> impl Iterator<Item = Bid> {
+    (0..RECORDS).map(|i| Bid {
+        auction: i % 100,
+        bidder: i % 1000,
+        price: i * 7,
+        channel: "Google".to_string(),
+        url: format!("https://www.nexmark.com/item.htm?query={i}"),
+        date_time: 1_000_000 + i,
+        extra: "".to_string(),
+    })
+}
+
+fn encode(mut encoder: impl Encode) -> Vec<u8> {
+    let mut input = Vec::new();
+    let mut buf = vec![0; 1024];
+    for bid in bids() {
+        let n = encoder.encode(&bid, &mut buf).unwrap();
+        input.extend_from_slice(&buf[..n]);
+    }
+    input
+}
+
+fn decode(mut decoder: impl Decode, framing: Framing, mut input: &[u8]) {
+    while let Some(n) = framing.split(input) {
+        let bid: Bid = decoder.decode(&input[..n]).unwrap();
+        std::hint::black_box(bid);
+        input = &input[n..];
+    }
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode");
+    group.throughput(Throughput::Elements(RECORDS));
+
+    let input = encode(csv::ser::Writer::new(','));
+    group.bench_with_input(BenchmarkId::new("csv", input.len()), &input, |b, input| {
+        b.iter(|| decode(csv::de::Reader::<1024>::new(','), Framing::Newline, input))
+    });
+
+    let input = bids()
+        .flat_map(|bid| {
+            let mut line = serde_json::to_vec(&bid).unwrap();
+            line.push(b'\n');
+            line
+        })
+        .collect::<Vec<_>>();
+    group.bench_with_input(BenchmarkId::new("json", input.len()), &input, |b, input| {
+        b.iter(|| decode(json::de::Reader::new(), Framing::Json, input))
+    });
+
+    let input = encode(msgpack::ser::Writer::new());
+    group.bench_with_input(
+        BenchmarkId::new("msgpack", input.len()),
+        &input,
+        |b, input| b.iter(|| decode(msgpack::de::Reader::new(), Framing::LengthPrefixed, input)),
+    );
+
+    let input = encode(cbor::ser::Writer::new());
+    group.bench_with_input(BenchmarkId::new("cbor", input.len()), &input, |b, input| {
+        b.iter(|| decode(cbor::de::Reader::new(), Framing::LengthPrefixed, input))
+    });
+
+    let input = encode(bincode::ser::Writer::new());
+    group.bench_with_input(
+        BenchmarkId::new("bincode", input.len()),
+        &input,
+        |b, input| b.iter(|| decode(bincode::de::Reader::new(), Framing::LengthPrefixed, input)),
+    );
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_decode);
+criterion_main!(benches);
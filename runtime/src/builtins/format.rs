@@ -1,6 +1,7 @@
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::formats::Framing;
 use crate::traits::DeepClone;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -8,6 +9,9 @@ use crate::traits::DeepClone;
 pub enum Format {
     Csv { sep: char },
     Json,
+    MessagePack,
+    Cbor,
+    Bincode,
 }
 
 impl std::fmt::Display for Format {
@@ -15,6 +19,9 @@ impl std::fmt::Display for Format {
         match self {
             Format::Csv { sep } => write!(f, "Csv({})", sep),
             Format::Json => write!(f, "Json"),
+            Format::MessagePack => write!(f, "MessagePack"),
+            Format::Cbor => write!(f, "Cbor"),
+            Format::Bincode => write!(f, "Bincode"),
         }
     }
 }
@@ -32,4 +39,22 @@ impl Format {
     pub fn json() -> Self {
         Self::Json
     }
+    pub fn msgpack() -> Self {
+        Self::MessagePack
+    }
+    pub fn cbor() -> Self {
+        Self::Cbor
+    }
+    pub fn bincode() -> Self {
+        Self::Bincode
+    }
+
+    /// Text formats are newline-delimited, and binary formats length-prefixed.
+    pub fn framing(&self) -> Framing {
+        match self {
+            Format::Csv { .. } => Framing::Newline,
+            Format::Json => Framing::Json,
+            Format::MessagePack | Format::Cbor | Format::Bincode => Framing::LengthPrefixed,
+        }
+    }
 }
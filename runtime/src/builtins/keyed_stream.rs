@@ -7,7 +7,9 @@ mod join;
 mod keyby;
 mod map;
 mod merge;
+pub mod process;
 mod scan;
+mod shuffle;
 mod sink;
 mod unkey;
 mod window;
@@ -16,12 +18,12 @@ use tokio::sync::mpsc::Receiver;
 use tokio::sync::mpsc::Sender;
 
 use crate::builtins::time::Time;
+use crate::error::Error;
 use crate::traits::Data;
 use crate::traits::Key;
 use serde::Deserialize;
 use serde::Serialize;
 
-use super::stream::SendError;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum KeyedEvent<K, T> {
@@ -42,8 +44,8 @@ impl<K: Key, T: Data> KeyedStream<K, T> {
 }
 
 impl<K: Data, T: Data> KeyedCollector<K, T> {
-    pub async fn send(&self, event: KeyedEvent<K, T>) -> Result<(), SendError> {
-        self.0.send(event).await.map_err(|_| SendError::Closed)
+    pub async fn send(&self, event: KeyedEvent<K, T>) -> Result<(), Error> {
+        self.0.send(event).await.map_err(|_| Error::Closed)
     }
 }
 
@@ -7,10 +7,13 @@ use super::KeyedStream;
 
 impl<K: Key, T: Data> KeyedStream<K, T> {
     pub fn drain(mut self, ctx: &mut Context) {
+        let state = ctx.sink_state();
         ctx.sink(|| async move {
             loop {
-                if let KeyedEvent::Sentinel = self.recv().await {
-                    break;
+                match self.recv().await {
+                    KeyedEvent::Snapshot(i) => state.ack(i),
+                    KeyedEvent::Sentinel => break,
+                    _ => {}
                 }
             }
             Ok(())
@@ -1,12 +1,20 @@
-use crate::HashMap;
 use crate::BTreeMap;
+use crate::HashMap;
 
+use std::collections::btree_map;
 use std::collections::hash_map::Entry;
+use std::collections::VecDeque;
 use std::ops::Range;
 
-use crate::builtins::window::Window;
+use serde::Deserialize;
+use serde::Serialize;
+
 use crate::builtins::duration::Duration;
+use crate::builtins::lateness::FireOnceLateness;
+use crate::builtins::stream::window::align;
+use crate::builtins::stream::window::Sessions;
 use crate::builtins::time::Time;
+use crate::builtins::window::Window;
 use crate::runner::context::Context;
 use crate::traits::Data;
 use crate::traits::Key;
@@ -14,13 +22,32 @@ use crate::traits::Key;
 use super::KeyedEvent;
 use super::KeyedStream;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct Properties {
+/// Algebraic properties of the `combine` function of an incremental window.
+#[derive(Debug)]
+pub struct Properties<P> {
     pub associative: bool,
     pub commutative: bool,
+    /// Removes a partial aggregate from another, such that
+    /// `inverse(combine(a, b), a) == b`.
+    pub inverse: Option<fn(&P, &P) -> P>,
+}
+
+impl<P> Clone for Properties<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
+impl<P> Copy for Properties<P> {}
+
 impl<K: Key, T: Data> KeyedStream<K, T> {
+    /// Incrementally aggregates each window, choosing an algorithm from the
+    /// properties of `combine`:
+    /// * Commutative: Partial aggregates are combined as data arrives.
+    /// * Invertible: Sliding windows subtract evicted data from a running
+    ///   aggregate.
+    /// * Associative: Sliding windows evict from a two-stacks queue.
+    /// * Neither: Windows are recomputed from the buffered data.
     pub fn incr_window<P, O>(
         self,
         ctx: &mut Context,
@@ -28,24 +55,90 @@ impl<K: Key, T: Data> KeyedStream<K, T> {
         lift: impl Fn(&T) -> P + Send + 'static,
         combine: impl Fn(&P, P) -> P + Send + 'static,
         lower: impl Fn(K, &P, Range<Time>) -> O + Send + 'static,
-        properties: Properties,
+        properties: Properties<P>,
     ) -> KeyedStream<K, O>
     where
         P: Data,
         O: Data,
     {
+        self.incr_window_with_lateness(
+            ctx,
+            assigner,
+            FireOnceLateness::Drop,
+            lift,
+            combine,
+            lower,
+            properties,
+        )
+        .0
+    }
+
+    /// Like `incr_window`, but events whose last window has already fired are
+    /// handled by the lateness policy. The second stream is the late output,
+    /// which stays empty for count-based windows.
+    #[allow(clippy::too_many_arguments)]
+    pub fn incr_window_with_lateness<P, O>(
+        self,
+        ctx: &mut Context,
+        assigner: Window,
+        lateness: FireOnceLateness,
+        lift: impl Fn(&T) -> P + Send + 'static,
+        combine: impl Fn(&P, P) -> P + Send + 'static,
+        lower: impl Fn(K, &P, Range<Time>) -> O + Send + 'static,
+        properties: Properties<P>,
+    ) -> (KeyedStream<K, O>, KeyedStream<K, T>)
+    where
+        P: Data,
+        O: Data,
+    {
+        let strategy = Strategy::of(&properties);
+        let inverse = properties.inverse;
         match assigner {
             Window::Tumbling { length } => {
                 if properties.commutative {
-                    self.commutative_tumbling_window(ctx, length, lift, combine, lower)
+                    self.commutative_tumbling_window(ctx, length, lateness, lift, combine, lower)
                 } else {
-                    todo!()
+                    // A tumbling window is a sliding window whose step is its length.
+                    let pane = None;
+                    self.sliding_window(
+                        ctx, length, length, lateness, pane, strategy, lift, combine, inverse,
+                        lower,
+                    )
                 }
             }
-            Window::Sliding { .. } => todo!(),
-            Window::Session { .. } => todo!(),
-            Window::Counting { .. } => todo!(),
-            Window::Moving { .. } => todo!(),
+            Window::Sliding { duration, step } => {
+                let pane = (properties.commutative && properties.associative)
+                    .then_some(step)
+                    .filter(|step| duration.nanoseconds() % step.nanoseconds() == 0);
+                self.sliding_window(
+                    ctx, duration, step, lateness, pane, strategy, lift, combine, inverse, lower,
+                )
+            }
+            Window::Session { gap } => {
+                if properties.commutative {
+                    self.commutative_session_window(ctx, gap, lateness, lift, combine, lower)
+                } else {
+                    self.session_window(ctx, gap, lateness, lift, combine, lower)
+                }
+            }
+            Window::Counting { length } => {
+                assert!(length > 0, "Counting window length must be positive");
+                self.counting_window(ctx, length as usize, lift, combine, lower)
+            }
+            Window::Moving { length, step } => {
+                assert!(length > 0, "Moving window length must be positive");
+                assert!(step > 0, "Moving window step must be positive");
+                self.moving_window(
+                    ctx,
+                    length as usize,
+                    step as usize,
+                    strategy,
+                    lift,
+                    combine,
+                    inverse,
+                    lower,
+                )
+            }
         }
     }
 
@@ -53,20 +146,30 @@ impl<K: Key, T: Data> KeyedStream<K, T> {
         mut self,
         ctx: &mut Context,
         duration: Duration,
+        lateness: FireOnceLateness,
         lift: impl Fn(&T) -> P + Send + 'static,
         combine: impl Fn(&P, P) -> P + Send + 'static,
         lower: impl Fn(K, &P, Range<Time>) -> O + Send + 'static,
-    ) -> KeyedStream<K, O>
+    ) -> (KeyedStream<K, O>, KeyedStream<K, T>)
     where
         P: Data,
         O: Data,
     {
-        ctx.keyed_operator(move |tx1| async move {
-            let mut aggs: BTreeMap<Time, HashMap<K, P>> = BTreeMap::new();
+        let state = ctx.operator_state();
+        ctx.keyed_co_operator(move |tx1, late| async move {
+            let (mut aggs, mut watermark): (BTreeMap<Time, HashMap<K, P>>, Time) = state
+                .restore()
+                .unwrap_or_else(|| (BTreeMap::new(), Time::zero()));
             loop {
                 match self.recv().await {
                     KeyedEvent::Data(time, key, data) => {
                         let t0 = time.div_floor(duration) * duration;
+                        if t0 + duration < watermark {
+                            if lateness.routes_late() {
+                                late.send(KeyedEvent::Data(time, key, data)).await.ok();
+                            }
+                            continue;
+                        }
                         let data = lift(&data);
                         match aggs.entry(t0).or_default().entry(key) {
                             Entry::Occupied(mut entry) => {
@@ -81,22 +184,220 @@ impl<K: Key, T: Data> KeyedStream<K, T> {
                         while let Some(entry) = aggs.first_entry() {
                             let t0 = *entry.key();
                             let t1 = t0 + duration;
-                            if t1 < time {
-                                for (key, p) in entry.remove() {
+                            if t1 >= time {
+                                break;
+                            }
+                            for (key, p) in entry.remove() {
+                                let data = lower(key.clone(), &p, t0..t1);
+                                tx1.send(KeyedEvent::Data(t1, key, data)).await?;
+                            }
+                        }
+                        // The windows closed by a watermark precede it, which
+                        // is only forwarded if it advances.
+                        if time > watermark {
+                            watermark = time;
+                            tx1.send(KeyedEvent::Watermark(time)).await?;
+                            late.send(KeyedEvent::Watermark(time)).await.ok();
+                        }
+                    }
+                    KeyedEvent::Snapshot(i) => {
+                        state.snapshot(i, &(&aggs, watermark));
+                        tx1.send(KeyedEvent::Snapshot(i)).await?;
+                        late.send(KeyedEvent::Snapshot(i)).await.ok();
+                    }
+                    KeyedEvent::Sentinel => {
+                        late.send(KeyedEvent::Sentinel).await.ok();
+                        tx1.send(KeyedEvent::Sentinel).await?;
+                        break;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn commutative_session_window<P, O>(
+        self,
+        ctx: &mut Context,
+        gap: Duration,
+        lateness: FireOnceLateness,
+        lift: impl Fn(&T) -> P + Send + 'static,
+        combine: impl Fn(&P, P) -> P + Send + 'static,
+        lower: impl Fn(K, &P, Range<Time>) -> O + Send + 'static,
+    ) -> (KeyedStream<K, O>, KeyedStream<K, T>)
+    where
+        P: Data,
+        O: Data,
+    {
+        self.time_session_commutative_associative_window_with_lateness(
+            ctx,
+            gap,
+            lateness,
+            lift,
+            move |a, b| combine(a, b.clone()),
+            move |key, p, wr| lower(key.clone(), p, wr.t0..wr.t1),
+        )
+    }
+
+    // Sessions may be merged by out-of-order events, so data is buffered until
+    // the watermark passes it. It is then combined into the partial aggregate
+    // of its session in event-time order.
+    fn session_window<P, O>(
+        mut self,
+        ctx: &mut Context,
+        gap: Duration,
+        lateness: FireOnceLateness,
+        lift: impl Fn(&T) -> P + Send + 'static,
+        combine: impl Fn(&P, P) -> P + Send + 'static,
+        lower: impl Fn(K, &P, Range<Time>) -> O + Send + 'static,
+    ) -> (KeyedStream<K, O>, KeyedStream<K, T>)
+    where
+        P: Data,
+        O: Data,
+    {
+        let state = ctx.operator_state();
+        ctx.keyed_co_operator(move |tx1, late| async move {
+            let (mut sessions, mut watermark): (HashMap<K, SessionState<P>>, Time) = state
+                .restore()
+                .unwrap_or_else(|| (HashMap::default(), Time::zero()));
+            loop {
+                match self.recv().await {
+                    KeyedEvent::Data(time, key, data) => {
+                        let s = sessions.entry(key.clone()).or_default();
+                        if s.sessions.is_late(time, gap, watermark) {
+                            if lateness.routes_late() {
+                                late.send(KeyedEvent::Data(time, key, data)).await.ok();
+                            }
+                            continue;
+                        }
+                        s.pending.entry(time).or_default().push(lift(&data));
+                    }
+                    KeyedEvent::Watermark(time) => {
+                        for (key, s) in sessions.iter_mut() {
+                            let after = s.pending.split_off(&time);
+                            let before = std::mem::replace(&mut s.pending, after);
+                            for (t, ps) in before {
+                                for p in ps {
+                                    s.sessions.insert(t, gap, p, |a, b| combine(&a, b));
+                                }
+                            }
+                            for (wr, p) in s.sessions.expire(time) {
+                                let data = lower(key.clone(), &p, wr.t0..wr.t1);
+                                tx1.send(KeyedEvent::Data(wr.t1, key.clone(), data)).await?;
+                            }
+                        }
+                        sessions.retain(|_, s| !s.pending.is_empty() || !s.sessions.is_empty());
+                        watermark = time;
+                        tx1.send(KeyedEvent::Watermark(time)).await?;
+                        late.send(KeyedEvent::Watermark(time)).await.ok();
+                    }
+                    KeyedEvent::Snapshot(i) => {
+                        state.snapshot(i, &(&sessions, watermark));
+                        tx1.send(KeyedEvent::Snapshot(i)).await?;
+                        late.send(KeyedEvent::Snapshot(i)).await.ok();
+                    }
+                    KeyedEvent::Sentinel => {
+                        late.send(KeyedEvent::Sentinel).await.ok();
+                        tx1.send(KeyedEvent::Sentinel).await?;
+                        break;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    // Data is buffered until the watermark passes it, and then inserted into a
+    // FIFO aggregator in event-time order. If `pane` is set, data within the
+    // same pane is combined on arrival.
+    #[allow(clippy::too_many_arguments)]
+    fn sliding_window<P, O>(
+        mut self,
+        ctx: &mut Context,
+        duration: Duration,
+        step: Duration,
+        lateness: FireOnceLateness,
+        pane: Option<Duration>,
+        strategy: Strategy,
+        lift: impl Fn(&T) -> P + Send + 'static,
+        combine: impl Fn(&P, P) -> P + Send + 'static,
+        inverse: Option<fn(&P, &P) -> P>,
+        lower: impl Fn(K, &P, Range<Time>) -> O + Send + 'static,
+    ) -> (KeyedStream<K, O>, KeyedStream<K, T>)
+    where
+        P: Data,
+        O: Data,
+    {
+        let state = ctx.operator_state();
+        ctx.keyed_co_operator(move |tx1, late| async move {
+            let (mut windows, mut watermark): (HashMap<K, SlidingState<P>>, Time) = state
+                .restore()
+                .unwrap_or_else(|| (HashMap::default(), Time::zero()));
+            loop {
+                match self.recv().await {
+                    KeyedEvent::Data(time, key, data) => {
+                        if align(time, step) + duration < watermark {
+                            if lateness.routes_late() {
+                                late.send(KeyedEvent::Data(time, key, data)).await.ok();
+                            }
+                            continue;
+                        }
+                        let data = lift(&data);
+                        let window = windows
+                            .entry(key)
+                            .or_insert_with(|| SlidingState::new(strategy));
+                        match pane {
+                            Some(pane) => match window.pending.entry(align(time, pane)) {
+                                btree_map::Entry::Occupied(mut entry) => {
+                                    let agg = entry.get_mut().pop().unwrap();
+                                    entry.get_mut().push(combine(&agg, data));
+                                }
+                                btree_map::Entry::Vacant(entry) => {
+                                    entry.insert(vec![data]);
+                                }
+                            },
+                            None => window.pending.entry(time).or_default().push(data),
+                        }
+                    }
+                    KeyedEvent::Watermark(time) => {
+                        for (key, window) in windows.iter_mut() {
+                            while let Some(first) = window.first() {
+                                let t0 = align(first - duration, step) + step;
+                                let t0 = window.next.map_or(t0, |next| next.max(t0));
+                                let t1 = t0 + duration;
+                                if t1 >= time {
+                                    break;
+                                }
+                                let after = window.pending.split_off(&t1);
+                                let before = std::mem::replace(&mut window.pending, after);
+                                // Data in windows which have already fired is dropped.
+                                for (t, ps) in before.range(t0..) {
+                                    for p in ps {
+                                        window.fifo.push(*t, p.clone(), &combine);
+                                    }
+                                }
+                                if let Some(p) = window.fifo.query(&combine) {
                                     let data = lower(key.clone(), &p, t0..t1);
-                                    tx1.send(KeyedEvent::Data(t1, key, data)).await?;
+                                    tx1.send(KeyedEvent::Data(t1, key.clone(), data)).await?;
+                                }
+                                window.next = Some(t0 + step);
+                                while window.fifo.oldest().is_some_and(|t| t < t0 + step) {
+                                    window.fifo.pop(&combine, inverse.as_ref());
                                 }
-                                tx1.send(KeyedEvent::Watermark(time)).await?;
-                            } else {
-                                tx1.send(KeyedEvent::Watermark(time)).await?;
-                                break;
                             }
                         }
+                        windows.retain(|_, window| window.first().is_some());
+                        watermark = time;
+                        tx1.send(KeyedEvent::Watermark(time)).await?;
+                        late.send(KeyedEvent::Watermark(time)).await.ok();
                     }
                     KeyedEvent::Snapshot(i) => {
+                        state.snapshot(i, &(&windows, watermark));
                         tx1.send(KeyedEvent::Snapshot(i)).await?;
+                        late.send(KeyedEvent::Snapshot(i)).await.ok();
                     }
                     KeyedEvent::Sentinel => {
+                        late.send(KeyedEvent::Sentinel).await.ok();
                         tx1.send(KeyedEvent::Sentinel).await?;
                         break;
                     }
@@ -105,4 +406,323 @@ impl<K: Key, T: Data> KeyedStream<K, T> {
             Ok(())
         })
     }
+
+    // Count-based windows aggregate data in the order it arrives.
+    fn counting_window<P, O>(
+        mut self,
+        ctx: &mut Context,
+        length: usize,
+        lift: impl Fn(&T) -> P + Send + 'static,
+        combine: impl Fn(&P, P) -> P + Send + 'static,
+        lower: impl Fn(K, &P, Range<Time>) -> O + Send + 'static,
+    ) -> (KeyedStream<K, O>, KeyedStream<K, T>)
+    where
+        P: Data,
+        O: Data,
+    {
+        let state = ctx.operator_state();
+        ctx.keyed_co_operator(move |tx1, late| async move {
+            let mut aggs: HashMap<K, (Time, P, usize)> = state.restore().unwrap_or_default();
+            loop {
+                match self.recv().await {
+                    KeyedEvent::Data(time, key, data) => {
+                        let data = lift(&data);
+                        let (t0, p, n) = match aggs.remove(&key) {
+                            Some((t0, p, n)) => (t0, combine(&p, data), n + 1),
+                            None => (time, data, 1),
+                        };
+                        if n == length {
+                            let data = lower(key.clone(), &p, t0..time);
+                            tx1.send(KeyedEvent::Data(time, key, data)).await?;
+                        } else {
+                            aggs.insert(key, (t0, p, n));
+                        }
+                    }
+                    KeyedEvent::Watermark(time) => {
+                        tx1.send(KeyedEvent::Watermark(time)).await?;
+                        late.send(KeyedEvent::Watermark(time)).await.ok();
+                    }
+                    KeyedEvent::Snapshot(i) => {
+                        state.snapshot(i, &aggs);
+                        tx1.send(KeyedEvent::Snapshot(i)).await?;
+                        late.send(KeyedEvent::Snapshot(i)).await.ok();
+                    }
+                    KeyedEvent::Sentinel => {
+                        late.send(KeyedEvent::Sentinel).await.ok();
+                        tx1.send(KeyedEvent::Sentinel).await?;
+                        break;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn moving_window<P, O>(
+        mut self,
+        ctx: &mut Context,
+        length: usize,
+        step: usize,
+        strategy: Strategy,
+        lift: impl Fn(&T) -> P + Send + 'static,
+        combine: impl Fn(&P, P) -> P + Send + 'static,
+        inverse: Option<fn(&P, &P) -> P>,
+        lower: impl Fn(K, &P, Range<Time>) -> O + Send + 'static,
+    ) -> (KeyedStream<K, O>, KeyedStream<K, T>)
+    where
+        P: Data,
+        O: Data,
+    {
+        let state = ctx.operator_state();
+        ctx.keyed_co_operator(move |tx1, late| async move {
+            let mut windows: HashMap<K, MovingState<P>> = state.restore().unwrap_or_default();
+            loop {
+                match self.recv().await {
+                    KeyedEvent::Data(time, key, data) => {
+                        let window = windows
+                            .entry(key.clone())
+                            .or_insert_with(|| MovingState::new(strategy));
+                        // Data between the end of a window and the start of
+                        // the next is dropped when the step exceeds the length.
+                        if window.skip > 0 {
+                            window.skip -= 1;
+                            continue;
+                        }
+                        let fifo = &mut window.fifo;
+                        fifo.push(time, lift(&data), &combine);
+                        if fifo.len() == length {
+                            let t0 = fifo.oldest().unwrap();
+                            let p = fifo.query(&combine).unwrap();
+                            let data = lower(key.clone(), &p, t0..time);
+                            tx1.send(KeyedEvent::Data(time, key, data)).await?;
+                            for _ in 0..step.min(length) {
+                                fifo.pop(&combine, inverse.as_ref());
+                            }
+                            window.skip = step.saturating_sub(length);
+                        }
+                    }
+                    KeyedEvent::Watermark(time) => {
+                        tx1.send(KeyedEvent::Watermark(time)).await?;
+                        late.send(KeyedEvent::Watermark(time)).await.ok();
+                    }
+                    KeyedEvent::Snapshot(i) => {
+                        state.snapshot(i, &windows);
+                        tx1.send(KeyedEvent::Snapshot(i)).await?;
+                        late.send(KeyedEvent::Snapshot(i)).await.ok();
+                    }
+                    KeyedEvent::Sentinel => {
+                        late.send(KeyedEvent::Sentinel).await.ok();
+                        tx1.send(KeyedEvent::Sentinel).await?;
+                        break;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Strategy {
+    TwoStacks,
+    Subtract,
+    Recompute,
+}
+
+impl Strategy {
+    fn of<P>(properties: &Properties<P>) -> Self {
+        if properties.inverse.is_some() {
+            Strategy::Subtract
+        } else if properties.associative {
+            Strategy::TwoStacks
+        } else {
+            Strategy::Recompute
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SessionState<P> {
+    pending: BTreeMap<Time, Vec<P>>,
+    sessions: Sessions<P>,
+}
+
+impl<P> Default for SessionState<P> {
+    fn default() -> Self {
+        Self {
+            pending: BTreeMap::new(),
+            sessions: Sessions::default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SlidingState<P> {
+    pending: BTreeMap<Time, Vec<P>>,
+    fifo: Fifo<P>,
+    next: Option<Time>,
+}
+
+impl<P> SlidingState<P> {
+    fn new(strategy: Strategy) -> Self {
+        Self {
+            pending: BTreeMap::new(),
+            fifo: Fifo::new(strategy),
+            next: None,
+        }
+    }
+
+    fn first(&self) -> Option<Time> {
+        let pending = self.pending.keys().next().copied();
+        match (self.fifo.oldest(), pending) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MovingState<P> {
+    fifo: Fifo<P>,
+    /// The number of records to drop before the next window starts.
+    skip: usize,
+}
+
+impl<P> MovingState<P> {
+    fn new(strategy: Strategy) -> Self {
+        Self {
+            fifo: Fifo::new(strategy),
+            skip: 0,
+        }
+    }
+}
+
+/// A queue of partial aggregates which supports eviction of its oldest element.
+#[derive(Debug, Serialize, Deserialize)]
+enum Fifo<P> {
+    /// The front stack holds the aggregate of each element and every newer
+    /// element in the stack. Elements are moved there from the back stack
+    /// once the front stack runs empty.
+    TwoStacks {
+        front: Vec<(Time, P)>,
+        back: Vec<(Time, P)>,
+        back_agg: Option<P>,
+    },
+    Subtract {
+        items: VecDeque<(Time, P)>,
+        agg: Option<P>,
+    },
+    Recompute {
+        items: VecDeque<(Time, P)>,
+    },
+}
+
+impl<P> Fifo<P> {
+    fn new(strategy: Strategy) -> Self {
+        match strategy {
+            Strategy::TwoStacks => Fifo::TwoStacks {
+                front: Vec::new(),
+                back: Vec::new(),
+                back_agg: None,
+            },
+            Strategy::Subtract => Fifo::Subtract {
+                items: VecDeque::new(),
+                agg: None,
+            },
+            Strategy::Recompute => Fifo::Recompute {
+                items: VecDeque::new(),
+            },
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Fifo::TwoStacks { front, back, .. } => front.len() + back.len(),
+            Fifo::Subtract { items, .. } | Fifo::Recompute { items } => items.len(),
+        }
+    }
+
+    fn oldest(&self) -> Option<Time> {
+        match self {
+            Fifo::TwoStacks { front, back, .. } => front.last().or(back.first()).map(|(t, _)| *t),
+            Fifo::Subtract { items, .. } | Fifo::Recompute { items } => {
+                items.front().map(|(t, _)| *t)
+            }
+        }
+    }
+}
+
+impl<P: Data> Fifo<P> {
+    fn push(&mut self, time: Time, p: P, combine: &impl Fn(&P, P) -> P) {
+        match self {
+            Fifo::TwoStacks { back, back_agg, .. } => {
+                *back_agg = Some(match back_agg.take() {
+                    Some(agg) => combine(&agg, p.clone()),
+                    None => p.clone(),
+                });
+                back.push((time, p));
+            }
+            Fifo::Subtract { items, agg } => {
+                *agg = Some(match agg.take() {
+                    Some(agg) => combine(&agg, p.clone()),
+                    None => p.clone(),
+                });
+                items.push_back((time, p));
+            }
+            Fifo::Recompute { items } => items.push_back((time, p)),
+        }
+    }
+
+    fn pop(&mut self, combine: &impl Fn(&P, P) -> P, inverse: Option<&impl Fn(&P, &P) -> P>) {
+        match self {
+            Fifo::TwoStacks {
+                front,
+                back,
+                back_agg,
+            } => {
+                if front.is_empty() {
+                    for (t, p) in back.drain(..).rev() {
+                        let agg = match front.last() {
+                            Some((_, agg)) => combine(&p, agg.clone()),
+                            None => p,
+                        };
+                        front.push((t, agg));
+                    }
+                    *back_agg = None;
+                }
+                front.pop();
+            }
+            Fifo::Subtract { items, agg } => {
+                if let Some((_, p)) = items.pop_front() {
+                    let inverse = inverse.expect("Subtract-on-evict requires an inverse");
+                    *agg = match agg.take() {
+                        Some(agg) if !items.is_empty() => Some(inverse(&agg, &p)),
+                        _ => None,
+                    };
+                }
+            }
+            Fifo::Recompute { items } => {
+                items.pop_front();
+            }
+        }
+    }
+
+    fn query(&self, combine: &impl Fn(&P, P) -> P) -> Option<P> {
+        match self {
+            Fifo::TwoStacks {
+                front, back_agg, ..
+            } => match (front.last(), back_agg) {
+                (Some((_, a)), Some(b)) => Some(combine(a, b.clone())),
+                (Some((_, a)), None) => Some(a.clone()),
+                (None, b) => b.clone(),
+            },
+            Fifo::Subtract { agg, .. } => agg.clone(),
+            Fifo::Recompute { items } => {
+                let mut iter = items.iter();
+                let (_, p) = iter.next()?;
+                Some(iter.fold(p.clone(), |agg, (_, p)| combine(&agg, p.clone())))
+            }
+        }
+    }
 }
@@ -22,7 +22,7 @@ impl<K: Key, T: Data> KeyedStream<K, T> {
                 match self.recv().await {
                     KeyedEvent::Data(t, k, v0) => {
                         if let Some(v1) = index.get(&k) {
-                            let v2 = merge(&v0, &v1);
+                            let v2 = merge(&v0, v1);
                             tx.send(KeyedEvent::Data(t, k, v2)).await?;
                         }
                     }
@@ -0,0 +1,351 @@
+use std::convert::TryFrom;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::builtins::time::Time;
+use crate::runner::context::Context;
+use crate::traits::Data;
+use crate::traits::Key;
+use crate::BTreeMap;
+use crate::HashMap;
+
+use super::KeyedEvent;
+use super::KeyedStream;
+
+/// A timer which has fired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timer {
+    /// Fires when the watermark reaches the time.
+    EventTime(Time),
+    /// Fires when the wall clock reaches the time.
+    ProcessingTime(Time),
+}
+
+/// A single value of per-key state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValueState<V>(Option<V>);
+
+/// A list of per-key state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListState<V>(Vec<V>);
+
+/// A map of per-key state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MapState<K: Eq + std::hash::Hash, V>(HashMap<K, V>);
+
+impl<V> Default for ValueState<V> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<V> Default for ListState<V> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<K: Eq + std::hash::Hash, V> Default for MapState<K, V> {
+    fn default() -> Self {
+        Self(HashMap::default())
+    }
+}
+
+impl<V> ValueState<V> {
+    pub fn value(&self) -> Option<&V> {
+        self.0.as_ref()
+    }
+
+    pub fn update(&mut self, value: V) {
+        self.0 = Some(value);
+    }
+
+    pub fn take(&mut self) -> Option<V> {
+        self.0.take()
+    }
+
+    pub fn clear(&mut self) {
+        self.0 = None;
+    }
+}
+
+impl<V> ListState<V> {
+    pub fn get(&self) -> &[V] {
+        &self.0
+    }
+
+    pub fn add(&mut self, value: V) {
+        self.0.push(value);
+    }
+
+    pub fn update(&mut self, values: Vec<V>) {
+        self.0 = values;
+    }
+
+    pub fn take(&mut self) -> Vec<V> {
+        std::mem::take(&mut self.0)
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl<K: Eq + std::hash::Hash, V> MapState<K, V> {
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.0.get(key)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.0.get_mut(key)
+    }
+
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        self.0.insert(key, value)
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.0.remove(key)
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.0.contains_key(key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.0.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// Timers of one time domain, ordered by time.
+#[derive(Debug, Serialize, Deserialize)]
+struct Timers<K>(BTreeMap<Time, Vec<K>>);
+
+impl<K: Key> Timers<K> {
+    fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    fn register(&mut self, time: Time, key: &K) {
+        let keys = self.0.entry(time).or_default();
+        if !keys.contains(key) {
+            keys.push(key.clone());
+        }
+    }
+
+    fn delete(&mut self, time: Time, key: &K) {
+        if let Some(keys) = self.0.get_mut(&time) {
+            keys.retain(|k| k != key);
+            if keys.is_empty() {
+                self.0.remove(&time);
+            }
+        }
+    }
+
+    fn next(&self) -> Option<Time> {
+        self.0.keys().next().copied()
+    }
+
+    /// Removes the timers which are due at `time`.
+    fn pop(&mut self, time: Time) -> Option<(Time, Vec<K>)> {
+        let entry = self.0.first_entry()?;
+        if *entry.key() <= time {
+            Some(entry.remove_entry())
+        } else {
+            None
+        }
+    }
+}
+
+/// The state, timers and output of the key which is being processed.
+pub struct ProcessContext<'a, K: Key, S, O> {
+    key: &'a K,
+    time: Time,
+    watermark: Time,
+    state: &'a mut S,
+    event_timers: &'a mut Timers<K>,
+    processing_timers: &'a mut Timers<K>,
+    output: &'a mut Vec<(Time, O)>,
+    cleared: bool,
+}
+
+impl<'a, K: Key, S, O> ProcessContext<'a, K, S, O> {
+    pub fn key(&self) -> &K {
+        self.key
+    }
+
+    /// The time of the event, or of the event-time timer, which is being
+    /// processed. Processing-time timers see the current watermark.
+    pub fn time(&self) -> Time {
+        self.time
+    }
+
+    pub fn watermark(&self) -> Time {
+        self.watermark
+    }
+
+    pub fn state(&mut self) -> &mut S {
+        self.state
+    }
+
+    /// Drops the state of the key once the callback returns.
+    pub fn clear(&mut self) {
+        self.cleared = true;
+    }
+
+    /// Emits an output at the current time.
+    pub fn emit(&mut self, data: O) {
+        self.output.push((self.time, data));
+    }
+
+    pub fn emit_at(&mut self, time: Time, data: O) {
+        self.output.push((time, data));
+    }
+
+    pub fn register_event_timer(&mut self, time: Time) {
+        self.event_timers.register(time, self.key);
+    }
+
+    pub fn register_processing_timer(&mut self, time: Time) {
+        self.processing_timers.register(time, self.key);
+    }
+
+    pub fn delete_event_timer(&mut self, time: Time) {
+        self.event_timers.delete(time, self.key);
+    }
+
+    pub fn delete_processing_timer(&mut self, time: Time) {
+        self.processing_timers.delete(time, self.key);
+    }
+}
+
+/// The state and timers of all keys.
+struct Keys<K: Key, S> {
+    states: HashMap<K, S>,
+    event_timers: Timers<K>,
+    processing_timers: Timers<K>,
+    watermark: Time,
+}
+
+impl<K: Key, S: Data + Default> Keys<K, S> {
+    /// Runs a callback on the state of a key, and returns what it emitted.
+    fn call<O>(
+        &mut self,
+        key: &K,
+        time: Time,
+        f: impl FnOnce(&mut ProcessContext<K, S, O>),
+    ) -> Vec<(Time, O)> {
+        let mut output = Vec::new();
+        let mut ctx = ProcessContext {
+            key,
+            time,
+            watermark: self.watermark,
+            state: self.states.entry(key.clone()).or_default(),
+            event_timers: &mut self.event_timers,
+            processing_timers: &mut self.processing_timers,
+            output: &mut output,
+            cleared: false,
+        };
+        f(&mut ctx);
+        if ctx.cleared {
+            self.states.remove(key);
+        }
+        output
+    }
+}
+
+impl<K: Key, T: Data> KeyedStream<K, T> {
+    /// Processes each event with access to the state of its key, and fires
+    /// event-time and processing-time timers which the callbacks register.
+    /// State and timers are included in snapshots.
+    pub fn process<S, O>(
+        mut self,
+        ctx: &mut Context,
+        mut on_event: impl FnMut(&mut ProcessContext<K, S, O>, T) + Send + 'static,
+        mut on_timer: impl FnMut(&mut ProcessContext<K, S, O>, Timer) + Send + 'static,
+    ) -> KeyedStream<K, O>
+    where
+        S: Data + Default,
+        O: Data,
+    {
+        let state = ctx.operator_state();
+        ctx.keyed_operator(move |tx| async move {
+            let (states, event_timers, processing_timers, watermark) =
+                state.restore().unwrap_or_else(|| {
+                    (HashMap::default(), Timers::new(), Timers::new(), Time::zero())
+                });
+            let mut keys = Keys {
+                states,
+                event_timers,
+                processing_timers,
+                watermark,
+            };
+            loop {
+                let deadline = keys.processing_timers.next().map(|time| {
+                    let delay = std::time::Duration::try_from(time.0 - Time::now().0);
+                    tokio::time::Instant::now() + delay.unwrap_or_default()
+                });
+                let timers = deadline.is_some();
+                let deadline = deadline.unwrap_or_else(tokio::time::Instant::now);
+                let event = tokio::select! {
+                    event = self.recv() => event,
+                    _ = tokio::time::sleep_until(deadline), if timers => {
+                        while let Some((time, ks)) = keys.processing_timers.pop(Time::now()) {
+                            for key in ks {
+                                let watermark = keys.watermark;
+                                let output = keys.call(&key, watermark, |ctx| {
+                                    on_timer(ctx, Timer::ProcessingTime(time))
+                                });
+                                for (t, data) in output {
+                                    tx.send(KeyedEvent::Data(t, key.clone(), data)).await?;
+                                }
+                            }
+                        }
+                        continue;
+                    }
+                };
+                match event {
+                    KeyedEvent::Data(time, key, data) => {
+                        let output = keys.call(&key, time, |ctx| on_event(ctx, data));
+                        for (t, data) in output {
+                            tx.send(KeyedEvent::Data(t, key.clone(), data)).await?;
+                        }
+                    }
+                    KeyedEvent::Watermark(time) => {
+                        keys.watermark = time;
+                        while let Some((t, ks)) = keys.event_timers.pop(time) {
+                            for key in ks {
+                                let output =
+                                    keys.call(&key, t, |ctx| on_timer(ctx, Timer::EventTime(t)));
+                                for (t, data) in output {
+                                    tx.send(KeyedEvent::Data(t, key.clone(), data)).await?;
+                                }
+                            }
+                        }
+                        tx.send(KeyedEvent::Watermark(time)).await?;
+                    }
+                    KeyedEvent::Snapshot(i) => {
+                        let snapshot = (
+                            &keys.states,
+                            &keys.event_timers,
+                            &keys.processing_timers,
+                            keys.watermark,
+                        );
+                        state.snapshot(i, &snapshot);
+                        tx.send(KeyedEvent::Snapshot(i)).await?;
+                    }
+                    KeyedEvent::Sentinel => {
+                        tx.send(KeyedEvent::Sentinel).await?;
+                        break;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+}
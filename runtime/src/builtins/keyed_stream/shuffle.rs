@@ -0,0 +1,138 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::Hasher;
+
+use crate::builtins::stream::barrier::Alignment;
+use crate::builtins::time::Time;
+use crate::error::Error;
+use crate::runner::context::Context;
+use crate::runner::exchange::Endpoints;
+use crate::traits::Data;
+use crate::traits::Key;
+
+use super::KeyedEvent;
+use super::KeyedStream;
+
+impl<K: Key, T: Data> KeyedStream<K, T> {
+    /// Routes each record to the worker of a `DataParallelRunner` which owns
+    /// its key, so that keyed operators downstream see every record of a key.
+    /// Watermarks, snapshots and sentinels are broadcast to all workers. The
+    /// output watermark is the minimum over all workers, and snapshots are
+    /// aligned across them. Outside of a data-parallel runner, this is a no-op.
+    pub fn shuffle(mut self, ctx: &mut Context) -> KeyedStream<K, T> {
+        let Some(Endpoints {
+            worker,
+            txs,
+            mut rx,
+        }) = ctx.exchange::<KeyedEvent<K, T>>()
+        else {
+            return self;
+        };
+        let workers = txs.len();
+        ctx.spawn(async move {
+            loop {
+                match self.recv().await {
+                    KeyedEvent::Data(t, k, v) => {
+                        let mut hasher = DefaultHasher::new();
+                        k.hash(&mut hasher);
+                        let tx = &txs[hasher.finish() as usize % workers];
+                        tx.send((worker, KeyedEvent::Data(t, k, v)))
+                            .await
+                            .map_err(|_| Error::Closed)?;
+                    }
+                    KeyedEvent::Watermark(t) => {
+                        for tx in &txs {
+                            tx.send((worker, KeyedEvent::Watermark(t)))
+                                .await
+                                .map_err(|_| Error::Closed)?;
+                        }
+                    }
+                    KeyedEvent::Snapshot(i) => {
+                        for tx in &txs {
+                            tx.send((worker, KeyedEvent::Snapshot(i)))
+                                .await
+                                .map_err(|_| Error::Closed)?;
+                        }
+                    }
+                    KeyedEvent::Sentinel => {
+                        for tx in &txs {
+                            tx.send((worker, KeyedEvent::Sentinel))
+                                .await
+                                .map_err(|_| Error::Closed)?;
+                        }
+                        break;
+                    }
+                }
+            }
+            Ok(())
+        });
+        ctx.keyed_operator(move |tx| async move {
+            let mut watermarks = vec![Time::zero(); workers];
+            let mut watermark = Time::zero();
+            let mut done = vec![false; workers];
+            let mut barriers = Alignment::new(workers);
+            // Events of workers which are blocked on a barrier.
+            let mut blocked: Vec<VecDeque<KeyedEvent<K, T>>> =
+                (0..workers).map(|_| VecDeque::new()).collect();
+            loop {
+                let unblocked =
+                    (0..workers).find(|i| !barriers.is_blocked(*i) && !blocked[*i].is_empty());
+                let (i, event) = match unblocked {
+                    Some(i) => (i, blocked[i].pop_front().unwrap()),
+                    None => match rx.recv().await {
+                        Some(event) => event,
+                        None => {
+                            tx.send(KeyedEvent::Sentinel).await?;
+                            break;
+                        }
+                    },
+                };
+                if barriers.is_blocked(i) {
+                    blocked[i].push_back(event);
+                    continue;
+                }
+                match event {
+                    KeyedEvent::Data(t, k, v) => tx.send(KeyedEvent::Data(t, k, v)).await?,
+                    KeyedEvent::Watermark(t) => {
+                        watermarks[i] = t;
+                        let min = Self::min_watermark(&watermarks, &done);
+                        if min > Some(watermark) {
+                            watermark = min.unwrap();
+                            tx.send(KeyedEvent::Watermark(watermark)).await?;
+                        }
+                    }
+                    KeyedEvent::Snapshot(s) => {
+                        if let Some(s) = barriers.arrive(i, s) {
+                            tx.send(KeyedEvent::Snapshot(s)).await?;
+                        }
+                    }
+                    KeyedEvent::Sentinel => {
+                        done[i] = true;
+                        if done.iter().all(|d| *d) {
+                            tx.send(KeyedEvent::Sentinel).await?;
+                            break;
+                        }
+                        if let Some(s) = barriers.finish(i) {
+                            tx.send(KeyedEvent::Snapshot(s)).await?;
+                        }
+                        let min = Self::min_watermark(&watermarks, &done);
+                        if min > Some(watermark) {
+                            watermark = min.unwrap();
+                            tx.send(KeyedEvent::Watermark(watermark)).await?;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn min_watermark(watermarks: &[Time], done: &[bool]) -> Option<Time> {
+        watermarks
+            .iter()
+            .zip(done)
+            .filter(|(_, done)| !**done)
+            .map(|(t, _)| *t)
+            .min()
+    }
+}
@@ -4,3 +4,5 @@ pub mod time_tumbling_holistic_vec;
 // pub mod time_sliding_holistic;
 pub mod time_sliding_aligned_commutative_associative;
 pub mod time_sliding_aligned_holistic;
+pub mod time_session_holistic;
+pub mod time_session_commutative_associative;
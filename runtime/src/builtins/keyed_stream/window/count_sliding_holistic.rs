@@ -18,8 +18,9 @@ impl<K: Key, T: Data> KeyedStream<K, T> {
     where
         O: Data,
     {
+        let state = ctx.operator_state();
         ctx.keyed_operator(move |tx| async move {
-            let mut aggs: HashMap<K, VecDeque<T>> = HashMap::default();
+            let mut aggs: HashMap<K, VecDeque<T>> = state.restore().unwrap_or_default();
             loop {
                 match self.recv().await {
                     KeyedEvent::Data(time, key, data) => {
@@ -37,6 +38,7 @@ impl<K: Key, T: Data> KeyedStream<K, T> {
                         tx.send(KeyedEvent::Watermark(time)).await?;
                     }
                     KeyedEvent::Snapshot(i) => {
+                        state.snapshot(i, &aggs);
                         tx.send(KeyedEvent::Snapshot(i)).await?;
                     }
                     KeyedEvent::Sentinel => {
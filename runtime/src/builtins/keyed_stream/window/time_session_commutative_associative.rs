@@ -0,0 +1,98 @@
+use crate::builtins::duration::Duration;
+use crate::builtins::keyed_stream::KeyedEvent;
+use crate::builtins::keyed_stream::KeyedStream;
+use crate::builtins::lateness::FireOnceLateness;
+use crate::builtins::stream::window::Sessions;
+use crate::builtins::stream::window::WindowRange;
+use crate::builtins::time::Time;
+use crate::runner::context::Context;
+use crate::traits::Data;
+use crate::traits::Key;
+use crate::HashMap;
+
+impl<K: Key, T: Data> KeyedStream<K, T> {
+    pub fn time_session_commutative_associative_window<P, O>(
+        self,
+        ctx: &mut Context,
+        gap: Duration,
+        lift: impl Fn(&T) -> P + Send + 'static,
+        combine: impl Fn(&P, &P) -> P + Send + 'static,
+        lower: impl Fn(&K, &P, WindowRange) -> O + Send + 'static,
+    ) -> KeyedStream<K, O>
+    where
+        O: Data,
+        P: Data,
+    {
+        self.time_session_commutative_associative_window_with_lateness(
+            ctx,
+            gap,
+            FireOnceLateness::Drop,
+            lift,
+            combine,
+            lower,
+        )
+        .0
+    }
+
+    /// Like `time_session_commutative_associative_window`, but events whose
+    /// session has already fired, and which do not fall into an open session of
+    /// their key, are handled by the lateness policy. The second stream is the
+    /// late output.
+    pub fn time_session_commutative_associative_window_with_lateness<P, O>(
+        mut self,
+        ctx: &mut Context,
+        gap: Duration,
+        lateness: FireOnceLateness,
+        lift: impl Fn(&T) -> P + Send + 'static,
+        combine: impl Fn(&P, &P) -> P + Send + 'static,
+        lower: impl Fn(&K, &P, WindowRange) -> O + Send + 'static,
+    ) -> (KeyedStream<K, O>, KeyedStream<K, T>)
+    where
+        O: Data,
+        P: Data,
+    {
+        let state = ctx.operator_state();
+        ctx.keyed_co_operator(move |tx, late| async move {
+            let (mut sessions, mut watermark): (HashMap<K, Sessions<P>>, Time) = state
+                .restore()
+                .unwrap_or_else(|| (HashMap::default(), Time::zero()));
+            loop {
+                match self.recv().await {
+                    KeyedEvent::Data(time, key, data) => {
+                        let s = sessions.entry(key.clone()).or_default();
+                        if s.is_late(time, gap, watermark) {
+                            if lateness.routes_late() {
+                                late.send(KeyedEvent::Data(time, key, data)).await.ok();
+                            }
+                            continue;
+                        }
+                        s.insert(time, gap, lift(&data), |a, b| combine(&a, &b));
+                    }
+                    KeyedEvent::Watermark(time) => {
+                        for (key, s) in sessions.iter_mut() {
+                            for (wr, p) in s.expire(time) {
+                                let data = lower(key, &p, wr);
+                                tx.send(KeyedEvent::Data(wr.t1, key.clone(), data)).await?;
+                            }
+                        }
+                        sessions.retain(|_, s| !s.is_empty());
+                        watermark = time;
+                        tx.send(KeyedEvent::Watermark(time)).await?;
+                        late.send(KeyedEvent::Watermark(time)).await.ok();
+                    }
+                    KeyedEvent::Snapshot(i) => {
+                        state.snapshot(i, &(&sessions, watermark));
+                        tx.send(KeyedEvent::Snapshot(i)).await?;
+                        late.send(KeyedEvent::Snapshot(i)).await.ok();
+                    }
+                    KeyedEvent::Sentinel => {
+                        late.send(KeyedEvent::Sentinel).await.ok();
+                        tx.send(KeyedEvent::Sentinel).await?;
+                        break;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::BTreeMap;
 use crate::builtins::duration::Duration;
 use crate::builtins::keyed_stream::KeyedEvent;
 use crate::builtins::keyed_stream::KeyedStream;
+use crate::builtins::lateness::FireOnceLateness;
 use crate::builtins::stream::window::WindowRange;
 use crate::builtins::time::Time;
 use crate::runner::context::Context;
@@ -14,28 +15,69 @@ impl<K: Key, T: Data> KeyedStream<K, T> {
     // Requires that duration % step == 0
     #[allow(clippy::too_many_arguments)]
     pub fn time_sliding_aligned_commutative_associative_window<P, O>(
+        self,
+        ctx: &mut Context,
+        duration: Duration,
+        step: Duration,
+        init: P,
+        lift: impl Fn(&T) -> P + Send + 'static,
+        combine: impl Fn(&P, &P) -> P + Send + 'static,
+        lower: impl Fn(&K, &P, WindowRange) -> O + Send + 'static,
+    ) -> KeyedStream<K, O>
+    where
+        O: Data,
+        P: Data,
+    {
+        self.time_sliding_aligned_commutative_associative_window_with_lateness(
+            ctx,
+            duration,
+            step,
+            FireOnceLateness::Drop,
+            init,
+            lift,
+            combine,
+            lower,
+        )
+        .0
+    }
+
+    /// Like `time_sliding_aligned_commutative_associative_window`, but events
+    /// whose last window has already fired are handled by the lateness policy.
+    /// The second stream is the late output.
+    #[allow(clippy::too_many_arguments)]
+    pub fn time_sliding_aligned_commutative_associative_window_with_lateness<P, O>(
         mut self,
         ctx: &mut Context,
         duration: Duration,
         step: Duration,
+        lateness: FireOnceLateness,
         _init: P,
         lift: impl Fn(&T) -> P + Send + 'static,
         combine: impl Fn(&P, &P) -> P + Send + 'static,
         lower: impl Fn(&K, &P, WindowRange) -> O + Send + 'static,
-    ) -> KeyedStream<K, O>
+    ) -> (KeyedStream<K, O>, KeyedStream<K, T>)
     where
         O: Data,
         P: Data,
     {
         assert!(duration % step == Duration::from_seconds(0));
-        ctx.keyed_operator(move |tx| async move {
-            let mut slices: BTreeMap<Time, HashMap<K, P>> = BTreeMap::new();
+        let state = ctx.operator_state();
+        ctx.keyed_co_operator(move |tx, late| async move {
+            let (mut slices, mut watermark): (BTreeMap<Time, HashMap<K, P>>, Time) = state
+                .restore()
+                .unwrap_or_else(|| (BTreeMap::new(), Time::zero()));
             let mut output: HashMap<K, P> = HashMap::default();
             loop {
                 match self.recv().await {
                     KeyedEvent::Data(time, key, data) => {
-                        let data = lift(&data);
                         let wr = WindowRange::of(time, step, step);
+                        if wr.t0 + duration <= watermark {
+                            if lateness.routes_late() {
+                                late.send(KeyedEvent::Data(time, key, data)).await.ok();
+                            }
+                            continue;
+                        }
+                        let data = lift(&data);
                         slices
                             .entry(wr.t0)
                             .or_default()
@@ -66,12 +108,17 @@ impl<K: Key, T: Data> KeyedStream<K, T> {
                                 break;
                             }
                         }
+                        watermark = time;
                         tx.send(KeyedEvent::Watermark(time)).await?;
+                        late.send(KeyedEvent::Watermark(time)).await.ok();
                     }
                     KeyedEvent::Snapshot(i) => {
+                        state.snapshot(i, &(&slices, watermark));
                         tx.send(KeyedEvent::Snapshot(i)).await?;
+                        late.send(KeyedEvent::Snapshot(i)).await.ok();
                     }
                     KeyedEvent::Sentinel => {
+                        late.send(KeyedEvent::Sentinel).await.ok();
                         tx.send(KeyedEvent::Sentinel).await?;
                         break;
                     }
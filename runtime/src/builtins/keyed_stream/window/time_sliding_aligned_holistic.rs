@@ -1,6 +1,7 @@
 use crate::builtins::duration::Duration;
 use crate::builtins::keyed_stream::KeyedEvent;
 use crate::builtins::keyed_stream::KeyedStream;
+use crate::builtins::lateness::Lateness;
 use crate::builtins::stream::window::align;
 use crate::builtins::stream::window::WindowRange;
 use crate::builtins::time::Time;
@@ -12,7 +13,7 @@ use crate::HashMap;
 
 impl<K: Key, T: Data> KeyedStream<K, T> {
     pub fn time_sliding_aligned_holistic_window<O>(
-        mut self,
+        self,
         ctx: &mut Context,
         size: Duration,
         slide: Duration,
@@ -21,45 +22,106 @@ impl<K: Key, T: Data> KeyedStream<K, T> {
     where
         O: Data,
     {
-        ctx.keyed_operator(move |tx| async move {
-            let mut slices: BTreeMap<Time, HashMap<K, Vec<T>>> = BTreeMap::new();
+        self.time_sliding_aligned_holistic_window_with_lateness(
+            ctx,
+            size,
+            slide,
+            Lateness::Drop,
+            compute,
+        )
+        .0
+    }
+
+    /// Like `time_sliding_aligned_holistic_window`, but events whose windows
+    /// have all fired are handled by the lateness policy. The second stream is
+    /// the late output.
+    pub fn time_sliding_aligned_holistic_window_with_lateness<O>(
+        mut self,
+        ctx: &mut Context,
+        size: Duration,
+        slide: Duration,
+        lateness: Lateness,
+        compute: impl for<'a, 'b> Fn(&K, Window<'a, 'b, T>, WindowRange) -> O + Send + 'static,
+    ) -> (KeyedStream<K, O>, KeyedStream<K, T>)
+    where
+        O: Data,
+    {
+        let state = ctx.operator_state();
+        let allowed = lateness.allowed_lateness();
+        ctx.keyed_co_operator(move |tx, late| async move {
+            let (mut slices, mut watermark): (BTreeMap<Time, HashMap<K, Vec<T>>>, Time) = state
+                .restore()
+                .unwrap_or_else(|| (BTreeMap::new(), Time::zero()));
             loop {
                 match self.recv().await {
                     KeyedEvent::Data(time, key, data) => {
+                        let t = align(time, slide);
+                        if t + size + allowed < watermark {
+                            if lateness.routes_late() {
+                                late.send(KeyedEvent::Data(time, key, data)).await.ok();
+                            }
+                            continue;
+                        }
                         slices
-                            .entry(align(time, slide))
+                            .entry(t)
                             .or_default()
-                            .entry(key)
+                            .entry(key.clone())
                             .or_default()
                             .push(data);
+                        // Windows which contain the slice and have already fired, fire again.
+                        for t0 in slices.range(..=t).map(|(t0, _)| t0) {
+                            let wr = WindowRange::new(*t0, *t0 + size);
+                            if wr.t1 <= t || wr.t1 >= watermark {
+                                continue;
+                            }
+                            let vs = slices
+                                .range(wr.t0..wr.t1)
+                                .filter_map(|(_, kvs)| kvs.get(&key))
+                                .map(Vec::as_slice)
+                                .collect::<Vec<_>>();
+                            let output = compute(&key, Window::new(vs.as_slice()), wr);
+                            tx.send(KeyedEvent::Data(watermark, key.clone(), output))
+                                .await?;
+                        }
                     }
                     KeyedEvent::Watermark(time) => {
-                        while let Some((t0, _)) = slices.first_key_value() {
-                            let t1 = *t0 + size;
-                            let wr = WindowRange::new(*t0, t1);
-                            if wr.t1 < time {
-                                let mut output: HashMap<K, Vec<&[T]>> = HashMap::default();
-                                for (_, kvs) in slices.range(..wr.t1) {
-                                    for (k, vs) in kvs {
-                                        output.entry(k.clone()).or_default().push(vs);
-                                    }
-                                }
-                                for (k, vs) in output.drain() {
-                                    let output = compute(&k, Window::new(vs.as_slice()), wr);
-                                    tx.send(KeyedEvent::Data(time, k, output.clone()))
-                                        .await?;
+                        for t0 in slices.keys() {
+                            let wr = WindowRange::new(*t0, *t0 + size);
+                            if wr.t1 >= time {
+                                break;
+                            }
+                            if wr.t1 < watermark {
+                                continue;
+                            }
+                            let mut output: HashMap<K, Vec<&[T]>> = HashMap::default();
+                            for (_, kvs) in slices.range(wr.t0..wr.t1) {
+                                for (k, vs) in kvs {
+                                    output.entry(k.clone()).or_default().push(vs);
                                 }
+                            }
+                            for (k, vs) in output.drain() {
+                                let output = compute(&k, Window::new(vs.as_slice()), wr);
+                                tx.send(KeyedEvent::Data(time, k, output.clone())).await?;
+                            }
+                        }
+                        while let Some((t0, _)) = slices.first_key_value() {
+                            if *t0 + size + allowed < time {
                                 slices.pop_first();
                             } else {
                                 break;
                             }
                         }
+                        watermark = time;
                         tx.send(KeyedEvent::Watermark(time)).await?;
+                        late.send(KeyedEvent::Watermark(time)).await.ok();
                     }
                     KeyedEvent::Snapshot(i) => {
+                        state.snapshot(i, &(&slices, watermark));
                         tx.send(KeyedEvent::Snapshot(i)).await?;
+                        late.send(KeyedEvent::Snapshot(i)).await.ok();
                     }
                     KeyedEvent::Sentinel => {
+                        late.send(KeyedEvent::Sentinel).await.ok();
                         tx.send(KeyedEvent::Sentinel).await?;
                         break;
                     }
@@ -1,6 +1,7 @@
 use crate::builtins::duration::Duration;
 use crate::builtins::keyed_stream::KeyedEvent;
 use crate::builtins::keyed_stream::KeyedStream;
+use crate::builtins::lateness::Lateness;
 use crate::builtins::stream::window::align;
 use crate::builtins::stream::window::WindowRange;
 use crate::builtins::time::Time;
@@ -12,7 +13,7 @@ use crate::HashMap;
 
 impl<K: Key, T: Data> KeyedStream<K, T> {
     pub fn time_tumbling_holistic_window<O>(
-        mut self,
+        self,
         ctx: &mut Context,
         duration: Duration,
         compute: impl for<'a> Fn(&K, &'a [T], WindowRange) -> O + Send + 'static,
@@ -20,38 +21,80 @@ impl<K: Key, T: Data> KeyedStream<K, T> {
     where
         O: Data,
     {
-        ctx.keyed_operator(move |tx| async move {
-            let mut aggs: BTreeMap<Time, HashMap<K, Vec<T>>> = BTreeMap::new();
+        self.time_tumbling_holistic_window_with_lateness(ctx, duration, Lateness::Drop, compute)
+            .0
+    }
+
+    /// Like `time_tumbling_holistic_window`, but events whose window has
+    /// already fired are handled by the lateness policy. The second stream is
+    /// the late output.
+    pub fn time_tumbling_holistic_window_with_lateness<O>(
+        mut self,
+        ctx: &mut Context,
+        duration: Duration,
+        lateness: Lateness,
+        compute: impl for<'a> Fn(&K, &'a [T], WindowRange) -> O + Send + 'static,
+    ) -> (KeyedStream<K, O>, KeyedStream<K, T>)
+    where
+        O: Data,
+    {
+        let state = ctx.operator_state();
+        let allowed = lateness.allowed_lateness();
+        ctx.keyed_co_operator(move |tx, late| async move {
+            let (mut aggs, mut watermark): (BTreeMap<Time, HashMap<K, Vec<T>>>, Time) = state
+                .restore()
+                .unwrap_or_else(|| (BTreeMap::new(), Time::zero()));
             loop {
                 match self.recv().await {
                     KeyedEvent::Data(time, key, data) => {
                         let t0 = align(time, duration);
-                        aggs.entry(t0)
-                            .or_default()
-                            .entry(key)
-                            .or_default()
-                            .push(data);
+                        let wr = WindowRange::new(t0, t0 + duration);
+                        if wr.t1 + allowed < watermark {
+                            if lateness.routes_late() {
+                                late.send(KeyedEvent::Data(time, key, data)).await.ok();
+                            }
+                            continue;
+                        }
+                        let vs = aggs.entry(t0).or_default().entry(key.clone()).or_default();
+                        vs.push(data);
+                        if wr.t1 < watermark {
+                            // The window has already fired, so fire it again.
+                            let data = compute(&key, vs, wr);
+                            tx.send(KeyedEvent::Data(wr.t1, key, data)).await?;
+                        }
                     }
                     KeyedEvent::Watermark(time) => {
+                        for (t0, kvs) in &aggs {
+                            let wr = WindowRange::new(*t0, *t0 + duration);
+                            if wr.t1 >= time {
+                                break;
+                            }
+                            if wr.t1 < watermark {
+                                continue;
+                            }
+                            for (key, vs) in kvs {
+                                let data = compute(key, vs, wr);
+                                tx.send(KeyedEvent::Data(wr.t1, key.clone(), data)).await?;
+                            }
+                        }
                         while let Some(entry) = aggs.first_entry() {
-                            let t0 = *entry.key();
-                            let wr = WindowRange::new(t0, t0 + duration);
-                            if wr.t1 < time {
-                                let kvs = entry.remove();
-                                for (key, vs) in kvs {
-                                    let data = compute(&key, &vs, wr);
-                                    tx.send(KeyedEvent::Data(wr.t1, key, data)).await?;
-                                }
+                            if *entry.key() + duration + allowed < time {
+                                entry.remove();
                             } else {
                                 break;
                             }
                         }
+                        watermark = time;
                         tx.send(KeyedEvent::Watermark(time)).await?;
+                        late.send(KeyedEvent::Watermark(time)).await.ok();
                     }
                     KeyedEvent::Snapshot(i) => {
+                        state.snapshot(i, &(&aggs, watermark));
                         tx.send(KeyedEvent::Snapshot(i)).await?;
+                        late.send(KeyedEvent::Snapshot(i)).await.ok();
                     }
                     KeyedEvent::Sentinel => {
+                        late.send(KeyedEvent::Sentinel).await.ok();
                         tx.send(KeyedEvent::Sentinel).await?;
                         break;
                     }
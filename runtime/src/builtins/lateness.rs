@@ -0,0 +1,128 @@
+use std::convert::TryFrom;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::builtins::duration::Duration;
+use crate::error::Error;
+use crate::traits::DeepClone;
+
+/// What an operator does with an event which arrives after the watermark has
+/// passed it, i.e. after every window containing the event has fired.
+///
+/// Sources and event-time windows take a policy in their `_with_lateness`
+/// variants, which also return the late output. Count-based windows aggregate
+/// data in arrival order, so no event is late for them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Lateness {
+    /// Discard late events.
+    #[default]
+    Drop,
+    /// Route late events to the late output of the operator. Operators keep
+    /// running if the late output is dropped.
+    SideOutput,
+    /// Keep fired windows around for the given duration past the watermark.
+    /// Events which arrive in the meantime are added to their windows, which
+    /// then fire again with updated results. Events later than that are routed
+    /// to the late output. Only sources and the `time_tumbling_holistic` and
+    /// `time_sliding_aligned_holistic` windows support this policy. Windows
+    /// which fire once take a `FireOnceLateness` instead.
+    Allowed(Duration),
+}
+
+impl DeepClone for Lateness {
+    fn deep_clone(&self) -> Self {
+        *self
+    }
+}
+
+impl std::fmt::Display for Lateness {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Lateness::Drop => write!(f, "Drop"),
+            Lateness::SideOutput => write!(f, "SideOutput"),
+            Lateness::Allowed(duration) => write!(f, "Allowed({})", duration),
+        }
+    }
+}
+
+impl Lateness {
+    pub fn side_output() -> Self {
+        Self::SideOutput
+    }
+
+    pub fn allowed(duration: Duration) -> Self {
+        Self::Allowed(duration)
+    }
+
+    /// How long past the watermark late events are still accepted.
+    pub fn allowed_lateness(&self) -> Duration {
+        match self {
+            Lateness::Allowed(duration) => *duration,
+            _ => Duration::zero(),
+        }
+    }
+
+    /// Whether events which are not accepted go to the late output.
+    pub fn routes_late(&self) -> bool {
+        !matches!(self, Lateness::Drop)
+    }
+}
+
+/// The policies of `Lateness` which windows that fire only once support, since
+/// they cannot add late events to a window which has already fired.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FireOnceLateness {
+    /// Discard late events.
+    #[default]
+    Drop,
+    /// Route late events to the late output of the operator.
+    SideOutput,
+}
+
+impl DeepClone for FireOnceLateness {
+    fn deep_clone(&self) -> Self {
+        *self
+    }
+}
+
+impl std::fmt::Display for FireOnceLateness {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        Lateness::from(*self).fmt(f)
+    }
+}
+
+impl FireOnceLateness {
+    pub fn side_output() -> Self {
+        Self::SideOutput
+    }
+
+    /// Whether late events go to the late output.
+    pub fn routes_late(&self) -> bool {
+        Lateness::from(*self).routes_late()
+    }
+}
+
+impl From<FireOnceLateness> for Lateness {
+    fn from(lateness: FireOnceLateness) -> Self {
+        match lateness {
+            FireOnceLateness::Drop => Lateness::Drop,
+            FireOnceLateness::SideOutput => Lateness::SideOutput,
+        }
+    }
+}
+
+/// Fails on `Allowed`.
+impl TryFrom<Lateness> for FireOnceLateness {
+    type Error = Error;
+
+    fn try_from(lateness: Lateness) -> Result<Self, Error> {
+        match lateness {
+            Lateness::Drop => Ok(FireOnceLateness::Drop),
+            Lateness::SideOutput => Ok(FireOnceLateness::SideOutput),
+            Lateness::Allowed(_) => Err(Error::custom(
+                "Allowed lateness is not supported by windows which fire once",
+            )),
+        }
+    }
+}
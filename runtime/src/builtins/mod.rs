@@ -2,8 +2,12 @@ pub mod window;
 pub mod duration;
 pub mod format;
 pub mod keyed_stream;
+pub mod lateness;
 pub mod option;
 pub mod reader;
 pub mod stream;
 pub mod time;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod watermark;
 pub mod writer;
@@ -10,7 +10,7 @@ pub enum Reader {
     Stdin,
     File { path: PathBuf, watch: bool },
     Http { addr: SocketAddr },
-    Tcp { addr: SocketAddr },
+    Tcp { addr: SocketAddr, watch: bool },
     Kafka { addr: SocketAddr, topic: String },
 }
 
@@ -18,14 +18,17 @@ impl Reader {
     pub fn stdin() -> Self {
         Self::Stdin
     }
-    pub fn file(path: PathBuf, watch: bool) -> Self {
-        Self::File { path, watch }
+    pub fn file(path: impl Into<PathBuf>, watch: bool) -> Self {
+        Self::File {
+            path: path.into(),
+            watch,
+        }
     }
     pub fn http(addr: SocketAddr) -> Self {
         Self::Http { addr }
     }
-    pub fn tcp(addr: SocketAddr) -> Self {
-        Self::Tcp { addr }
+    pub fn tcp(addr: SocketAddr, watch: bool) -> Self {
+        Self::Tcp { addr, watch }
     }
     pub fn kafka(addr: SocketAddr, topic: String) -> Self {
         Self::Kafka { addr, topic }
@@ -1,4 +1,5 @@
 use crate::builtins::time::Time;
+use crate::error::Error;
 use crate::traits::Data;
 use serde::Deserialize;
 use serde::Serialize;
@@ -6,12 +7,18 @@ use tokio::sync::mpsc::Receiver;
 use tokio::sync::mpsc::Sender;
 
 pub mod assert;
+pub(crate) mod barrier;
 pub mod batch;
+pub mod broadcast;
+#[cfg(feature = "columnar")]
+pub mod columnar;
+pub mod connect;
 pub mod drain;
 pub mod filter;
 pub mod filter_map;
 pub mod flat_map;
 pub mod fork;
+pub(crate) mod frontier;
 pub mod join;
 pub mod keyby;
 pub mod map;
@@ -21,6 +28,9 @@ pub mod scan;
 pub mod sink;
 pub mod source;
 pub mod take;
+pub mod union;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 pub mod window;
 pub mod collect;
 pub mod sorted;
@@ -46,8 +56,8 @@ impl<T: Data> Stream<T> {
 }
 
 impl<T: Data> Collector<T> {
-    pub async fn send(&self, event: Event<T>) -> Result<(), SendError> {
-        self.0.send(event).await.map_err(|_| SendError::Closed)
+    pub async fn send(&self, event: Event<T>) -> Result<(), Error> {
+        self.0.send(event).await.map_err(|_| Error::Closed)
     }
 }
 
@@ -57,7 +67,3 @@ impl<T> Stream<T> {
         (Collector(tx), Stream(rx))
     }
 }
-
-pub enum SendError {
-    Closed,
-}
@@ -0,0 +1,49 @@
+/// Aligns snapshot barriers across the inputs of a multi-input operator.
+///
+/// An input which has delivered a barrier is blocked until the same barrier
+/// has arrived on every other input that has not yet terminated.
+pub(crate) struct Alignment {
+    pending: Vec<Option<usize>>,
+    done: Vec<bool>,
+}
+
+impl Alignment {
+    pub(crate) fn new(inputs: usize) -> Self {
+        Self {
+            pending: vec![None; inputs],
+            done: vec![false; inputs],
+        }
+    }
+
+    pub(crate) fn is_blocked(&self, input: usize) -> bool {
+        self.pending[input].is_some()
+    }
+
+    /// Registers a barrier on `input`. Returns the snapshot id once aligned.
+    pub(crate) fn arrive(&mut self, input: usize, snapshot: usize) -> Option<usize> {
+        self.pending[input] = Some(snapshot);
+        self.aligned()
+    }
+
+    /// Registers the termination of `input`. Returns the snapshot id if this
+    /// releases a barrier which the other inputs were waiting for.
+    pub(crate) fn finish(&mut self, input: usize) -> Option<usize> {
+        self.done[input] = true;
+        self.aligned()
+    }
+
+    fn aligned(&mut self) -> Option<usize> {
+        let snapshot = self.pending.iter().find_map(|p| *p)?;
+        let aligned = self
+            .pending
+            .iter()
+            .zip(self.done.iter())
+            .all(|(p, done)| *done || *p == Some(snapshot));
+        if aligned {
+            self.pending.iter_mut().for_each(|p| *p = None);
+            Some(snapshot)
+        } else {
+            None
+        }
+    }
+}
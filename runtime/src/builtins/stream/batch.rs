@@ -1,5 +1,8 @@
-use crate::builtins::duration::Duration;
+use crate::builtins::lateness::Lateness;
 use crate::builtins::time::Time;
+use crate::builtins::watermark::WatermarkStrategy;
+use crate::builtins::watermark::Watermarks;
+use crate::error::Error;
 use crate::runner::context::Context;
 use crate::traits::Data;
 
@@ -7,34 +10,124 @@ use super::Event;
 use super::Stream;
 
 impl<T: Data> Stream<T> {
+    /// Emits the items of an iterator. Watermarks are emitted by the strategy
+    /// every `watermark_frequency` items, and punctuated watermarks right away.
     pub fn from_iter<I>(
         ctx: &mut Context,
         iter: I,
         f: impl Fn(&T) -> Time + Send + 'static,
         watermark_frequency: usize,
-        slack: Duration,
+        watermarks: impl Into<WatermarkStrategy<T>>,
     ) -> Stream<T>
     where
         I: IntoIterator<Item = T> + Send + 'static,
         <I as IntoIterator>::IntoIter: Send + 'static,
     {
-        ctx.operator(move |tx| async move {
-            let mut latest_time = Time::zero();
-            let mut watermark = Time::zero();
-            for (i, v) in iter.into_iter().enumerate() {
+        Self::from_iter_with_lateness(
+            ctx,
+            iter,
+            f,
+            watermark_frequency,
+            watermarks,
+            Lateness::Drop,
+        )
+        .0
+    }
+
+    /// Like `from_iter`, but events behind the watermark are handled by the
+    /// lateness policy. The second stream is the late output.
+    pub fn from_iter_with_lateness<I>(
+        ctx: &mut Context,
+        iter: I,
+        f: impl Fn(&T) -> Time + Send + 'static,
+        watermark_frequency: usize,
+        watermarks: impl Into<WatermarkStrategy<T>>,
+        lateness: Lateness,
+    ) -> (Stream<T>, Stream<T>)
+    where
+        I: IntoIterator<Item = T> + Send + 'static,
+        <I as IntoIterator>::IntoIter: Send + 'static,
+    {
+        Self::try_from_iter_with_lateness(
+            ctx,
+            iter.into_iter().map(Ok),
+            f,
+            watermark_frequency,
+            watermarks,
+            lateness,
+        )
+    }
+
+    /// Like `from_iter`, but the stream fails at the first error of the
+    /// iterator.
+    pub fn try_from_iter(
+        ctx: &mut Context,
+        iter: impl Iterator<Item = Result<T, Error>> + Send + 'static,
+        f: impl Fn(&T) -> Time + Send + 'static,
+        watermark_frequency: usize,
+        watermarks: impl Into<WatermarkStrategy<T>>,
+    ) -> Stream<T> {
+        Self::try_from_iter_with_lateness(
+            ctx,
+            iter,
+            f,
+            watermark_frequency,
+            watermarks,
+            Lateness::Drop,
+        )
+        .0
+    }
+
+    fn try_from_iter_with_lateness(
+        ctx: &mut Context,
+        iter: impl Iterator<Item = Result<T, Error>> + Send + 'static,
+        f: impl Fn(&T) -> Time + Send + 'static,
+        watermark_frequency: usize,
+        watermarks: impl Into<WatermarkStrategy<T>>,
+        lateness: Lateness,
+    ) -> (Stream<T>, Stream<T>) {
+        let state = ctx.operator_state();
+        let allowed = lateness.allowed_lateness();
+        let mut watermarks = Watermarks::new(watermarks.into());
+        ctx.co_operator(move |tx, late| async move {
+            let offset = match state.restore() {
+                Some((offset, s)) => {
+                    watermarks.restore(s);
+                    offset
+                }
+                None => 0,
+            };
+            let mut snapshot = state.restored();
+            let mut last_snapshot = std::time::Instant::now();
+            for (i, v) in iter.enumerate().skip(offset) {
+                let v = v?;
+                if let Some(interval) = state.interval() {
+                    if last_snapshot.elapsed() >= interval {
+                        snapshot += 1;
+                        last_snapshot = std::time::Instant::now();
+                        state.snapshot(snapshot, &(i, watermarks.state()));
+                        tx.send(Event::Snapshot(snapshot)).await?;
+                        late.send(Event::Snapshot(snapshot)).await.ok();
+                    }
+                }
                 let time = f(&v);
-                if time < watermark {
+                if time < watermarks.watermark() - allowed {
+                    if lateness.routes_late() {
+                        late.send(Event::Data(time, v)).await.ok();
+                    }
                     continue;
                 }
-                if time > latest_time {
-                    latest_time = time;
-                }
+                let mut watermark = watermarks.on_event(&v, time);
                 tx.send(Event::Data(time, v)).await?;
                 if i % watermark_frequency == 0 {
-                    watermark = latest_time - slack;
+                    watermark = watermarks.on_periodic().or(watermark);
+                }
+                if let Some(watermark) = watermark {
                     tx.send(Event::Watermark(watermark)).await?;
+                    late.send(Event::Watermark(watermark)).await.ok();
                 }
             }
+            late.send(Event::Sentinel).await.ok();
             tx.send(Event::Sentinel).await
         })
     }
@@ -0,0 +1,245 @@
+use std::collections::VecDeque;
+
+use crate::builtins::stream::barrier::Alignment;
+use crate::builtins::stream::connect::Signal;
+use crate::builtins::stream::frontier::Frontier;
+use crate::builtins::stream::Event;
+use crate::builtins::time::Time;
+use crate::error::Error;
+use crate::runner::context::Context;
+use crate::runner::exchange::Endpoints;
+use crate::traits::Data;
+
+use super::Stream;
+
+/// A data stream and a control stream whose elements are broadcast to every
+/// instance of the operator which processes them.
+pub struct BroadcastConnectedStreams<T, C>(Stream<T>, Stream<C>);
+
+/// The broadcast state and output of a `BroadcastConnectedStreams::process`
+/// operator, as seen by a data element.
+pub struct BroadcastContext<'a, S, O> {
+    time: Time,
+    watermark: Time,
+    state: &'a S,
+    output: &'a mut Vec<(Time, O)>,
+}
+
+impl<'a, S, O> BroadcastContext<'a, S, O> {
+    /// The time of the data element which is being processed.
+    pub fn time(&self) -> Time {
+        self.time
+    }
+
+    pub fn watermark(&self) -> Time {
+        self.watermark
+    }
+
+    /// The broadcast state. It is read-only, so that all instances hold the
+    /// same state.
+    pub fn state(&self) -> &S {
+        self.state
+    }
+
+    /// Emits an output at the current time.
+    pub fn emit(&mut self, data: O) {
+        self.output.push((self.time, data));
+    }
+
+    pub fn emit_at(&mut self, time: Time, data: O) {
+        self.output.push((time, data));
+    }
+}
+
+impl<T: Data> Stream<T> {
+    /// Sends every event to all workers of a `DataParallelRunner`, so that each
+    /// worker sees the elements of all workers. The output watermark is the
+    /// minimum over all workers, and snapshots are aligned across them.
+    /// Outside of a data-parallel runner, this is a no-op.
+    pub fn broadcast(mut self, ctx: &mut Context) -> Stream<T> {
+        let Some(Endpoints {
+            worker,
+            txs,
+            mut rx,
+        }) = ctx.exchange::<Event<T>>()
+        else {
+            return self;
+        };
+        let workers = txs.len();
+        ctx.spawn(async move {
+            loop {
+                let event = self.recv().await;
+                let done = matches!(event, Event::Sentinel);
+                for tx in &txs {
+                    tx.send((worker, event.clone()))
+                        .await
+                        .map_err(|_| Error::Closed)?;
+                }
+                if done {
+                    break;
+                }
+            }
+            Ok(())
+        });
+        ctx.operator(move |tx| async move {
+            let mut frontier = Frontier::new(workers);
+            let mut barriers = Alignment::new(workers);
+            // Events of workers which are blocked on a barrier.
+            let mut blocked: Vec<VecDeque<Event<T>>> =
+                (0..workers).map(|_| VecDeque::new()).collect();
+            loop {
+                let unblocked =
+                    (0..workers).find(|i| !barriers.is_blocked(*i) && !blocked[*i].is_empty());
+                let (i, event) = match unblocked {
+                    Some(i) => (i, blocked[i].pop_front().unwrap()),
+                    None => match rx.recv().await {
+                        Some(event) => event,
+                        None => {
+                            tx.send(Event::Sentinel).await?;
+                            break;
+                        }
+                    },
+                };
+                if barriers.is_blocked(i) {
+                    blocked[i].push_back(event);
+                    continue;
+                }
+                match event {
+                    Event::Data(t, data) => tx.send(Event::Data(t, data)).await?,
+                    Event::Watermark(t) => {
+                        if let Some(t) = frontier.advance(i, t) {
+                            tx.send(Event::Watermark(t)).await?;
+                        }
+                    }
+                    Event::Snapshot(s) => {
+                        if let Some(s) = barriers.arrive(i, s) {
+                            tx.send(Event::Snapshot(s)).await?;
+                        }
+                    }
+                    Event::Sentinel => {
+                        if let Some(t) = frontier.finish(i) {
+                            tx.send(Event::Watermark(t)).await?;
+                        }
+                        if let Some(s) = barriers.finish(i) {
+                            tx.send(Event::Snapshot(s)).await?;
+                        }
+                        if frontier.all_done() {
+                            tx.send(Event::Sentinel).await?;
+                            break;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Connects a control stream, which is broadcast to every worker. See
+    /// `BroadcastConnectedStreams::process`.
+    pub fn connect_broadcast<C: Data>(
+        self,
+        ctx: &mut Context,
+        control: Stream<C>,
+    ) -> BroadcastConnectedStreams<T, C> {
+        BroadcastConnectedStreams(self, control.broadcast(ctx))
+    }
+}
+
+impl<T: Data, C: Data> BroadcastConnectedStreams<T, C> {
+    /// Applies control elements to a broadcast state as soon as they arrive,
+    /// and processes data elements against the current state. Unlike merging
+    /// and sorting both streams, data is not held back until the watermark.
+    /// Control elements which are ready take precedence over data elements.
+    ///
+    /// Every worker receives the same control elements, so the state is the
+    /// same on all workers. The watermark is the minimum of both inputs, and
+    /// the state is included in snapshots.
+    pub fn process<S, O>(
+        self,
+        ctx: &mut Context,
+        mut on_control: impl FnMut(&mut S, C) + Send + 'static,
+        mut on_data: impl FnMut(&mut BroadcastContext<S, O>, T) + Send + 'static,
+    ) -> Stream<O>
+    where
+        S: Data + Default,
+        O: Data,
+    {
+        let BroadcastConnectedStreams(mut data, mut control) = self;
+        let state = ctx.operator_state();
+        ctx.operator(move |tx| async move {
+            let mut s: S = state.restore().unwrap_or_default();
+            let mut frontier = Frontier::new(2);
+            let mut barriers = Alignment::new(2);
+            let mut watermark = Time::zero();
+            let mut output = Vec::new();
+            loop {
+                let (i, signal) = tokio::select! {
+                    biased;
+                    event = control.recv(), if !frontier.is_done(1) && !barriers.is_blocked(1) => {
+                        let signal = match event {
+                            Event::Data(_, data) => {
+                                on_control(&mut s, data);
+                                None
+                            }
+                            Event::Watermark(t) => Some(Signal::Watermark(t)),
+                            Event::Snapshot(i) => Some(Signal::Snapshot(i)),
+                            Event::Sentinel => Some(Signal::Sentinel),
+                        };
+                        (1, signal)
+                    },
+                    event = data.recv(), if !frontier.is_done(0) && !barriers.is_blocked(0) => {
+                        let signal = match event {
+                            Event::Data(time, data) => {
+                                let mut ctx = BroadcastContext {
+                                    time,
+                                    watermark,
+                                    state: &s,
+                                    output: &mut output,
+                                };
+                                on_data(&mut ctx, data);
+                                None
+                            }
+                            Event::Watermark(t) => Some(Signal::Watermark(t)),
+                            Event::Snapshot(i) => Some(Signal::Snapshot(i)),
+                            Event::Sentinel => Some(Signal::Sentinel),
+                        };
+                        (0, signal)
+                    },
+                };
+                for (t, data) in output.drain(..) {
+                    tx.send(Event::Data(t, data)).await?;
+                }
+                match signal {
+                    None => {}
+                    Some(Signal::Watermark(t)) => {
+                        if let Some(t) = frontier.advance(i, t) {
+                            watermark = t;
+                            tx.send(Event::Watermark(t)).await?;
+                        }
+                    }
+                    Some(Signal::Snapshot(id)) => {
+                        if let Some(id) = barriers.arrive(i, id) {
+                            state.snapshot(id, &s);
+                            tx.send(Event::Snapshot(id)).await?;
+                        }
+                    }
+                    Some(Signal::Sentinel) => {
+                        if let Some(t) = frontier.finish(i) {
+                            watermark = t;
+                            tx.send(Event::Watermark(t)).await?;
+                        }
+                        if let Some(id) = barriers.finish(i) {
+                            state.snapshot(id, &s);
+                            tx.send(Event::Snapshot(id)).await?;
+                        }
+                        if frontier.all_done() {
+                            tx.send(Event::Sentinel).await?;
+                            break;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,58 @@
+use std::path::Path;
+
+use crate::builtins::time::Time;
+use crate::builtins::watermark::WatermarkStrategy;
+use crate::error::Error;
+use crate::formats::columnar;
+use crate::runner::context::Context;
+use crate::traits::Data;
+
+use super::Stream;
+
+impl<T: Data> Stream<T> {
+    /// Reads the records of an Arrow IPC or Parquet file. The stream fails if
+    /// the file cannot be opened or read.
+    pub fn from_columnar(
+        ctx: &mut Context,
+        path: impl AsRef<Path>,
+        f: impl Fn(&T) -> Time + Send + 'static,
+        watermark_frequency: usize,
+        watermarks: impl Into<WatermarkStrategy<T>>,
+    ) -> Stream<T> {
+        let iter = columnar::records(path.as_ref()).map_err(|e| open_error(path.as_ref(), e));
+        Self::try_from_iter(ctx, flatten(iter), f, watermark_frequency, watermarks)
+    }
+}
+
+impl<T: Data> Stream<Vec<T>> {
+    /// Reads the record batches of an Arrow IPC or Parquet file. Each batch is
+    /// timestamped with the latest time of its records. The stream fails like
+    /// `Stream::from_columnar`.
+    pub fn from_columnar_batches(
+        ctx: &mut Context,
+        path: impl AsRef<Path>,
+        f: impl Fn(&T) -> Time + Send + 'static,
+        watermark_frequency: usize,
+        watermarks: impl Into<WatermarkStrategy<Vec<T>>>,
+    ) -> Stream<Vec<T>> {
+        let f = move |batch: &Vec<T>| batch.iter().map(&f).max().unwrap_or(Time::zero());
+        let iter = columnar::batches(path.as_ref()).map_err(|e| open_error(path.as_ref(), e));
+        Self::try_from_iter(ctx, flatten(iter), f, watermark_frequency, watermarks)
+    }
+}
+
+fn open_error(path: &Path, e: std::io::Error) -> Error {
+    let msg = format!("Failed to open file `{}`: {}", path.display(), e);
+    std::io::Error::new(e.kind(), msg).into()
+}
+
+/// Turns a failure to open a file into the only item of its records.
+fn flatten<T>(
+    iter: Result<impl Iterator<Item = Result<T, Error>>, Error>,
+) -> impl Iterator<Item = Result<T, Error>> {
+    let (iter, error) = match iter {
+        Ok(iter) => (Some(iter), None),
+        Err(e) => (None, Some(Err(e))),
+    };
+    error.into_iter().chain(iter.into_iter().flatten())
+}
@@ -0,0 +1,154 @@
+use crate::builtins::stream::barrier::Alignment;
+use crate::builtins::stream::frontier::Frontier;
+use crate::builtins::stream::Event;
+use crate::builtins::time::Time;
+use crate::runner::context::Context;
+use crate::traits::Data;
+
+use super::Stream;
+
+/// Two streams of different types which are processed by one operator.
+pub struct ConnectedStreams<T0, T1>(Stream<T0>, Stream<T1>);
+
+/// The events of an input which are handled by the operator itself.
+pub(crate) enum Signal {
+    Watermark(Time),
+    Snapshot(usize),
+    Sentinel,
+}
+
+/// The state and output of a `ConnectedStreams::process` operator.
+pub struct CoProcessContext<'a, S, O> {
+    time: Time,
+    watermark: Time,
+    state: &'a mut S,
+    output: &'a mut Vec<(Time, O)>,
+}
+
+impl<'a, S, O> CoProcessContext<'a, S, O> {
+    /// The time of the event which is being processed.
+    pub fn time(&self) -> Time {
+        self.time
+    }
+
+    pub fn watermark(&self) -> Time {
+        self.watermark
+    }
+
+    pub fn state(&mut self) -> &mut S {
+        self.state
+    }
+
+    /// Emits an output at the current time.
+    pub fn emit(&mut self, data: O) {
+        self.output.push((self.time, data));
+    }
+
+    pub fn emit_at(&mut self, time: Time, data: O) {
+        self.output.push((time, data));
+    }
+}
+
+impl<T: Data> Stream<T> {
+    pub fn connect<U: Data>(self, other: Stream<U>) -> ConnectedStreams<T, U> {
+        ConnectedStreams(self, other)
+    }
+}
+
+impl<T0: Data, T1: Data> ConnectedStreams<T0, T1> {
+    /// Processes the events of both inputs with shared state. The watermark
+    /// is the minimum of the inputs, and the state is included in snapshots.
+    pub fn process<S, O>(
+        self,
+        ctx: &mut Context,
+        mut on_left: impl FnMut(&mut CoProcessContext<S, O>, T0) + Send + 'static,
+        mut on_right: impl FnMut(&mut CoProcessContext<S, O>, T1) + Send + 'static,
+    ) -> Stream<O>
+    where
+        S: Data + Default,
+        O: Data,
+    {
+        let ConnectedStreams(mut left, mut right) = self;
+        let state = ctx.operator_state();
+        ctx.operator(move |tx| async move {
+            let mut s: S = state.restore().unwrap_or_default();
+            let mut frontier = Frontier::new(2);
+            let mut barriers = Alignment::new(2);
+            let mut watermark = Time::zero();
+            let mut output = Vec::new();
+            loop {
+                let (i, signal) = tokio::select! {
+                    event = left.recv(), if !frontier.is_done(0) && !barriers.is_blocked(0) => {
+                        let signal = match event {
+                            Event::Data(time, data) => {
+                                let mut ctx = CoProcessContext {
+                                    time,
+                                    watermark,
+                                    state: &mut s,
+                                    output: &mut output,
+                                };
+                                on_left(&mut ctx, data);
+                                None
+                            }
+                            Event::Watermark(t) => Some(Signal::Watermark(t)),
+                            Event::Snapshot(i) => Some(Signal::Snapshot(i)),
+                            Event::Sentinel => Some(Signal::Sentinel),
+                        };
+                        (0, signal)
+                    },
+                    event = right.recv(), if !frontier.is_done(1) && !barriers.is_blocked(1) => {
+                        let signal = match event {
+                            Event::Data(time, data) => {
+                                let mut ctx = CoProcessContext {
+                                    time,
+                                    watermark,
+                                    state: &mut s,
+                                    output: &mut output,
+                                };
+                                on_right(&mut ctx, data);
+                                None
+                            }
+                            Event::Watermark(t) => Some(Signal::Watermark(t)),
+                            Event::Snapshot(i) => Some(Signal::Snapshot(i)),
+                            Event::Sentinel => Some(Signal::Sentinel),
+                        };
+                        (1, signal)
+                    },
+                };
+                for (t, data) in output.drain(..) {
+                    tx.send(Event::Data(t, data)).await?;
+                }
+                match signal {
+                    None => {}
+                    Some(Signal::Watermark(t)) => {
+                        if let Some(t) = frontier.advance(i, t) {
+                            watermark = t;
+                            tx.send(Event::Watermark(t)).await?;
+                        }
+                    }
+                    Some(Signal::Snapshot(id)) => {
+                        if let Some(id) = barriers.arrive(i, id) {
+                            state.snapshot(id, &s);
+                            tx.send(Event::Snapshot(id)).await?;
+                        }
+                    }
+                    Some(Signal::Sentinel) => {
+                        if let Some(t) = frontier.finish(i) {
+                            watermark = t;
+                            tx.send(Event::Watermark(t)).await?;
+                        }
+                        if let Some(id) = barriers.finish(i) {
+                            state.snapshot(id, &s);
+                            tx.send(Event::Snapshot(id)).await?;
+                        }
+                        if frontier.all_done() {
+                            tx.send(Event::Sentinel).await?;
+                            break;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+}
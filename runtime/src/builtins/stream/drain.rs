@@ -6,10 +6,13 @@ use super::Stream;
 
 impl<T: Data> Stream<T> {
     pub fn drain(mut self, ctx: &mut Context) {
+        let state = ctx.sink_state();
         ctx.sink(|| async move {
             loop {
-                if let Event::Sentinel = self.recv().await {
-                    break;
+                match self.recv().await {
+                    Event::Snapshot(i) => state.ack(i),
+                    Event::Sentinel => break,
+                    _ => {}
                 }
             }
             Ok(())
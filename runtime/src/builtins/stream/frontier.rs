@@ -0,0 +1,63 @@
+use crate::builtins::time::Time;
+
+/// Tracks the watermarks of the inputs of a multi-input operator.
+///
+/// The output watermark is the minimum over the inputs which have not yet
+/// terminated, and only moves forward. Once every input has terminated, it is
+/// the maximum, since no input will deliver further events.
+pub(crate) struct Frontier {
+    watermarks: Vec<Time>,
+    done: Vec<bool>,
+    watermark: Time,
+}
+
+impl Frontier {
+    pub(crate) fn new(inputs: usize) -> Self {
+        Self {
+            watermarks: vec![Time::zero(); inputs],
+            done: vec![false; inputs],
+            watermark: Time::zero(),
+        }
+    }
+
+    pub(crate) fn is_done(&self, input: usize) -> bool {
+        self.done[input]
+    }
+
+    pub(crate) fn all_done(&self) -> bool {
+        self.done.iter().all(|done| *done)
+    }
+
+    /// Registers a watermark on `input`. Returns the output watermark if it
+    /// has advanced.
+    pub(crate) fn advance(&mut self, input: usize, time: Time) -> Option<Time> {
+        self.watermarks[input] = time;
+        self.update()
+    }
+
+    /// Registers the termination of `input`. Returns the output watermark if
+    /// the input was holding it back.
+    pub(crate) fn finish(&mut self, input: usize) -> Option<Time> {
+        self.done[input] = true;
+        self.update()
+    }
+
+    fn update(&mut self) -> Option<Time> {
+        let watermark = if self.all_done() {
+            self.watermarks.iter().max().copied()?
+        } else {
+            self.watermarks
+                .iter()
+                .zip(self.done.iter())
+                .filter(|(_, done)| !**done)
+                .map(|(t, _)| *t)
+                .min()?
+        };
+        if watermark > self.watermark {
+            self.watermark = watermark;
+            Some(watermark)
+        } else {
+            None
+        }
+    }
+}
@@ -2,15 +2,19 @@ use std::collections::VecDeque;
 
 use crate::builtins::duration::Duration;
 use crate::builtins::stream::Collector;
+use crate::builtins::stream::barrier::Alignment;
 use crate::builtins::stream::Event;
-use crate::builtins::stream::SendError;
 use crate::builtins::stream::Stream;
 use crate::builtins::time::Time;
+use crate::error::Error;
 use crate::runner::context::Context;
 use crate::traits::Data;
 use crate::traits::Key;
 use crate::HashMap;
 
+use serde::Deserialize;
+use serde::Serialize;
+
 impl<T: Data> Stream<T> {
     #[allow(clippy::too_many_arguments)]
     pub fn interval_join<R, K, O>(
@@ -28,15 +32,17 @@ impl<T: Data> Stream<T> {
         K: Data + Key,
         O: Data,
     {
+        let state = ctx.operator_state();
         ctx.operator(move |tx| async move {
-            let mut s: State<K, T, R> = State::new(lower_bound, upper_bound);
-            let mut l_watermark = Time::zero();
-            let mut r_watermark = Time::zero();
+            let (mut s, mut l_watermark, mut r_watermark): (State<K, T, R>, Time, Time) = state
+                .restore()
+                .unwrap_or_else(|| (State::new(lower_bound, upper_bound), Time::zero(), Time::zero()));
             let mut done_l = false;
             let mut done_r = false;
+            let mut barriers = Alignment::new(2);
             loop {
                 tokio::select! {
-                    event = self.recv(), if !done_l => match event {
+                    event = self.recv(), if !done_l && !barriers.is_blocked(0) => match event {
                         Event::Data(time, data) => {
                             let key = left_key(&data);
                             s.incremental_join_left(key, time, data, &joiner, &tx).await?;
@@ -56,10 +62,19 @@ impl<T: Data> Stream<T> {
                                 break;
                             }
                             done_l = true;
+                            if let Some(i) = barriers.finish(0) {
+                                state.snapshot(i, &(&s, l_watermark, r_watermark));
+                                tx.send(Event::Snapshot(i)).await?;
+                            }
+                        }
+                        Event::Snapshot(i) => {
+                            if let Some(i) = barriers.arrive(0, i) {
+                                state.snapshot(i, &(&s, l_watermark, r_watermark));
+                                tx.send(Event::Snapshot(i)).await?;
+                            }
                         }
-                        Event::Snapshot(_) => unimplemented!()
                     },
-                    event = other.recv(), if !done_r => match event {
+                    event = other.recv(), if !done_r && !barriers.is_blocked(1) => match event {
                         Event::Data(time, data) => {
                             let key = right_key(&data);
                             s.incremental_join_right(key, time, data, &joiner, &tx).await?;
@@ -79,8 +94,17 @@ impl<T: Data> Stream<T> {
                                 break;
                             }
                             done_r = true;
+                            if let Some(i) = barriers.finish(1) {
+                                state.snapshot(i, &(&s, l_watermark, r_watermark));
+                                tx.send(Event::Snapshot(i)).await?;
+                            }
+                        }
+                        Event::Snapshot(i) => {
+                            if let Some(i) = barriers.arrive(1, i) {
+                                state.snapshot(i, &(&s, l_watermark, r_watermark));
+                                tx.send(Event::Snapshot(i)).await?;
+                            }
                         }
-                        Event::Snapshot(_) => unimplemented!(),
                     },
                 };
             }
@@ -89,6 +113,8 @@ impl<T: Data> Stream<T> {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "K: Key, L: Data, R: Data"))]
 struct State<K, L, R> {
     lslices: SliceSeq<K, L>,
     rslices: SliceSeq<K, R>,
@@ -96,8 +122,12 @@ struct State<K, L, R> {
     upper_bound: Duration,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "K: Key, T: Data"))]
 struct SliceSeq<K, T>(VecDeque<Slice<K, T>>);
 
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "K: Key, T: Data"))]
 struct Slice<K, T> {
     latest: Time,
     earliest: Time,
@@ -184,7 +214,7 @@ where
         upper_bound: Duration,
         joiner: impl Fn(&T, &R) -> O,
         tx: &Collector<O>,
-    ) -> Result<(), SendError> {
+    ) -> Result<(), Error> {
         self.push_data_or_create(time, key.clone(), data.clone());
         let earliest_possible = time - lower_bound;
         let latest_possible = time + upper_bound;
@@ -254,7 +284,7 @@ where
         ldata: L,
         joiner: impl Fn(&L, &R) -> O,
         tx: &Collector<O>,
-    ) -> Result<(), SendError> {
+    ) -> Result<(), Error> {
         SliceSeq::incremental_join(
             &mut self.lslices,
             &mut self.rslices,
@@ -276,7 +306,7 @@ where
         rdata: R,
         joiner: impl Fn(&L, &R) -> O,
         tx: &Collector<O>,
-    ) -> Result<(), SendError> {
+    ) -> Result<(), Error> {
         SliceSeq::incremental_join(
             &mut self.rslices,
             &mut self.lslices,
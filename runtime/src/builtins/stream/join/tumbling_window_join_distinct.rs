@@ -1,5 +1,6 @@
 use crate::builtins::duration::Duration;
 use crate::builtins::stream::window::align;
+use crate::builtins::stream::barrier::Alignment;
 use crate::builtins::stream::Event;
 use crate::builtins::stream::Stream;
 use crate::builtins::time::Time;
@@ -9,6 +10,9 @@ use crate::traits::Key;
 use crate::BTreeMap;
 use crate::HashMap;
 
+use serde::Deserialize;
+use serde::Serialize;
+
 impl<T: Data> Stream<T> {
     pub fn tumbling_window_join_distinct<R, K, O>(
         mut self,
@@ -24,15 +28,17 @@ impl<T: Data> Stream<T> {
         K: Data + Key,
         O: Data,
     {
+        let state = ctx.operator_state();
         ctx.operator(move |tx| async move {
-            let mut s: JoinState<K,T,R> = JoinState::default();
-            let mut l_watermark = Time::zero();
-            let mut r_watermark = Time::zero();
+            let (mut s, mut l_watermark, mut r_watermark): (JoinState<K, T, R>, Time, Time) = state
+                .restore()
+                .unwrap_or_else(|| (JoinState::default(), Time::zero(), Time::zero()));
             let mut done_l = false;
             let mut done_r = false;
+            let mut barriers = Alignment::new(2);
             loop {
                 tokio::select! {
-                    event = self.recv(), if !done_l => match event {
+                    event = self.recv(), if !done_l && !barriers.is_blocked(0) => match event {
                         Event::Data(time, data) => {
                             let key = left_key(&data);
                             let t0 = align(time, duration);
@@ -63,10 +69,19 @@ impl<T: Data> Stream<T> {
                                 break;
                             }
                             done_l = true;
+                            if let Some(i) = barriers.finish(0) {
+                                state.snapshot(i, &(&s, l_watermark, r_watermark));
+                                tx.send(Event::Snapshot(i)).await?;
+                            }
+                        }
+                        Event::Snapshot(i) => {
+                            if let Some(i) = barriers.arrive(0, i) {
+                                state.snapshot(i, &(&s, l_watermark, r_watermark));
+                                tx.send(Event::Snapshot(i)).await?;
+                            }
                         }
-                        Event::Snapshot(_) => unimplemented!(),
                     },
-                    event = other.recv(), if !done_r => match event {
+                    event = other.recv(), if !done_r && !barriers.is_blocked(1) => match event {
                         Event::Data(time, data) => {
                             let key = right_key(&data);
                             let t0 = align(time, duration);
@@ -97,8 +112,17 @@ impl<T: Data> Stream<T> {
                                 break;
                             }
                             done_r = true;
+                            if let Some(i) = barriers.finish(1) {
+                                state.snapshot(i, &(&s, l_watermark, r_watermark));
+                                tx.send(Event::Snapshot(i)).await?;
+                            }
+                        }
+                        Event::Snapshot(i) => {
+                            if let Some(i) = barriers.arrive(1, i) {
+                                state.snapshot(i, &(&s, l_watermark, r_watermark));
+                                tx.send(Event::Snapshot(i)).await?;
+                            }
                         }
-                        Event::Snapshot(_) => unimplemented!(),
                     },
                 };
             }
@@ -107,8 +131,12 @@ impl<T: Data> Stream<T> {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "K: Key, L: Data, R: Data"))]
 struct JoinState<K, L, R>(BTreeMap<Time, KeyState<K, L, R>>);
 
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = "K: Key, L: Data, R: Data"))]
 struct KeyState<K, L, R>(HashMap<K, State<L, R>>);
 
 impl<K, L, R> Default for JoinState<K, L, R> {
@@ -123,7 +151,7 @@ impl<K, L, R> Default for KeyState<K, L, R> {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Serialize, Deserialize)]
 enum State<L, R> {
     Left(L),
     Right(R),
@@ -1,3 +1,4 @@
+use crate::error::Error;
 use crate::runner::context::Context;
 use crate::traits::Data;
 
@@ -24,4 +25,34 @@ impl<T: Data> Stream<T> {
             Ok(())
         })
     }
+
+    /// Like `map`, but the function may fail. The first error cancels the
+    /// dataflow, and is returned by the runner.
+    pub fn try_map<O, E>(
+        mut self,
+        ctx: &mut Context,
+        f: impl Fn(T) -> Result<O, E> + Send + 'static,
+    ) -> Stream<O>
+    where
+        O: Data,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        ctx.operator(|tx| async move {
+            loop {
+                match self.recv().await {
+                    Event::Data(t, v) => {
+                        let v = f(v).map_err(Error::custom)?;
+                        tx.send(Event::Data(t, v)).await?
+                    }
+                    Event::Watermark(t) => tx.send(Event::Watermark(t)).await?,
+                    Event::Snapshot(i) => tx.send(Event::Snapshot(i)).await?,
+                    Event::Sentinel => {
+                        tx.send(Event::Sentinel).await?;
+                        break;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
 }
@@ -1,9 +1,11 @@
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 
+use crate::builtins::stream::barrier::Alignment;
 use crate::builtins::stream::Event;
 use crate::builtins::time::Time;
 use crate::runner::context::Context;
+use crate::state::OperatorState;
 use crate::traits::Data;
 
 use super::Stream;
@@ -39,9 +41,10 @@ impl<T: Data> Stream<T> {
             let mut r_done = false;
             let mut l_watermark = Time::zero();
             let mut r_watermark = Time::zero();
+            let mut barriers = Alignment::new(2);
             loop {
                 tokio::select! {
-                    event = self.recv(), if !l_done => match event {
+                    event = self.recv(), if !l_done && !barriers.is_blocked(0) => match event {
                         Event::Data(t, v) => tx.send(Event::Data(t, v)).await?,
                         Event::Watermark(t) => {
                             if t < r_watermark {
@@ -57,10 +60,17 @@ impl<T: Data> Stream<T> {
                                 tx.send(Event::Sentinel).await?;
                                 break;
                             }
+                            if let Some(i) = barriers.finish(0) {
+                                tx.send(Event::Snapshot(i)).await?;
+                            }
                         },
-                        Event::Snapshot(i) => tx.send(Event::Snapshot(i)).await?
+                        Event::Snapshot(i) => {
+                            if let Some(i) = barriers.arrive(0, i) {
+                                tx.send(Event::Snapshot(i)).await?;
+                            }
+                        }
                     },
-                    event = other.recv(), if !r_done => match event {
+                    event = other.recv(), if !r_done && !barriers.is_blocked(1) => match event {
                         Event::Data(t, v) => tx.send(Event::Data(t, v)).await?,
                         Event::Watermark(t) => {
                             if t < l_watermark {
@@ -76,8 +86,15 @@ impl<T: Data> Stream<T> {
                                 tx.send(Event::Sentinel).await?;
                                 break;
                             }
+                            if let Some(i) = barriers.finish(1) {
+                                tx.send(Event::Snapshot(i)).await?;
+                            }
                         },
-                        Event::Snapshot(i) => tx.send(Event::Snapshot(i)).await?
+                        Event::Snapshot(i) => {
+                            if let Some(i) = barriers.arrive(1, i) {
+                                tx.send(Event::Snapshot(i)).await?;
+                            }
+                        }
                     },
                 };
             }
@@ -86,35 +103,66 @@ impl<T: Data> Stream<T> {
     }
 
     pub fn sorted_merge(mut self, ctx: &mut Context, mut other: Self) -> Self {
+        let state = ctx.operator_state();
         ctx.operator(|tx| async move {
             let mut l_done = false;
             let mut r_done = false;
-            let mut l_watermark = Time::zero();
-            let mut r_watermark = Time::zero();
+            let (events, (mut l_watermark, mut r_watermark)): (Vec<Event<T>>, (Time, Time)) = state
+                .restore()
+                .unwrap_or_else(|| (Vec::new(), (Time::zero(), Time::zero())));
 
             let mut buffer = BinaryHeap::<HeapEntry<T>>::new();
             let mut seq = 0;
+            for event in events {
+                if let Event::Data(t, _) = event {
+                    buffer.push(HeapEntry { time: t, seq, event });
+                    seq += 1;
+                }
+            }
+            let mut barriers = Alignment::new(2);
 
             loop {
                 tokio::select! {
-                    event = self.recv(), if !l_done => match event {
+                    event = self.recv(), if !l_done && !barriers.is_blocked(0) => match event {
                         Event::Data(t, v) => {
                             buffer.push(HeapEntry { time: t, seq, event: Event::Data(t, v) });
                             seq += 1;
                         },
                         Event::Watermark(t) => l_watermark = t,
-                        Event::Sentinel => l_done = true,
-                        Event::Snapshot(i) => tx.send(Event::Snapshot(i)).await?,
+                        Event::Sentinel => {
+                            l_done = true;
+                            if let Some(i) = barriers.finish(0) {
+                                Self::snapshot_heap(&state, i, &buffer, (l_watermark, r_watermark));
+                                tx.send(Event::Snapshot(i)).await?;
+                            }
+                        },
+                        Event::Snapshot(i) => {
+                            if let Some(i) = barriers.arrive(0, i) {
+                                Self::snapshot_heap(&state, i, &buffer, (l_watermark, r_watermark));
+                                tx.send(Event::Snapshot(i)).await?;
+                            }
+                        },
                     },
 
-                    event = other.recv(), if !r_done => match event {
+                    event = other.recv(), if !r_done && !barriers.is_blocked(1) => match event {
                         Event::Data(t, v) => {
                             buffer.push(HeapEntry { time: t, seq, event: Event::Data(t, v) });
                             seq += 1;
                         },
                         Event::Watermark(t) => r_watermark = t,
-                        Event::Sentinel => r_done = true,
-                        Event::Snapshot(i) => tx.send(Event::Snapshot(i)).await?,
+                        Event::Sentinel => {
+                            r_done = true;
+                            if let Some(i) = barriers.finish(1) {
+                                Self::snapshot_heap(&state, i, &buffer, (l_watermark, r_watermark));
+                                tx.send(Event::Snapshot(i)).await?;
+                            }
+                        },
+                        Event::Snapshot(i) => {
+                            if let Some(i) = barriers.arrive(1, i) {
+                                Self::snapshot_heap(&state, i, &buffer, (l_watermark, r_watermark));
+                                tx.send(Event::Snapshot(i)).await?;
+                            }
+                        },
                     },
 
                     else => {println!("{:?}",buffer.peek())},
@@ -145,4 +193,14 @@ impl<T: Data> Stream<T> {
             Ok(())
         })
     }
+
+    fn snapshot_heap(
+        state: &OperatorState,
+        snapshot: usize,
+        buffer: &BinaryHeap<HeapEntry<T>>,
+        watermarks: (Time, Time),
+    ) {
+        let events: Vec<&Event<T>> = buffer.iter().map(|entry| &entry.event).collect();
+        state.snapshot(snapshot, &(events, watermarks));
+    }
 }
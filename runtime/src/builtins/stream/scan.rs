@@ -11,8 +11,9 @@ impl<T: Data> Stream<T> {
         init: A,
         fun: impl Fn(T, A) -> A + Send + 'static,
     ) -> Stream<A> {
+        let state = ctx.operator_state();
         ctx.operator(|tx| async move {
-            let mut acc = init;
+            let mut acc = state.restore().unwrap_or(init);
             loop {
                 match self.recv().await {
                     Event::Data(t, v) => {
@@ -23,6 +24,7 @@ impl<T: Data> Stream<T> {
                         tx.send(Event::Watermark(t)).await?;
                     }
                     Event::Snapshot(i) => {
+                        state.snapshot(i, &acc);
                         tx.send(Event::Snapshot(i)).await?;
                     }
                     Event::Sentinel => {
@@ -1,13 +1,44 @@
-use std::io::BufWriter;
-use std::io::Write;
+#[cfg(feature = "kafka")]
+use std::collections::VecDeque;
 use std::net::SocketAddr;
-use std::net::TcpStream;
 use std::path::PathBuf;
 
+use http_body_util::BodyExt;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header;
+use hyper::Request;
+use hyper::StatusCode;
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::client::legacy::Client;
+use hyper_util::rt::TokioExecutor;
+#[cfg(feature = "kafka")]
+use rdkafka::error::KafkaError;
+#[cfg(feature = "kafka")]
+use rdkafka::error::RDKafkaErrorCode;
+#[cfg(feature = "kafka")]
+use rdkafka::producer::DeliveryFuture;
+#[cfg(feature = "kafka")]
+use rdkafka::producer::FutureProducer;
+#[cfg(feature = "kafka")]
+use rdkafka::producer::FutureRecord;
+#[cfg(feature = "kafka")]
+use rdkafka::ClientConfig;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufWriter;
+use tokio::net::TcpStream;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::time::Instant;
+use url::Url;
+
 use crate::builtins::format::Format;
 use crate::builtins::writer::Writer;
+use crate::error::Error;
 use crate::formats::Encode;
+use crate::formats::Framing;
+use crate::io::Output;
 use crate::runner::context::Context;
+use crate::state::OperatorState;
 use crate::traits::Data;
 
 use super::Event;
@@ -15,118 +46,468 @@ use super::Stream;
 
 impl<T: Data> Stream<T> {
     pub fn sink(self, ctx: &mut Context, writer: Writer, encoding: Format) {
+        let framing = encoding.framing();
+        match encoding {
+            Format::Csv { sep } => {
+                let encoder = crate::formats::csv::ser::Writer::new(sep);
+                self.sink_encoder(ctx, writer, framing, encoder);
+            }
+            Format::Json => {
+                let encoder = crate::formats::json::ser::Writer::new();
+                self.sink_encoder(ctx, writer, framing, encoder);
+            }
+            Format::MessagePack => {
+                let encoder = crate::formats::msgpack::ser::Writer::new();
+                self.sink_encoder(ctx, writer, framing, encoder);
+            }
+            Format::Cbor => {
+                let encoder = crate::formats::cbor::ser::Writer::new();
+                self.sink_encoder(ctx, writer, framing, encoder);
+            }
+            Format::Bincode => {
+                let encoder = crate::formats::bincode::ser::Writer::new();
+                self.sink_encoder(ctx, writer, framing, encoder);
+            }
+        }
+    }
+
+    // Only Kafka sinks use the framing of the encoding.
+    #[cfg_attr(not(feature = "kafka"), allow(unused_variables))]
+    fn sink_encoder(
+        self,
+        ctx: &mut Context,
+        writer: Writer,
+        framing: Framing,
+        encoder: impl Encode + Send + 'static,
+    ) {
+        let writer = match writer {
+            #[cfg(feature = "kafka")]
+            Writer::Kafka { addr, topic } => {
+                return self.write_kafka(ctx, addr, topic, framing, encoder)
+            }
+            Writer::Http { url } => return self.write_http(ctx, url, encoder),
+            writer => writer,
+        };
+        // Snapshots are passed on to the writer, which acknowledges them once
+        // every record before them has been written.
         let mut this = self;
-        let (tx, rx) = std::sync::mpsc::channel();
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let state = ctx.sink_state();
         ctx.sink(|| async move {
             loop {
                 let event = this.recv().await;
                 match event {
-                    Event::Data(_, data) => tx.send(data).unwrap(),
+                    Event::Data(..) | Event::Snapshot(_) => {
+                        tx.send(event).map_err(|_| Error::Closed)?
+                    }
                     Event::Watermark(_) => continue,
-                    Event::Snapshot(_) => todo!(),
                     Event::Sentinel => break,
                 }
             }
             Ok(())
         });
-        Self::sink_encoding(ctx, rx, writer, encoding);
+        Self::sink_writer(ctx, rx, state, writer, encoder);
     }
 
+    /// Writes encoded records to `tx`. A snapshot is acknowledged once every
+    /// record before it has been flushed, and stores the number of bytes
+    /// written so far.
     async fn write_pipe(
-        rx: std::sync::mpsc::Receiver<T>,
+        mut rx: UnboundedReceiver<Event<T>>,
+        state: OperatorState,
         mut encoder: impl Encode + Send + 'static,
-        tx: impl Write + Unpin,
-    ) {
-        let mut tx = BufWriter::new(tx);
+        mut tx: Output,
+        mut offset: u64,
+    ) -> Result<(), Error> {
         let mut buf = vec![0; 1024];
         loop {
-            match rx.recv() {
-                Ok(data) => match encoder.encode(&data, &mut buf) {
+            match rx.recv().await {
+                Some(Event::Data(_, data)) => match encoder.encode(&data, &mut buf) {
                     Ok(n) => {
                         tracing::info!("Encoded: {:?}", data);
-                        tx.write_all(&buf[0..n]).unwrap();
+                        tx.write_all(&buf[0..n]).await?;
+                        offset += n as u64;
                     }
                     Err(e) => tracing::info!("Failed to encode: {}", e),
                 },
-                Err(_) => {
-                    tx.flush().unwrap();
+                Some(Event::Snapshot(i)) => {
+                    tx.flush().await?;
+                    state.snapshot(i, &offset);
+                    state.ack(i);
+                }
+                Some(_) => {}
+                None => {
+                    tx.flush().await?;
                     break;
                 }
             }
         }
+        Ok(())
     }
 
+    /// Creates the file, or, when restored from a snapshot, truncates it to
+    /// the bytes written before that snapshot and appends to it.
     async fn write_file(
-        rx: std::sync::mpsc::Receiver<T>,
+        rx: UnboundedReceiver<Event<T>>,
+        state: OperatorState,
         path: PathBuf,
         encoder: impl Encode + Send + 'static,
-    ) {
-        match std::fs::File::create(&path) {
-            Ok(tx) => Self::write_pipe(rx, encoder, tx).await,
-            Err(e) => panic!("Failed to open file `{}`: {}", path.display(), e),
+    ) -> Result<(), Error> {
+        let offset = state.restore::<u64>().unwrap_or(0);
+        match Output::create_at(&path, offset).await {
+            Ok(tx) => Self::write_pipe(rx, state, encoder, tx, offset).await,
+            Err(e) => {
+                let msg = format!("Failed to open file `{}`: {}", path.display(), e);
+                Err(std::io::Error::new(e.kind(), msg).into())
+            }
         }
     }
 
+    /// Writes framed records to `addr`. If the connection cannot
+    /// be established or is lost, the sink reconnects with exponential
+    /// backoff, and buffers up to `TCP_BUFFER_SIZE` records in the meantime.
+    /// Records are kept until they have been flushed, so records which were
+    /// in flight when a connection was lost are sent again. A snapshot is
+    /// acknowledged once every record before it has been flushed. The sink fails if
+    /// its buffer overflows, or if it cannot reconnect at the maximum backoff
+    /// once the input has ended.
     async fn write_socket(
-        rx: std::sync::mpsc::Receiver<T>,
+        mut rx: UnboundedReceiver<Event<T>>,
+        state: OperatorState,
         addr: SocketAddr,
         mut encoder: impl Encode + 'static,
-    ) {
-        println!("Connecting to {}", addr);
-        let socket = TcpStream::connect(addr).expect("Failed to connect");
-        println!("Connected to {}", addr);
-        
-        let mut writer = BufWriter::new(socket);
+    ) -> Result<(), Error> {
+        let mut buffer: Vec<Vec<u8>> = Vec::new();
+        let mut snapshots = Vec::new();
+        let mut socket: Option<BufWriter<TcpStream>> = None;
+        let mut backoff = TCP_BACKOFF;
+        let mut retry = Instant::now();
         let mut buf = vec![0; 1024];
-
-        while let Ok(data) = rx.recv() {
-            match encoder.encode(&data, &mut buf) {
-                Ok(n) => {
-                    println!("Encoded: {:?}", data);
-                    let s = std::str::from_utf8(&buf[..n - 1]).unwrap();
-                    println!("Sending: [{}]", s);
-                    writer.write_all(s.as_bytes()).unwrap();
-                    writer.write_all(b"\n").unwrap();
-                    writer.flush().unwrap();
+        let mut closed = false;
+        while !closed || !buffer.is_empty() {
+            if !closed {
+                match tokio::time::timeout(backoff, rx.recv()).await {
+                    Ok(Some(Event::Data(_, data))) => match encoder.encode(&data, &mut buf) {
+                        Ok(_) if buffer.len() == TCP_BUFFER_SIZE => {
+                            return Err(Error::custom(format!(
+                                "Buffer of {} records to {} overflowed",
+                                TCP_BUFFER_SIZE, addr
+                            )));
+                        }
+                        Ok(n) => buffer.push(buf[..n].to_vec()),
+                        Err(e) => tracing::info!("Failed to encode: {}", e),
+                    },
+                    Ok(Some(Event::Snapshot(i))) => snapshots.push(i),
+                    Ok(Some(_)) => {}
+                    Ok(None) => closed = true,
+                    Err(_) => {}
+                }
+                if buffer.is_empty() {
+                    snapshots.drain(..).for_each(|i| state.ack(i));
+                }
+                // Batch records which are already waiting into one flush.
+                if !closed && !rx.is_empty() && buffer.len() < TCP_BUFFER_SIZE {
+                    continue;
+                }
+            }
+            if socket.is_none() {
+                if Instant::now() < retry {
+                    if closed {
+                        tokio::time::sleep_until(retry).await;
+                    }
+                    continue;
+                }
+                match TcpStream::connect(addr).await {
+                    Ok(s) => {
+                        tracing::info!("Connected to {}", addr);
+                        socket = Some(BufWriter::new(s));
+                        backoff = TCP_BACKOFF;
+                    }
+                    Err(e) if closed && backoff == TCP_MAX_BACKOFF => {
+                        let msg = format!(
+                            "Failed to connect to {} with {} records left: {}",
+                            addr,
+                            buffer.len(),
+                            e
+                        );
+                        return Err(std::io::Error::new(e.kind(), msg).into());
+                    }
+                    Err(e) => {
+                        tracing::info!(
+                            "Failed to connect to {}, retrying in {:?}: {}",
+                            addr,
+                            backoff,
+                            e
+                        );
+                        retry = Instant::now() + backoff;
+                        backoff = (backoff * 2).min(TCP_MAX_BACKOFF);
+                        continue;
+                    }
+                }
+            }
+            if let Some(writer) = &mut socket {
+                match Self::flush_socket(writer, &buffer).await {
+                    Ok(()) => {
+                        buffer.clear();
+                        snapshots.drain(..).for_each(|i| state.ack(i));
+                    }
+                    Err(e) => {
+                        tracing::info!("Lost connection to {}: {}", addr, e);
+                        socket = None;
+                    }
                 }
-                Err(e) => println!("Failed to encode: {}", e),
             }
         }
+        Ok(())
     }
 
-    fn sink_encoding(
-        ctx: &mut Context,
-        rx: std::sync::mpsc::Receiver<T>,
-        writer: Writer,
-        encoding: Format,
-    ) {
-        match encoding {
-            Format::Csv { sep } => {
-                let encoder = crate::formats::csv::ser::Writer::new(sep);
-                Self::sink_writer(ctx, rx, writer, encoder);
-            }
-            Format::Json => {
-                let encoder = crate::formats::json::ser::Writer::new();
-                Self::sink_writer(ctx, rx, writer, encoder);
-            }
+    async fn flush_socket(
+        writer: &mut BufWriter<TcpStream>,
+        records: &[Vec<u8>],
+    ) -> std::io::Result<()> {
+        for record in records {
+            writer.write_all(record).await?;
         }
+        writer.flush().await
     }
 
     fn sink_writer(
         ctx: &mut Context,
-        rx: std::sync::mpsc::Receiver<T>,
+        rx: UnboundedReceiver<Event<T>>,
+        state: OperatorState,
         writer: Writer,
         encoder: impl Encode + Send + 'static,
     ) {
         ctx.spawn(async move {
             match writer {
-                Writer::Stdout => Self::write_pipe(rx, encoder, std::io::stdout()).await,
-                Writer::File { path } => Self::write_file(rx, path, encoder).await,
-                // Writer::Http { url } => Self::write_http(rx, url, encoder).await,
-                Writer::Tcp { addr } => Self::write_socket(rx, addr, encoder).await,
-                Writer::Kafka { addr: _, topic: _ } => todo!(),
+                Writer::Stdout => Self::write_pipe(rx, state, encoder, Output::stdout(), 0).await,
+                Writer::File { path } => Self::write_file(rx, state, path, encoder).await,
+                Writer::Http { .. } => unreachable!(),
+                Writer::Tcp { addr } => Self::write_socket(rx, state, addr, encoder).await,
+                #[cfg(feature = "kafka")]
+                Writer::Kafka { .. } => unreachable!(),
+                #[cfg(not(feature = "kafka"))]
+                Writer::Kafka { .. } => Err(Error::custom(
+                    "Writing to Kafka requires the `kafka` feature",
+                )),
+            }
+        });
+    }
+
+    /// Produces one record per event, timestamped with its event time. A
+    /// snapshot is acknowledged once every record before it has been delivered.
+    /// Newline-delimited and JSON records are produced without their newline.
+    /// If a record cannot be delivered, the sink fails.
+    #[cfg(feature = "kafka")]
+    fn write_kafka(
+        self,
+        ctx: &mut Context,
+        addr: SocketAddr,
+        topic: String,
+        framing: Framing,
+        mut encoder: impl Encode + Send + 'static,
+    ) {
+        let mut this = self;
+        let state = ctx.sink_state();
+        ctx.sink(|| async move {
+            let producer: FutureProducer = ClientConfig::new()
+                .set("bootstrap.servers", addr.to_string())
+                .create()
+                .map_err(Error::custom)?;
+            let mut pending = VecDeque::new();
+            let mut buf = vec![0; 1024];
+            loop {
+                match this.recv().await {
+                    Event::Data(time, data) => match encoder.encode(&data, &mut buf) {
+                        Ok(n) => {
+                            let payload = match framing {
+                                Framing::Newline | Framing::Json => {
+                                    buf[..n].strip_suffix(b"\n").unwrap_or(&buf[..n])
+                                }
+                                Framing::LengthPrefixed => &buf[..n],
+                            };
+                            let mut record = FutureRecord::<(), [u8]>::to(&topic)
+                                .payload(payload)
+                                .timestamp(time.milliseconds() as i64);
+                            loop {
+                                match producer.send_result(record) {
+                                    Ok(delivery) => break pending.push_back(delivery),
+                                    Err((
+                                        KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull),
+                                        r,
+                                    )) => {
+                                        record = r;
+                                        tokio::time::sleep(std::time::Duration::from_millis(10))
+                                            .await;
+                                    }
+                                    Err((e, _)) => return Err(Error::custom(e)),
+                                }
+                            }
+                            if pending.len() > KAFKA_MAX_PENDING {
+                                await_delivery(pending.pop_front().unwrap()).await?;
+                            }
+                        }
+                        Err(e) => tracing::info!("Failed to encode: {}", e),
+                    },
+                    Event::Watermark(_) => continue,
+                    Event::Snapshot(i) => {
+                        for delivery in pending.drain(..) {
+                            await_delivery(delivery).await?;
+                        }
+                        state.ack(i);
+                    }
+                    Event::Sentinel => {
+                        for delivery in pending.drain(..) {
+                            await_delivery(delivery).await?;
+                        }
+                        break;
+                    }
+                }
             }
             Ok(())
         });
     }
+
+    /// POSTs batches of encoded records to `url`. A batch is sent once it
+    /// exceeds `HTTP_BATCH_SIZE` bytes, after `HTTP_BATCH_INTERVAL`, and before a
+    /// snapshot is acknowledged. If a batch cannot be delivered, the sink
+    /// fails with an `HttpError`.
+    fn write_http(self, ctx: &mut Context, url: Url, mut encoder: impl Encode + Send + 'static) {
+        let mut this = self;
+        let state = ctx.sink_state();
+        ctx.sink(|| async move {
+            let client = Client::builder(TokioExecutor::new()).build_http();
+            let content_type = encoder.content_type();
+            let mut batch = Vec::new();
+            let mut buf = vec![0; 1024];
+            let mut interval = tokio::time::interval(HTTP_BATCH_INTERVAL);
+            loop {
+                let flush = tokio::select! {
+                    _ = interval.tick() => !batch.is_empty(),
+                    event = this.recv() => match event {
+                        Event::Data(_, data) => match encoder.encode(&data, &mut buf) {
+                            Ok(n) => {
+                                batch.extend_from_slice(&buf[..n]);
+                                batch.len() >= HTTP_BATCH_SIZE
+                            }
+                            Err(e) => {
+                                tracing::info!("Failed to encode: {}", e);
+                                false
+                            }
+                        },
+                        Event::Watermark(_) => false,
+                        Event::Snapshot(i) => {
+                            let batch = std::mem::take(&mut batch);
+                            post_http(&client, &url, content_type, batch)
+                                .await
+                                .map_err(Error::custom)?;
+                            state.ack(i);
+                            false
+                        }
+                        Event::Sentinel => {
+                            let batch = std::mem::take(&mut batch);
+                            post_http(&client, &url, content_type, batch)
+                                .await
+                                .map_err(Error::custom)?;
+                            break;
+                        }
+                    }
+                };
+                if flush {
+                    interval.reset();
+                    let batch = std::mem::take(&mut batch);
+                    post_http(&client, &url, content_type, batch)
+                        .await
+                        .map_err(Error::custom)?;
+                }
+            }
+            Ok(())
+        });
+    }
+}
+
+const TCP_BUFFER_SIZE: usize = 100_000;
+const TCP_BACKOFF: std::time::Duration = std::time::Duration::from_millis(100);
+const TCP_MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[cfg(feature = "kafka")]
+const KAFKA_MAX_PENDING: usize = 100_000;
+
+const HTTP_BATCH_SIZE: usize = 64 * 1024;
+const HTTP_BATCH_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+const HTTP_RETRIES: usize = 5;
+const HTTP_BACKOFF: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Error of an HTTP sink which failed to deliver a batch.
+#[derive(Debug)]
+pub enum HttpError {
+    /// The URL or headers do not form a valid request.
+    Request(hyper::http::Error),
+    /// The request could not be sent, even after retrying.
+    Connection(hyper_util::client::legacy::Error),
+    /// The server rejected the batch.
+    Status(StatusCode),
+}
+
+impl std::fmt::Display for HttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HttpError::Request(e) => write!(f, "Invalid request: {e}"),
+            HttpError::Connection(e) => write!(f, "Failed to send request: {e}"),
+            HttpError::Status(status) => write!(f, "Server responded with {status}"),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+/// Sends a batch, retrying with exponential backoff on connection errors and
+/// 5xx responses.
+async fn post_http(
+    client: &Client<HttpConnector, Full<Bytes>>,
+    url: &Url,
+    content_type: &'static str,
+    batch: Vec<u8>,
+) -> Result<(), HttpError> {
+    if batch.is_empty() {
+        return Ok(());
+    }
+    let batch = Bytes::from(batch);
+    let mut backoff = HTTP_BACKOFF;
+    let mut attempt = 0;
+    loop {
+        let request = Request::post(url.as_str())
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Full::new(batch.clone()))
+            .map_err(HttpError::Request)?;
+        let error = match client.request(request).await {
+            Ok(response) if response.status().is_success() => {
+                // Drain the body so that the connection can be reused.
+                response.into_body().collect().await.ok();
+                return Ok(());
+            }
+            Ok(response) if response.status().is_server_error() => {
+                HttpError::Status(response.status())
+            }
+            Ok(response) => return Err(HttpError::Status(response.status())),
+            Err(e) => HttpError::Connection(e),
+        };
+        attempt += 1;
+        if attempt > HTTP_RETRIES {
+            return Err(error);
+        }
+        tracing::info!("Retrying in {:?}: {}", backoff, error);
+        tokio::time::sleep(backoff).await;
+        backoff *= 2;
+    }
+}
+
+/// Waits until a record has been acknowledged by the broker.
+#[cfg(feature = "kafka")]
+async fn await_delivery(delivery: DeliveryFuture) -> Result<(), Error> {
+    match delivery.await {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err((e, _))) => Err(Error::custom(e)),
+        Err(_) => Err(Error::custom("Kafka producer was dropped")),
+    }
 }
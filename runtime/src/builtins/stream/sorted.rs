@@ -24,20 +24,22 @@ impl<T> Eq for HeapEntry<T> {}
 
 impl<T> PartialOrd for HeapEntry<T> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(other.cmp(self)) // Reverse order for min-heap
+        Some(self.cmp(other))
     }
 }
 
 impl<T> Ord for HeapEntry<T> {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.time.cmp(&other.time).then(self.seq.cmp(&other.seq))
+        // Reverse order for min-heap
+        other.time.cmp(&self.time).then(other.seq.cmp(&self.seq))
     }
 }
 
 impl<T: Data> Stream<T> {
     pub fn sorted(mut self, ctx: &mut Context) -> Self {
+        let state = ctx.operator_state();
         ctx.operator(|tx| async move {
-            let mut buffer: Vec<Event<T>> = Vec::new();
+            let mut buffer: Vec<Event<T>> = state.restore().unwrap_or_default();
 
             loop {
                 match self.recv().await {
@@ -64,7 +66,10 @@ impl<T: Data> Stream<T> {
 
                         tx.send(Event::Watermark(w)).await?;
                     },
-                    Event::Snapshot(id) => tx.send(Event::Snapshot(id)).await?,
+                    Event::Snapshot(id) => {
+                        state.snapshot(id, &buffer);
+                        tx.send(Event::Snapshot(id)).await?;
+                    }
                     Event::Sentinel => {
                         buffer.sort_by_key(|e| match e {
                             Event::Data(t, _) => *t,
@@ -85,9 +90,16 @@ impl<T: Data> Stream<T> {
     }
 
     pub fn sorted_heap(mut self, ctx: &mut Context) -> Self {
+        let state = ctx.operator_state();
         ctx.operator(|tx| async move {
             let mut heap: BinaryHeap<HeapEntry<T>> = BinaryHeap::new();
             let mut seq = 0;
+            for event in state.restore::<Vec<Event<T>>>().unwrap_or_default() {
+                if let Event::Data(time, _) = event {
+                    heap.push(HeapEntry { time, seq, event });
+                    seq += 1;
+                }
+            }
     
             loop {
                 match self.recv().await {
@@ -114,6 +126,8 @@ impl<T: Data> Stream<T> {
                         tx.send(Event::Watermark(w)).await?;
                     },
                     Event::Snapshot(id) => {
+                        let events: Vec<&Event<T>> = heap.iter().map(|entry| &entry.event).collect();
+                        state.snapshot(id, &events);
                         tx.send(Event::Snapshot(id)).await?;
                     },
                     Event::Sentinel => {
@@ -1,18 +1,69 @@
-use std::io::{BufRead, BufReader, Read};
-use std::net::{SocketAddr, TcpListener};
+use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use futures_util::StreamExt;
+use http_body_util::BodyExt;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::body::Incoming;
+use hyper::header;
+use hyper::header::HeaderValue;
+use hyper::service::service_fn;
+use hyper::Method;
+use hyper::Request;
+use hyper::Response;
+use hyper::StatusCode;
+use hyper_util::rt::TokioExecutor;
+use hyper_util::rt::TokioIo;
+use hyper_util::server::conn::auto;
+use tokio_util::bytes::BytesMut;
+use tokio_util::codec::Decoder;
+use tokio_util::codec::FramedRead;
 
 use crate::formats::Decode;
+use crate::formats::Framing;
+use crate::io::Input;
 use crate::runner::context::Context;
+use crate::state::OperatorState;
 
-use time::OffsetDateTime;
+#[cfg(feature = "kafka")]
+use rdkafka::client::NativeClient;
+#[cfg(feature = "kafka")]
+use rdkafka::consumer::Consumer;
+#[cfg(feature = "kafka")]
+use rdkafka::consumer::ConsumerContext;
+#[cfg(feature = "kafka")]
+use rdkafka::consumer::DefaultConsumerContext;
+#[cfg(feature = "kafka")]
+use rdkafka::consumer::StreamConsumer;
+#[cfg(feature = "kafka")]
+use rdkafka::types::RDKafkaRespErr;
+#[cfg(feature = "kafka")]
+use rdkafka::ClientConfig;
+#[cfg(feature = "kafka")]
+use rdkafka::ClientContext;
+#[cfg(feature = "kafka")]
+use rdkafka::Message;
+#[cfg(feature = "kafka")]
+use rdkafka::Offset;
+#[cfg(feature = "kafka")]
+use rdkafka::TopicPartitionList;
 
 use crate::builtins::duration::Duration;
 use crate::builtins::format::Format;
+use crate::builtins::lateness::Lateness;
 use crate::builtins::reader::Reader;
 use crate::builtins::stream::Event;
 use crate::builtins::time::Time;
+use crate::builtins::watermark::WatermarkState;
+use crate::builtins::watermark::WatermarkStrategy;
+use crate::builtins::watermark::Watermarks;
+use crate::error::Error;
 use crate::traits::Data;
+#[cfg(feature = "kafka")]
+use crate::HashMap;
 
 use super::Stream;
 
@@ -22,19 +73,46 @@ impl<T: Data> Stream<T> {
         reader: Reader,
         encoding: Format,
         extractor: impl FnMut(T, Time) -> Time + Send + 'static,
-        slack: Duration,
+        watermarks: impl Into<WatermarkStrategy<T>>,
         watermark_interval: Duration,
     ) -> Stream<T> {
+        Self::source_with_lateness(
+            ctx,
+            reader,
+            encoding,
+            extractor,
+            watermarks,
+            watermark_interval,
+            Lateness::Drop,
+        )
+        .0
+    }
+
+    /// Like `source`, but events behind the watermark are handled by the
+    /// lateness policy. The second stream is the late output.
+    pub fn source_with_lateness(
+        ctx: &mut Context,
+        reader: Reader,
+        encoding: Format,
+        extractor: impl FnMut(T, Time) -> Time + Send + 'static,
+        watermarks: impl Into<WatermarkStrategy<T>>,
+        watermark_interval: Duration,
+        lateness: Lateness,
+    ) -> (Stream<T>, Stream<T>) {
+        let watermarks = watermarks.into();
+        let framing = encoding.framing();
         match encoding {
             Format::Csv { sep } => {
                 let mut decoder = crate::formats::csv::de::Reader::<1024>::new(sep);
                 Self::_source1(
                     ctx,
                     reader,
+                    framing,
                     move |s| decoder.decode(s),
                     extractor,
-                    slack,
+                    watermarks,
                     watermark_interval,
+                    lateness,
                 )
             }
             Format::Json => {
@@ -42,10 +120,51 @@ impl<T: Data> Stream<T> {
                 Self::_source1(
                     ctx,
                     reader,
+                    framing,
+                    move |s| decoder.decode(s),
+                    extractor,
+                    watermarks,
+                    watermark_interval,
+                    lateness,
+                )
+            }
+            Format::MessagePack => {
+                let mut decoder = crate::formats::msgpack::de::Reader::new();
+                Self::_source1(
+                    ctx,
+                    reader,
+                    framing,
+                    move |s| decoder.decode(s),
+                    extractor,
+                    watermarks,
+                    watermark_interval,
+                    lateness,
+                )
+            }
+            Format::Cbor => {
+                let mut decoder = crate::formats::cbor::de::Reader::new();
+                Self::_source1(
+                    ctx,
+                    reader,
+                    framing,
+                    move |s| decoder.decode(s),
+                    extractor,
+                    watermarks,
+                    watermark_interval,
+                    lateness,
+                )
+            }
+            Format::Bincode => {
+                let mut decoder = crate::formats::bincode::de::Reader::new();
+                Self::_source1(
+                    ctx,
+                    reader,
+                    framing,
                     move |s| decoder.decode(s),
                     extractor,
-                    slack,
+                    watermarks,
                     watermark_interval,
+                    lateness,
                 )
             }
         }
@@ -56,23 +175,56 @@ impl<T: Data> Stream<T> {
         reader: Reader,
         encoding: Format,
         extractor: impl FnMut(T, Time) -> Time + Send + 'static,
-        slack: Duration,
+        watermarks: impl Into<WatermarkStrategy<T>>,
         watermark_interval: Duration,
         type_tag: Seed,
     ) -> Stream<T>
     where
         Seed: Clone + Send + Sync + for<'a> serde::de::DeserializeSeed<'a, Value = T> + 'static,
     {
+        Self::dyn_source_with_lateness(
+            ctx,
+            reader,
+            encoding,
+            extractor,
+            watermarks,
+            watermark_interval,
+            type_tag,
+            Lateness::Drop,
+        )
+        .0
+    }
+
+    /// Like `dyn_source`, but events behind the watermark are handled by the
+    /// lateness policy. The second stream is the late output.
+    #[allow(clippy::too_many_arguments)]
+    pub fn dyn_source_with_lateness<Seed>(
+        ctx: &mut Context,
+        reader: Reader,
+        encoding: Format,
+        extractor: impl FnMut(T, Time) -> Time + Send + 'static,
+        watermarks: impl Into<WatermarkStrategy<T>>,
+        watermark_interval: Duration,
+        type_tag: Seed,
+        lateness: Lateness,
+    ) -> (Stream<T>, Stream<T>)
+    where
+        Seed: Clone + Send + Sync + for<'a> serde::de::DeserializeSeed<'a, Value = T> + 'static,
+    {
+        let watermarks = watermarks.into();
+        let framing = encoding.framing();
         match encoding {
             Format::Csv { sep } => {
                 let mut decoder = crate::formats::csv::de::Reader::<1024>::new(sep);
                 Self::_source1(
                     ctx,
                     reader,
+                    framing,
                     move |s| decoder.decode_dyn(s, type_tag.clone()),
                     extractor,
-                    slack,
+                    watermarks,
                     watermark_interval,
+                    lateness,
                 )
             }
             Format::Json => {
@@ -80,155 +232,555 @@ impl<T: Data> Stream<T> {
                 Self::_source1(
                     ctx,
                     reader,
+                    framing,
                     move |s| decoder.decode_dyn(s, type_tag.clone()),
                     extractor,
-                    slack,
+                    watermarks,
                     watermark_interval,
+                    lateness,
+                )
+            }
+            Format::MessagePack => {
+                let mut decoder = crate::formats::msgpack::de::Reader::new();
+                Self::_source1(
+                    ctx,
+                    reader,
+                    framing,
+                    move |s| decoder.decode_dyn(s, type_tag.clone()),
+                    extractor,
+                    watermarks,
+                    watermark_interval,
+                    lateness,
+                )
+            }
+            Format::Cbor => {
+                let mut decoder = crate::formats::cbor::de::Reader::new();
+                Self::_source1(
+                    ctx,
+                    reader,
+                    framing,
+                    move |s| decoder.decode_dyn(s, type_tag.clone()),
+                    extractor,
+                    watermarks,
+                    watermark_interval,
+                    lateness,
+                )
+            }
+            Format::Bincode => {
+                let mut decoder = crate::formats::bincode::de::Reader::new();
+                Self::_source1(
+                    ctx,
+                    reader,
+                    framing,
+                    move |s| decoder.decode_dyn(s, type_tag.clone()),
+                    extractor,
+                    watermarks,
+                    watermark_interval,
+                    lateness,
                 )
             }
         }
     }
 
+    /// Reads framed records from `rx`, which starts at byte `position` of the
+    /// input. Each record is sent with the position after its frame.
     async fn read_pipe<E: std::error::Error>(
-        rx: impl Read + Unpin,
+        mut rx: Input,
+        mut position: u64,
+        mut framing: Framing,
         mut decoder: impl for<'a> FnMut(&'a [u8]) -> Result<T, E> + Send + 'static,
         watch: bool,
-        tx: tokio::sync::mpsc::Sender<T>,
-    ) {
-        let mut rx = BufReader::with_capacity(1024 * 30, rx);
-        let mut buf = Vec::with_capacity(1024 * 30);
+        tx: tokio::sync::mpsc::Sender<(T, u64)>,
+    ) -> Result<(), Error> {
+        let mut buf = BytesMut::with_capacity(1024 * 30);
+        let mut chunk = vec![0; 1024 * 30];
         loop {
-            match rx.read_until(b'\n', &mut buf) {
-                Ok(0) => {
-                    tracing::info!("EOF");
-                    println!("EOF");
-                    if watch {
-                        println!("Waiting for more data...");
+            let frame = match framing.decode(&mut buf) {
+                Ok(Some(frame)) => frame,
+                Ok(None) => match rx.read(&mut chunk).await {
+                    Ok(0) if watch => {
+                        tracing::debug!("EOF, waiting for more data");
                         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-                    } else {
-                        break;
+                        continue;
                     }
-                }
-                Ok(n) => match decoder(&buf[0..n]) {
-                    Ok(data) => {
-                        tracing::info!("Decoded: {:?}", data);
-                        if tx.send(data).await.is_err() {
+                    Ok(0) => match framing.decode_eof(&mut buf) {
+                        Ok(Some(frame)) => frame,
+                        Ok(None) => {
+                            tracing::info!("EOF");
                             break;
                         }
-                        buf.clear();
+                        Err(e) => return Err(e.into()),
+                    },
+                    Ok(n) => {
+                        buf.extend_from_slice(&chunk[..n]);
+                        position += n as u64;
+                        continue;
                     }
-                    Err(e) => tracing::info!("Failed to decode: {}", e),
+                    Err(e) => return Err(e.into()),
                 },
-                Err(e) => panic!("Failed to read from stdin: {}", e),
+                Err(e) => return Err(e.into()),
+            };
+            let end = position - buf.len() as u64;
+            match decoder(&frame) {
+                Ok(data) => {
+                    tracing::info!("Decoded: {:?}", data);
+                    if tx.send((data, end)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(e) => tracing::info!("Failed to decode: {}", e),
             }
         }
+        Ok(())
     }
 
     async fn read_file<E: std::error::Error>(
         path: PathBuf,
+        position: u64,
+        framing: Framing,
         decoder: impl for<'a> FnMut(&'a [u8]) -> Result<T, E> + Send + 'static,
         watch: bool,
-        tx2: tokio::sync::mpsc::Sender<T>,
-    ) {
-        match std::fs::File::open(&path) {
-            Ok(rx) => Self::read_pipe(rx, decoder, watch, tx2).await,
-            Err(e) => panic!("Failed to open file `{}`: {}", path.display(), e),
+        tx2: tokio::sync::mpsc::Sender<(T, u64)>,
+    ) -> Result<(), Error> {
+        match Input::open_at(&path, position).await {
+            Ok(rx) => Self::read_pipe(rx, position, framing, decoder, watch, tx2).await,
+            Err(e) => {
+                let msg = format!("Failed to open file `{}`: {}", path.display(), e);
+                Err(std::io::Error::new(e.kind(), msg).into())
+            }
         }
     }
 
-    async fn read_socket<E: std::error::Error>(
+    /// Accepts any number of producers and merges their framed records.
+    /// Producers may connect and disconnect at any time. Unless `watch` is
+    /// set, the source ends once every producer which has connected so far
+    /// has disconnected.
+    async fn read_socket<E: std::error::Error + 'static>(
         addr: SocketAddr,
-        mut decoder: impl for<'a> FnMut(&'a [u8]) -> Result<T, E> + Send + 'static,
-        tx: tokio::sync::mpsc::Sender<T>,
-    ) {
+        framing: Framing,
+        decoder: impl for<'a> FnMut(&'a [u8]) -> Result<T, E> + Send + 'static,
+        watch: bool,
+        tx: tokio::sync::mpsc::Sender<(T, u64)>,
+    ) -> Result<(), Error> {
         tracing::info!("Trying to listen on {}", addr);
-        let listener = TcpListener::bind(addr).expect("Failed to bind");
+        let listener = tokio::net::TcpListener::bind(addr).await?;
         tracing::info!("Listening on {}", addr);
-        let (stream, _) = listener.accept().expect("Failed to accept");
-        tracing::info!("Accepted connection from {}", addr);
-        let reader = BufReader::new(stream);
-        for line in reader.lines() {
-            match line {
-                Ok(l) => match decoder(l.as_bytes()) {
-                    Ok(data) => {
-                        println!("Decoded: {:?}", data);
-                        if tx.send(data).await.is_err() {
+        let decoder = Arc::new(Mutex::new(decoder));
+        let mut producers = tokio::task::JoinSet::new();
+        loop {
+            let (stream, peer) = tokio::select! {
+                stream = listener.accept() => match stream {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        tracing::info!("Failed to accept: {}", e);
+                        continue;
+                    }
+                },
+                Some(_) = producers.join_next() => {
+                    if !watch && producers.is_empty() {
+                        break;
+                    }
+                    continue;
+                }
+                _ = tx.closed() => break,
+            };
+            tracing::info!("Accepted connection from {}", peer);
+            let decoder = decoder.clone();
+            let tx = tx.clone();
+            producers.spawn(async move {
+                let mut frames = FramedRead::new(stream, framing);
+                while let Some(frame) = frames.next().await {
+                    let frame = match frame {
+                        Ok(frame) => frame,
+                        Err(e) => {
+                            tracing::info!("Failed to read from {}: {}", peer, e);
                             break;
                         }
+                    };
+                    let data = match decoder.lock().unwrap()(&frame) {
+                        Ok(data) => data,
+                        Err(e) => {
+                            tracing::info!("Failed to decode: {}", e);
+                            continue;
+                        }
+                    };
+                    // Sockets cannot be replayed, so records have no position.
+                    if tx.send((data, 0)).await.is_err() {
+                        break;
+                    }
+                }
+                tracing::info!("Connection from {} closed", peer);
+            });
+        }
+        Ok(())
+    }
+
+    /// Serves POST requests whose body holds one or more framed records. A
+    /// request is rejected with 429 if the source has no room for its records
+    /// within `HTTP_TIMEOUT`, and with 503 once the dataflow has shut down.
+    /// Records are forwarded in chunks of at most the capacity of the channel,
+    /// so once the first chunk has been accepted, the rest wait for room.
+    /// Connections are served by the source, and closed when it ends.
+    async fn read_http<E: std::error::Error + 'static>(
+        addr: SocketAddr,
+        framing: Framing,
+        decoder: impl for<'a> FnMut(&'a [u8]) -> Result<T, E> + Send + 'static,
+        tx: tokio::sync::mpsc::Sender<(T, u64)>,
+    ) -> Result<(), Error> {
+        tracing::info!("Trying to listen on {}", addr);
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        tracing::info!("Listening on {}", addr);
+        let decoder = Arc::new(Mutex::new(decoder));
+        let mut connections = tokio::task::JoinSet::new();
+        loop {
+            let stream = tokio::select! {
+                stream = listener.accept() => match stream {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        tracing::info!("Failed to accept: {}", e);
+                        continue;
                     }
-                    Err(e) => println!("Failed to decode: {}", e),
                 },
-                Err(e) => {
-                    println!("Failed to read line: {}", e);
-                    break;
+                Some(result) = connections.join_next() => {
+                    // A panic while serving a request fails the source.
+                    result?;
+                    continue;
                 }
-            }
+                _ = tx.closed() => break,
+            };
+            let decoder = decoder.clone();
+            let tx = tx.clone();
+            let service = service_fn(move |request| {
+                Self::handle_http(request, framing, decoder.clone(), tx.clone())
+            });
+            connections.spawn(async move {
+                if let Err(e) = auto::Builder::new(TokioExecutor::new())
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    tracing::info!("Failed to serve connection: {}", e);
+                }
+            });
         }
+        connections.shutdown().await;
+        Ok(())
     }
 
-    async fn read_http<E: std::error::Error>(
-        _addr: SocketAddr,
-        _decoder: impl for<'a> FnMut(&'a [u8]) -> Result<T, E> + Send + 'static,
-        _tx: tokio::sync::mpsc::Sender<T>,
-    ) {
-        todo!()
+    async fn handle_http<E: std::error::Error>(
+        request: Request<Incoming>,
+        mut framing: Framing,
+        decoder: Arc<Mutex<impl for<'a> FnMut(&'a [u8]) -> Result<T, E>>>,
+        tx: tokio::sync::mpsc::Sender<(T, u64)>,
+    ) -> Result<Response<Full<Bytes>>, hyper::Error> {
+        let response = |status: StatusCode, body: String| {
+            let mut response = Response::new(Full::new(Bytes::from(body)));
+            *response.status_mut() = status;
+            Ok(response)
+        };
+        if request.method() != Method::POST {
+            return response(StatusCode::METHOD_NOT_ALLOWED, String::new());
+        }
+        let mut body = BytesMut::from(&request.into_body().collect().await?.to_bytes()[..]);
+        let records = {
+            let mut decoder = decoder.lock().unwrap();
+            let mut records = Vec::new();
+            loop {
+                let frame = match framing.decode_eof(&mut body) {
+                    Ok(Some(frame)) => frame,
+                    Ok(None) => break,
+                    Err(e) => return response(StatusCode::BAD_REQUEST, e.to_string()),
+                };
+                if framing == Framing::Newline && frame.trim_ascii().is_empty() {
+                    continue;
+                }
+                match decoder(&frame) {
+                    Ok(data) => records.push(data),
+                    Err(e) => return response(StatusCode::BAD_REQUEST, e.to_string()),
+                }
+            }
+            records
+        };
+        let mut records = records.into_iter().peekable();
+        let mut accepted = false;
+        while records.peek().is_some() {
+            let n = records.len().min(tx.max_capacity());
+            let permits = if accepted {
+                tx.reserve_many(n).await
+            } else {
+                match tokio::time::timeout(HTTP_TIMEOUT, tx.reserve_many(n)).await {
+                    Ok(permits) => permits,
+                    Err(_) => {
+                        let mut response = response(StatusCode::TOO_MANY_REQUESTS, String::new())?;
+                        response
+                            .headers_mut()
+                            .insert(header::RETRY_AFTER, HeaderValue::from_static("1"));
+                        return Ok(response);
+                    }
+                }
+            };
+            let Ok(permits) = permits else {
+                return response(StatusCode::SERVICE_UNAVAILABLE, String::new());
+            };
+            for (permit, data) in permits.zip(records.by_ref()) {
+                permit.send((data, 0));
+            }
+            accepted = true;
+        }
+        response(StatusCode::OK, String::new())
     }
 
-    fn _source1<E: std::error::Error + Send>(
+    #[allow(clippy::too_many_arguments)]
+    fn _source1<E: std::error::Error + Send + 'static>(
         ctx: &mut Context,
         reader: Reader,
+        framing: Framing,
         decoder: impl for<'a> FnMut(&'a [u8]) -> Result<T, E> + Send + 'static,
         extractor: impl FnMut(T, Time) -> Time + Send + 'static,
-        slack: Duration,
+        watermarks: WatermarkStrategy<T>,
         watermark_interval: Duration,
-    ) -> Stream<T> {
+        lateness: Lateness,
+    ) -> (Stream<T>, Stream<T>) {
         let (tx2, rx2) = tokio::sync::mpsc::channel(10);
+        let reader = match reader {
+            #[cfg(feature = "kafka")]
+            Reader::Kafka { addr, topic } => {
+                return Self::read_kafka(
+                    ctx,
+                    addr,
+                    topic,
+                    framing,
+                    decoder,
+                    extractor,
+                    watermarks,
+                    watermark_interval,
+                    lateness,
+                )
+            }
+            reader => reader,
+        };
+        let state = ctx.operator_state();
+        // Files resume from the position of the last record before the
+        // snapshot. Other readers cannot replay their input.
+        let restored: Option<(u64, WatermarkState)> = state.restore();
+        let position = restored.as_ref().map_or(0, |(position, _)| *position);
         ctx.spawn(async move {
             match reader {
-                Reader::Stdin => Self::read_pipe(std::io::stdin(), decoder, false, tx2).await,
-                Reader::File { path, watch } => Self::read_file(path, decoder, watch, tx2).await,
-                Reader::Http { addr } => Self::read_http(addr, decoder, tx2).await,
-                Reader::Tcp { addr } => Self::read_socket(addr, decoder, tx2).await,
-                Reader::Kafka { addr: _, topic: _ } => todo!(),
+                Reader::Stdin => {
+                    Self::read_pipe(Input::stdin(), 0, framing, decoder, false, tx2).await
+                }
+                Reader::File { path, watch } => {
+                    Self::read_file(path, position, framing, decoder, watch, tx2).await
+                }
+                Reader::Http { addr } => Self::read_http(addr, framing, decoder, tx2).await,
+                Reader::Tcp { addr, watch } => {
+                    Self::read_socket(addr, framing, decoder, watch, tx2).await
+                }
+                #[cfg(feature = "kafka")]
+                Reader::Kafka { .. } => unreachable!(),
+                #[cfg(not(feature = "kafka"))]
+                Reader::Kafka { .. } => Err(Error::custom(
+                    "Reading from Kafka requires the `kafka` feature",
+                )),
             }
-            Ok(())
         });
-        Self::_source4(ctx, rx2, extractor, watermark_interval, slack)
+        Self::_source4(
+            ctx,
+            rx2,
+            state,
+            restored,
+            extractor,
+            watermark_interval,
+            watermarks,
+            lateness,
+        )
+    }
+
+    /// Consumes a Kafka topic. Partitions are balanced across all consumers of
+    /// the topic, and the extractor receives the timestamp of each record.
+    /// Snapshots store the next offset of every partition which has been read,
+    /// and partitions assigned to a restored source resume from there.
+    #[cfg(feature = "kafka")]
+    #[allow(clippy::too_many_arguments)]
+    fn read_kafka<E: std::error::Error + Send>(
+        ctx: &mut Context,
+        addr: SocketAddr,
+        topic: String,
+        mut framing: Framing,
+        mut decoder: impl for<'a> FnMut(&'a [u8]) -> Result<T, E> + Send + 'static,
+        mut extractor: impl FnMut(T, Time) -> Time + Send + 'static,
+        watermarks: WatermarkStrategy<T>,
+        watermark_interval: Duration,
+        lateness: Lateness,
+    ) -> (Stream<T>, Stream<T>) {
+        let state = ctx.operator_state();
+        let allowed = lateness.allowed_lateness();
+        let mut watermarks = Watermarks::new(watermarks);
+        ctx.co_operator(move |tx, late| async move {
+            let offsets: HashMap<i32, i64> = match state.restore() {
+                Some((offsets, s)) => {
+                    watermarks.restore(s);
+                    offsets
+                }
+                None => HashMap::default(),
+            };
+            let offsets = Arc::new(Mutex::new(offsets));
+            let context = KafkaContext {
+                topic: topic.clone(),
+                offsets: offsets.clone(),
+            };
+            let consumer: StreamConsumer<KafkaContext> = ClientConfig::new()
+                .set("bootstrap.servers", addr.to_string())
+                .set("group.id", format!("runtime-{topic}"))
+                .set("enable.auto.commit", "false")
+                .set("auto.offset.reset", "earliest")
+                .create_with_context(context)
+                .map_err(Error::custom)?;
+            consumer.subscribe(&[&topic]).map_err(Error::custom)?;
+            let mut watermark_interval = tokio::time::interval(watermark_interval.to_std());
+            let checkpointing = state.interval().is_some();
+            let period = state.interval().unwrap_or(watermark_interval.period());
+            let start = tokio::time::Instant::now() + period;
+            let mut snapshot_interval = tokio::time::interval_at(start, period);
+            let mut snapshot = state.restored();
+            let mut buf = BytesMut::new();
+            loop {
+                tokio::select! {
+                    _ = snapshot_interval.tick(), if checkpointing => {
+                        snapshot += 1;
+                        let offsets = offsets.lock().unwrap().clone();
+                        state.snapshot(snapshot, &(offsets, watermarks.state()));
+                        tx.send(Event::Snapshot(snapshot)).await?;
+                        late.send(Event::Snapshot(snapshot)).await.ok();
+                    },
+                    _ = watermark_interval.tick() => {
+                        if let Some(watermark) = watermarks.on_periodic() {
+                            tx.send(Event::Watermark(watermark)).await?;
+                            late.send(Event::Watermark(watermark)).await.ok();
+                        }
+                    },
+                    message = consumer.recv() => {
+                        let message = match message {
+                            Ok(message) => message,
+                            Err(e) => {
+                                tracing::info!("Failed to consume: {}", e);
+                                continue;
+                            }
+                        };
+                        offsets
+                            .lock()
+                            .unwrap()
+                            .insert(message.partition(), message.offset() + 1);
+                        let Some(payload) = message.payload() else {
+                            continue;
+                        };
+                        // Each message holds one record, which may lack its newline.
+                        buf.clear();
+                        buf.extend_from_slice(payload);
+                        let frame = match framing.decode_eof(&mut buf) {
+                            Ok(Some(frame)) => frame,
+                            Ok(None) => continue,
+                            Err(e) => {
+                                tracing::info!("Failed to read record: {}", e);
+                                continue;
+                            }
+                        };
+                        let data = match decoder(&frame) {
+                            Ok(data) => data,
+                            Err(e) => {
+                                tracing::info!("Failed to decode: {}", e);
+                                continue;
+                            }
+                        };
+                        let record_time = message
+                            .timestamp()
+                            .to_millis()
+                            .map(|millis| Time::from_milliseconds(millis as i128))
+                            .unwrap_or_else(Time::now);
+                        let time = extractor(data.clone(), record_time);
+                        if time < watermarks.watermark() - allowed {
+                            if lateness.routes_late() {
+                                late.send(Event::Data(time, data)).await.ok();
+                            }
+                            continue;
+                        }
+                        let watermark = watermarks.on_event(&data, time);
+                        tx.send(Event::Data(time, data)).await?;
+                        if let Some(watermark) = watermark {
+                            tx.send(Event::Watermark(watermark)).await?;
+                            late.send(Event::Watermark(watermark)).await.ok();
+                        }
+                    }
+                    // The topic is unbounded, so the source only ends once
+                    // its output is dropped.
+                    _ = tx.0.closed() => break,
+                }
+            }
+            late.send(Event::Sentinel).await.ok();
+            Ok(())
+        })
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn _source4(
         ctx: &mut Context,
-        mut rx: tokio::sync::mpsc::Receiver<T>,
+        mut rx: tokio::sync::mpsc::Receiver<(T, u64)>,
+        state: OperatorState,
+        restored: Option<(u64, WatermarkState)>,
         mut extractor: impl FnMut(T, Time) -> Time + Send + 'static,
         watermark_interval: Duration,
-        slack: Duration,
-    ) -> Stream<T> {
-        ctx.operator(move |tx| async move {
-            let mut latest_time = OffsetDateTime::UNIX_EPOCH;
-            let slack = slack.to_std();
+        watermarks: WatermarkStrategy<T>,
+        lateness: Lateness,
+    ) -> (Stream<T>, Stream<T>) {
+        let allowed = lateness.allowed_lateness();
+        let mut watermarks = Watermarks::new(watermarks);
+        ctx.co_operator(move |tx, late| async move {
+            let mut position = match restored {
+                Some((position, s)) => {
+                    watermarks.restore(s);
+                    position
+                }
+                None => 0,
+            };
             let mut watermark_interval = tokio::time::interval(watermark_interval.to_std());
-            let mut watermark = OffsetDateTime::UNIX_EPOCH;
+            let checkpointing = state.interval().is_some();
+            let period = state.interval().unwrap_or(watermark_interval.period());
+            let start = tokio::time::Instant::now() + period;
+            let mut snapshot_interval = tokio::time::interval_at(start, period);
+            let mut snapshot = state.restored();
             loop {
                 tokio::select! {
+                    _ = snapshot_interval.tick(), if checkpointing => {
+                        snapshot += 1;
+                        state.snapshot(snapshot, &(position, watermarks.state()));
+                        tx.send(Event::Snapshot(snapshot)).await?;
+                        late.send(Event::Snapshot(snapshot)).await.ok();
+                    },
                     _ = watermark_interval.tick() => {
-                        if latest_time > OffsetDateTime::UNIX_EPOCH {
-                            watermark = latest_time - slack;
-                            tx.send(Event::Watermark(Time(watermark))).await?;
+                        if let Some(watermark) = watermarks.on_periodic() {
+                            tx.send(Event::Watermark(watermark)).await?;
+                            late.send(Event::Watermark(watermark)).await.ok();
                         }
                     },
                     data = rx.recv() => {
                         match data {
-                            Some(data) => {
+                            Some((data, end)) => {
+                                position = end;
                                 let time = extractor(data.clone(), Time::now());
-                                if time.0 < watermark {
+                                if time < watermarks.watermark() - allowed {
+                                    if lateness.routes_late() {
+                                        late.send(Event::Data(time, data)).await.ok();
+                                    }
                                     continue;
                                 }
-                                if time.0 > latest_time {
-                                    latest_time = time.0;
-                                }
+                                let watermark = watermarks.on_event(&data, time);
                                 tx.send(Event::Data(time, data)).await?;
+                                if let Some(watermark) = watermark {
+                                    tx.send(Event::Watermark(watermark)).await?;
+                                    late.send(Event::Watermark(watermark)).await.ok();
+                                }
                             }
                             None => {
+                                late.send(Event::Sentinel).await.ok();
                                 tx.send(Event::Sentinel).await?;
                                 break;
                             },
@@ -240,3 +792,41 @@ impl<T: Data> Stream<T> {
         })
     }
 }
+
+/// How long an HTTP request waits for room in the source before it is
+/// rejected.
+const HTTP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Starts each partition assigned to a Kafka source at the offset after the
+/// last record it has read, which is restored from a snapshot. Other
+/// partitions start from the earliest offset.
+#[cfg(feature = "kafka")]
+struct KafkaContext {
+    topic: String,
+    offsets: Arc<Mutex<HashMap<i32, i64>>>,
+}
+
+#[cfg(feature = "kafka")]
+impl ClientContext for KafkaContext {}
+
+#[cfg(feature = "kafka")]
+impl ConsumerContext for KafkaContext {
+    fn rebalance(
+        &self,
+        native_client: &NativeClient,
+        err: RDKafkaRespErr,
+        tpl: &mut TopicPartitionList,
+    ) {
+        if err == RDKafkaRespErr::RD_KAFKA_RESP_ERR__ASSIGN_PARTITIONS {
+            for (partition, offset) in self.offsets.lock().unwrap().iter() {
+                if tpl.find_partition(&self.topic, *partition).is_some() {
+                    let offset = Offset::Offset(*offset);
+                    if let Err(e) = tpl.set_partition_offset(&self.topic, *partition, offset) {
+                        tracing::warn!("Failed to seek partition {}: {}", partition, e);
+                    }
+                }
+            }
+        }
+        DefaultConsumerContext.rebalance(native_client, err, tpl)
+    }
+}
@@ -5,8 +5,10 @@ use super::Event;
 use super::Stream;
 
 impl<T: Data> Stream<T> {
-    pub fn take(mut self, ctx: &mut Context, mut i: i32) -> Stream<T> {
+    pub fn take(mut self, ctx: &mut Context, i: i32) -> Stream<T> {
+        let state = ctx.operator_state();
         ctx.operator(move |tx| async move {
+            let mut i = state.restore().unwrap_or(i);
             loop {
                 if i == 0 {
                     tx.send(Event::Sentinel).await?;
@@ -19,7 +21,10 @@ impl<T: Data> Stream<T> {
                     }
                     Event::Watermark(t) => tx.send(Event::Watermark(t)).await?,
 
-                    Event::Snapshot(i) => tx.send(Event::Snapshot(i)).await?,
+                    Event::Snapshot(id) => {
+                        state.snapshot(id, &i);
+                        tx.send(Event::Snapshot(id)).await?;
+                    }
                     Event::Sentinel => {
                         tx.send(Event::Sentinel).await?;
                         break;
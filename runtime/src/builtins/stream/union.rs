@@ -0,0 +1,62 @@
+use std::task::Poll;
+
+use crate::builtins::stream::barrier::Alignment;
+use crate::builtins::stream::frontier::Frontier;
+use crate::builtins::stream::Event;
+use crate::runner::context::Context;
+use crate::traits::Data;
+
+use super::Stream;
+
+impl<T: Data> Stream<T> {
+    /// Merges any number of streams. The watermark is the minimum over the
+    /// inputs which have not terminated, and the output terminates once every
+    /// input has.
+    pub fn union(ctx: &mut Context, mut inputs: Vec<Stream<T>>) -> Stream<T> {
+        ctx.operator(|tx| async move {
+            let n = inputs.len();
+            let mut frontier = Frontier::new(n);
+            let mut barriers = Alignment::new(n);
+            // Inputs are polled round-robin, starting after the last one which was ready.
+            let mut next = 0;
+            while !frontier.all_done() {
+                let (i, event) = std::future::poll_fn(|cx| {
+                    for j in 0..n {
+                        let i = (next + j) % n;
+                        if frontier.is_done(i) || barriers.is_blocked(i) {
+                            continue;
+                        }
+                        if let Poll::Ready(event) = inputs[i].0.poll_recv(cx) {
+                            return Poll::Ready((i, event.unwrap_or(Event::Sentinel)));
+                        }
+                    }
+                    Poll::Pending
+                })
+                .await;
+                next = (i + 1) % n;
+                match event {
+                    Event::Data(t, v) => tx.send(Event::Data(t, v)).await?,
+                    Event::Watermark(t) => {
+                        if let Some(t) = frontier.advance(i, t) {
+                            tx.send(Event::Watermark(t)).await?;
+                        }
+                    }
+                    Event::Snapshot(s) => {
+                        if let Some(s) = barriers.arrive(i, s) {
+                            tx.send(Event::Snapshot(s)).await?;
+                        }
+                    }
+                    Event::Sentinel => {
+                        if let Some(t) = frontier.finish(i) {
+                            tx.send(Event::Watermark(t)).await?;
+                        }
+                        if let Some(s) = barriers.finish(i) {
+                            tx.send(Event::Snapshot(s)).await?;
+                        }
+                    }
+                }
+            }
+            tx.send(Event::Sentinel).await
+        })
+    }
+}
@@ -0,0 +1,156 @@
+use wasmtime::component::ComponentType;
+use wasmtime::component::Lift;
+use wasmtime::component::Lower;
+use wasmtime::component::Type;
+
+use crate::builtins::wasm::WasmComponent;
+use crate::error::Error;
+use crate::runner::context::Context;
+use crate::traits::Data;
+
+use super::Event;
+use super::Stream;
+
+impl<T> Stream<T>
+where
+    T: Data + ComponentType + Lower,
+{
+    /// Maps each record with an export of type `func(x: T) -> O`. The export
+    /// is type-checked when the dataflow is built, and a trap cancels the
+    /// dataflow.
+    pub fn wasm_map<O>(
+        self,
+        ctx: &mut Context,
+        component: &WasmComponent,
+        export: &str,
+    ) -> Result<Stream<O>, Error>
+    where
+        O: Data + ComponentType + Lift,
+    {
+        let export = component.export::<(T,), (O,)>(export)?;
+        Ok(self.wasm_operator(ctx, export, |export, v| Ok(Some(export.call((v,))?.0))))
+    }
+
+    /// Keeps the records for which an export of type `func(x: T) -> bool`
+    /// returns true.
+    pub fn wasm_filter(
+        self,
+        ctx: &mut Context,
+        component: &WasmComponent,
+        export: &str,
+    ) -> Result<Stream<T>, Error> {
+        let export = component.export::<(T,), (bool,)>(export)?;
+        Ok(self.wasm_operator(ctx, export, |export, v| {
+            Ok(export.call((v.clone(),))?.0.then_some(v))
+        }))
+    }
+
+    /// Maps each record with an export of type `func(x: T) -> option<O>`, and
+    /// drops the records for which it returns `none`.
+    pub fn wasm_filter_map<O>(
+        self,
+        ctx: &mut Context,
+        component: &WasmComponent,
+        export: &str,
+    ) -> Result<Stream<O>, Error>
+    where
+        O: Data + ComponentType + Lift,
+    {
+        let export = component.export::<(T,), (Option<O>,)>(export)?;
+        Ok(self.wasm_operator(ctx, export, |export, v| Ok(export.call((v,))?.0)))
+    }
+}
+
+impl<T> Stream<T>
+where
+    T: Data,
+{
+    /// Like `wasm_map`, but the type of the export is only known at runtime.
+    /// Records are converted to and from the parameters and results of the
+    /// export through their serde encoding, so a component and export chosen
+    /// at runtime can process any stream. A record which does not match the
+    /// type of the export cancels the dataflow.
+    pub fn wasm_dyn_map<O>(
+        self,
+        ctx: &mut Context,
+        component: &WasmComponent,
+        export: &str,
+    ) -> Result<Stream<O>, Error>
+    where
+        O: Data,
+    {
+        let export = component.dyn_export(export)?;
+        Ok(self.wasm_operator(ctx, export, |export, v| Ok(Some(export.call(&v)?))))
+    }
+
+    /// Like `wasm_filter`, but the type of the export is only known at
+    /// runtime. The export must return a `bool`.
+    pub fn wasm_dyn_filter(
+        self,
+        ctx: &mut Context,
+        component: &WasmComponent,
+        name: &str,
+    ) -> Result<Stream<T>, Error> {
+        let export = component.dyn_export(name)?;
+        if !matches!(export.results(), [Type::Bool]) {
+            return Err(Error::custom(format!(
+                "Export `{}` must return a bool",
+                name
+            )));
+        }
+        Ok(self.wasm_operator(ctx, export, |export, v| {
+            Ok(export.call::<T, bool>(&v)?.then_some(v))
+        }))
+    }
+
+    /// Like `wasm_filter_map`, but the type of the export is only known at
+    /// runtime. The export must return an `option`.
+    pub fn wasm_dyn_filter_map<O>(
+        self,
+        ctx: &mut Context,
+        component: &WasmComponent,
+        name: &str,
+    ) -> Result<Stream<O>, Error>
+    where
+        O: Data,
+    {
+        let export = component.dyn_export(name)?;
+        if !matches!(export.results(), [Type::Option(_)]) {
+            return Err(Error::custom(format!(
+                "Export `{}` must return an option",
+                name
+            )));
+        }
+        Ok(self.wasm_operator(ctx, export, |export, v| export.call(&v)))
+    }
+
+    fn wasm_operator<E, O>(
+        mut self,
+        ctx: &mut Context,
+        mut export: E,
+        f: impl Fn(&mut E, T) -> Result<Option<O>, Error> + Send + 'static,
+    ) -> Stream<O>
+    where
+        E: Send + 'static,
+        O: Data,
+    {
+        ctx.operator(|tx| async move {
+            loop {
+                match self.recv().await {
+                    Event::Data(t, v) => {
+                        if let Some(o) = f(&mut export, v)? {
+                            tx.send(Event::Data(t, o)).await?;
+                        }
+                    }
+                    Event::Watermark(t) => tx.send(Event::Watermark(t)).await?,
+                    Event::Snapshot(i) => tx.send(Event::Snapshot(i)).await?,
+                    Event::Sentinel => {
+                        tx.send(Event::Sentinel).await?;
+                        break;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+}
@@ -1,5 +1,9 @@
 use crate::builtins::duration::Duration;
 use crate::builtins::time::Time;
+use crate::BTreeMap;
+
+use serde::Deserialize;
+use serde::Serialize;
 
 pub mod count_sliding_aligned_commutative_associative;
 pub mod count_sliding_holistic;
@@ -13,7 +17,10 @@ pub mod time_sliding_invertible;
 pub mod time_tumbling_holistic;
 pub mod time_sliding_aligned_holistic_vec;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub mod time_session_commutative_associative;
+pub mod time_session_holistic;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct WindowRange {
     pub t0: Time,
     pub t1: Time,
@@ -34,3 +41,69 @@ impl WindowRange {
 pub fn align(time: Time, step: Duration) -> Time {
     time.div_floor(step) * step
 }
+
+/// Disjoint session windows ordered by start time, each holding an aggregate.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Sessions<A>(BTreeMap<Time, (Time, A)>);
+
+impl<A> Default for Sessions<A> {
+    fn default() -> Self {
+        Self(BTreeMap::new())
+    }
+}
+
+impl<A> Sessions<A> {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Inserts an event at `time` which opens the session `time..time+gap`. All
+    /// sessions which overlap it, including ones bridged by an out-of-order
+    /// event, are merged into one.
+    pub(crate) fn insert(&mut self, time: Time, gap: Duration, agg: A, merge: impl Fn(A, A) -> A) {
+        let mut t0 = time;
+        let mut t1 = time + gap;
+        let mut agg = agg;
+        let overlapping = self
+            .0
+            .range(..=t1)
+            .rev()
+            .take_while(|(_, (end, _))| *end >= time)
+            .map(|(start, _)| *start)
+            .collect::<Vec<_>>();
+        for start in overlapping {
+            let (end, other) = self.0.remove(&start).unwrap();
+            t0 = t0.min(start);
+            t1 = t1.max(end);
+            agg = merge(other, agg);
+        }
+        self.0.insert(t0, (t1, agg));
+    }
+
+    /// Whether an event at `time` is late, i.e. its session `time..time+gap`
+    /// ends at or before the watermark and is not merged into an open session.
+    pub(crate) fn is_late(&self, time: Time, gap: Duration, watermark: Time) -> bool {
+        let t1 = time + gap;
+        t1 <= watermark
+            && self
+                .0
+                .range(..=t1)
+                .next_back()
+                .is_none_or(|(_, (end, _))| *end < time)
+    }
+
+    /// Removes the sessions which end at or before the watermark.
+    pub(crate) fn expire(&mut self, watermark: Time) -> Vec<(WindowRange, A)> {
+        let mut expired = Vec::new();
+        while let Some(entry) = self.0.first_entry() {
+            let (t1, _) = entry.get();
+            if *t1 > watermark {
+                break;
+            }
+            let t0 = *entry.key();
+            let (t1, agg) = entry.remove();
+            expired.push((WindowRange::new(t0, t1), agg));
+        }
+        expired
+    }
+}
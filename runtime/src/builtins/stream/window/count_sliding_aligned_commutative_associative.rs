@@ -21,10 +21,10 @@ impl<T: Data> Stream<T> {
         P: Data,
         O: Data,
     {
-        assert!(size % step == 0);
+        assert!(size.is_multiple_of(step));
+        let state = ctx.operator_state();
         ctx.operator(move |tx| async move {
-            let mut s: VecDeque<P> = VecDeque::new();
-            let mut n = 0;
+            let (mut s, mut n): (VecDeque<P>, usize) = state.restore().unwrap_or_default();
             loop {
                 match self.recv().await {
                     Event::Data(time, data) => {
@@ -48,7 +48,10 @@ impl<T: Data> Stream<T> {
                         }
                     }
                     Event::Watermark(time) => tx.send(Event::Watermark(time)).await?,
-                    Event::Snapshot(i) => tx.send(Event::Snapshot(i)).await?,
+                    Event::Snapshot(i) => {
+                        state.snapshot(i, &(&s, n));
+                        tx.send(Event::Snapshot(i)).await?;
+                    }
                     Event::Sentinel => {
                         tx.send(Event::Sentinel).await?;
                         break;
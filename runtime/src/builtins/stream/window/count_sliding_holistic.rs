@@ -16,8 +16,11 @@ impl<T: Data> Stream<T> {
     where
         O: Data,
     {
+        let state = ctx.operator_state();
         ctx.operator(move |tx| async move {
-            let mut s: VecDeque<T> = VecDeque::with_capacity(size);
+            let mut s: VecDeque<T> = state
+                .restore()
+                .unwrap_or_else(|| VecDeque::with_capacity(size));
             loop {
                 match self.recv().await {
                     Event::Data(time, data) => {
@@ -34,6 +37,7 @@ impl<T: Data> Stream<T> {
                         tx.send(Event::Watermark(time)).await?;
                     }
                     Event::Snapshot(i) => {
+                        state.snapshot(i, &s);
                         tx.send(Event::Snapshot(i)).await?;
                     }
                     Event::Sentinel => {
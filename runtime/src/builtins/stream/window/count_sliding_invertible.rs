@@ -22,10 +22,11 @@ impl<T: Data> Stream<T> {
         P: Data,
         O: Data,
     {
+        let state = ctx.operator_state();
         ctx.operator(move |tx| async move {
-            let mut s: P = init;
-            let mut vec: VecDeque<P> = VecDeque::new();
-            let mut n = 0;
+            let (mut s, mut vec, mut n): (P, VecDeque<P>, usize) = state
+                .restore()
+                .unwrap_or_else(|| (init, VecDeque::new(), 0));
             loop {
                 match self.recv().await {
                     Event::Data(time, data) => {
@@ -47,6 +48,7 @@ impl<T: Data> Stream<T> {
                         tx.send(Event::Watermark(time)).await?;
                     }
                     Event::Snapshot(i) => {
+                        state.snapshot(i, &(&s, &vec, n));
                         tx.send(Event::Snapshot(i)).await?;
                     }
                     Event::Sentinel => {
@@ -13,8 +13,11 @@ impl<T: Data> Stream<T> {
     where
         O: Data,
     {
+        let state = ctx.operator_state();
         ctx.operator(move |tx| async move {
-            let mut agg: Vec<T> = Vec::with_capacity(size);
+            let mut agg: Vec<T> = state
+                .restore()
+                .unwrap_or_else(|| Vec::with_capacity(size));
             loop {
                 match self.recv().await {
                     Event::Data(time, data) => {
@@ -26,7 +29,10 @@ impl<T: Data> Stream<T> {
                         }
                     }
                     Event::Watermark(time) => tx.send(Event::Watermark(time)).await?,
-                    Event::Snapshot(i) => tx.send(Event::Snapshot(i)).await?,
+                    Event::Snapshot(i) => {
+                        state.snapshot(i, &agg);
+                        tx.send(Event::Snapshot(i)).await?;
+                    }
                     Event::Sentinel => {
                         tx.send(Event::Sentinel).await?;
                         break;
@@ -0,0 +1,95 @@
+use crate::builtins::duration::Duration;
+use crate::builtins::lateness::FireOnceLateness;
+use crate::builtins::stream::Event;
+use crate::builtins::stream::Stream;
+use crate::builtins::time::Time;
+use crate::runner::context::Context;
+use crate::traits::Data;
+
+use super::Sessions;
+use super::WindowRange;
+
+impl<T: Data> Stream<T> {
+    // Properties:
+    // * Commutative and associative: Partial aggregates of merged sessions can
+    //   be combined in any order.
+    pub fn time_session_commutative_associative_window<P, O>(
+        self,
+        ctx: &mut Context,
+        gap: Duration,
+        lift: impl Fn(&T) -> P + Send + 'static,
+        combine: impl Fn(&P, &P) -> P + Send + 'static,
+        lower: impl Fn(&P, WindowRange) -> O + Send + 'static,
+    ) -> Stream<O>
+    where
+        O: Data,
+        P: Data,
+    {
+        self.time_session_commutative_associative_window_with_lateness(
+            ctx,
+            gap,
+            FireOnceLateness::Drop,
+            lift,
+            combine,
+            lower,
+        )
+        .0
+    }
+
+    /// Like `time_session_commutative_associative_window`, but events whose
+    /// session has already fired, and which do not fall into an open session,
+    /// are handled by the lateness policy. The second stream is the late output.
+    pub fn time_session_commutative_associative_window_with_lateness<P, O>(
+        mut self,
+        ctx: &mut Context,
+        gap: Duration,
+        lateness: FireOnceLateness,
+        lift: impl Fn(&T) -> P + Send + 'static,
+        combine: impl Fn(&P, &P) -> P + Send + 'static,
+        lower: impl Fn(&P, WindowRange) -> O + Send + 'static,
+    ) -> (Stream<O>, Stream<T>)
+    where
+        O: Data,
+        P: Data,
+    {
+        let state = ctx.operator_state();
+        ctx.co_operator(move |tx, late| async move {
+            let (mut sessions, mut watermark): (Sessions<P>, Time) = state
+                .restore()
+                .unwrap_or_else(|| (Sessions::default(), Time::zero()));
+            loop {
+                match self.recv().await {
+                    Event::Data(time, data) => {
+                        if sessions.is_late(time, gap, watermark) {
+                            if lateness.routes_late() {
+                                late.send(Event::Data(time, data)).await.ok();
+                            }
+                            continue;
+                        }
+                        sessions.insert(time, gap, lift(&data), |a, b| combine(&a, &b));
+                    }
+                    Event::Watermark(time) => {
+                        for (wr, p) in sessions.expire(time) {
+                            let data = lower(&p, wr);
+                            tx.send(Event::Data(wr.t1, data)).await?;
+                        }
+                        watermark = time;
+                        tx.send(Event::Watermark(time)).await?;
+                        late.send(Event::Watermark(time)).await.ok();
+                    }
+                    Event::Snapshot(i) => {
+                        state.snapshot(i, &(&sessions, watermark));
+                        tx.send(Event::Snapshot(i)).await?;
+                        late.send(Event::Snapshot(i)).await.ok();
+                    }
+                    Event::Sentinel => {
+                        late.send(Event::Sentinel).await.ok();
+                        tx.send(Event::Sentinel).await?;
+                        break;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,85 @@
+use crate::builtins::duration::Duration;
+use crate::builtins::lateness::FireOnceLateness;
+use crate::builtins::stream::Event;
+use crate::builtins::stream::Stream;
+use crate::builtins::time::Time;
+use crate::runner::context::Context;
+use crate::traits::Data;
+
+use super::Sessions;
+use super::WindowRange;
+
+impl<T: Data> Stream<T> {
+    /// A session closes when no data has arrived for `gap`. Sessions are merged
+    /// when an out-of-order event bridges the gap between them, and fire once
+    /// the watermark passes their end.
+    pub fn time_session_holistic_window<O>(
+        self,
+        ctx: &mut Context,
+        gap: Duration,
+        compute: impl Fn(&[T], WindowRange) -> O + Send + 'static,
+    ) -> Stream<O>
+    where
+        O: Data,
+    {
+        self.time_session_holistic_window_with_lateness(ctx, gap, FireOnceLateness::Drop, compute)
+            .0
+    }
+
+    /// Like `time_session_holistic_window`, but events whose session has
+    /// already fired, and which do not fall into an open session, are handled
+    /// by the lateness policy. The second stream is the late output.
+    pub fn time_session_holistic_window_with_lateness<O>(
+        mut self,
+        ctx: &mut Context,
+        gap: Duration,
+        lateness: FireOnceLateness,
+        compute: impl Fn(&[T], WindowRange) -> O + Send + 'static,
+    ) -> (Stream<O>, Stream<T>)
+    where
+        O: Data,
+    {
+        let state = ctx.operator_state();
+        ctx.co_operator(move |tx, late| async move {
+            let (mut sessions, mut watermark): (Sessions<Vec<T>>, Time) = state
+                .restore()
+                .unwrap_or_else(|| (Sessions::default(), Time::zero()));
+            loop {
+                match self.recv().await {
+                    Event::Data(time, data) => {
+                        if sessions.is_late(time, gap, watermark) {
+                            if lateness.routes_late() {
+                                late.send(Event::Data(time, data)).await.ok();
+                            }
+                            continue;
+                        }
+                        sessions.insert(time, gap, vec![data], |mut a, b| {
+                            a.extend(b);
+                            a
+                        });
+                    }
+                    Event::Watermark(time) => {
+                        for (wr, vs) in sessions.expire(time) {
+                            let data = compute(&vs, wr);
+                            tx.send(Event::Data(wr.t1, data)).await?;
+                        }
+                        watermark = time;
+                        tx.send(Event::Watermark(time)).await?;
+                        late.send(Event::Watermark(time)).await.ok();
+                    }
+                    Event::Snapshot(i) => {
+                        state.snapshot(i, &(&sessions, watermark));
+                        tx.send(Event::Snapshot(i)).await?;
+                        late.send(Event::Snapshot(i)).await.ok();
+                    }
+                    Event::Sentinel => {
+                        late.send(Event::Sentinel).await.ok();
+                        tx.send(Event::Sentinel).await?;
+                        break;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+}
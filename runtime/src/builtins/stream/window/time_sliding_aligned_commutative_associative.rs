@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 
 use crate::builtins::duration::Duration;
+use crate::builtins::lateness::FireOnceLateness;
 use crate::builtins::stream::window::align;
 use crate::builtins::stream::Event;
 use crate::builtins::stream::Stream;
@@ -14,28 +15,70 @@ impl<T: Data> Stream<T> {
     // Requires that duration % step == 0
     #[allow(clippy::too_many_arguments)]
     pub fn time_sliding_aligned_commutative_associative_window<P, O>(
+        self,
+        ctx: &mut Context,
+        duration: Duration,
+        step: Duration,
+        init: P,
+        lift: impl Fn(&T) -> P + Send + 'static,
+        combine: impl Fn(&P, &P) -> P + Send + 'static,
+        lower: impl Fn(&P, WindowRange) -> O + Send + 'static,
+    ) -> Stream<O>
+    where
+        O: Data,
+        P: Data,
+    {
+        self.time_sliding_aligned_commutative_associative_window_with_lateness(
+            ctx,
+            duration,
+            step,
+            FireOnceLateness::Drop,
+            init,
+            lift,
+            combine,
+            lower,
+        )
+        .0
+    }
+
+    /// Like `time_sliding_aligned_commutative_associative_window`, but events
+    /// whose last window has already fired are handled by the lateness policy.
+    /// The second stream is the late output.
+    #[allow(clippy::too_many_arguments)]
+    pub fn time_sliding_aligned_commutative_associative_window_with_lateness<P, O>(
         mut self,
         ctx: &mut Context,
         duration: Duration,
         step: Duration,
+        lateness: FireOnceLateness,
         _init: P,
         lift: impl Fn(&T) -> P + Send + 'static,
         combine: impl Fn(&P, &P) -> P + Send + 'static,
         lower: impl Fn(&P, WindowRange) -> O + Send + 'static,
-    ) -> Stream<O>
+    ) -> (Stream<O>, Stream<T>)
     where
         O: Data,
         P: Data,
     {
         assert!(duration % step == Duration::from_seconds(0));
-        ctx.operator(move |tx| async move {
-            let mut slices: BTreeMap<Time, P> = BTreeMap::new();
+        let state = ctx.operator_state();
+        ctx.co_operator(move |tx, late| async move {
+            let (mut slices, mut watermark): (BTreeMap<Time, P>, Time) = state
+                .restore()
+                .unwrap_or_else(|| (BTreeMap::new(), Time::zero()));
             loop {
                 match self.recv().await {
                     Event::Data(time, data) => {
+                        let t0 = align(time, step);
+                        if t0 + duration <= watermark {
+                            if lateness.routes_late() {
+                                late.send(Event::Data(time, data)).await.ok();
+                            }
+                            continue;
+                        }
                         let data = lift(&data);
                         slices
-                            .entry(align(time, step))
+                            .entry(t0)
                             .and_modify(|agg| *agg = combine(agg, &data))
                             .or_insert(data);
                     }
@@ -55,12 +98,17 @@ impl<T: Data> Stream<T> {
                                 break;
                             }
                         }
+                        watermark = time;
                         tx.send(Event::Watermark(time)).await?;
+                        late.send(Event::Watermark(time)).await.ok();
                     }
                     Event::Snapshot(i) => {
+                        state.snapshot(i, &(&slices, watermark));
                         tx.send(Event::Snapshot(i)).await?;
+                        late.send(Event::Snapshot(i)).await.ok();
                     }
                     Event::Sentinel => {
+                        late.send(Event::Sentinel).await.ok();
                         tx.send(Event::Sentinel).await?;
                         break;
                     }
@@ -1,21 +1,25 @@
 use std::collections::BTreeMap;
 
 use crate::builtins::duration::Duration;
+use crate::builtins::lateness::Lateness;
 use crate::builtins::stream::Event;
 use crate::builtins::stream::Stream;
 use crate::builtins::time::Time;
 use crate::runner::context::Context;
 use crate::traits::Data;
 
-use super::WindowRange;
+use serde::Deserialize;
+use serde::Serialize;
+
 use super::align;
+use super::WindowRange;
 
 impl<T: Data> Stream<T> {
     /// This window maintains a BTreeMap that maps window starting times to slices of data, where
     /// each slice is a vector of (Time, Data) pairs. When a watermark is received, the window
     /// iterates over the slices before the watermark and sends the result of the compute function
     pub fn time_sliding_aligned_holistic_window<O>(
-        mut self,
+        self,
         ctx: &mut Context,
         duration: Duration,
         step: Duration,
@@ -24,32 +28,88 @@ impl<T: Data> Stream<T> {
     where
         O: Data,
     {
-        ctx.operator(move |tx| async move {
-            let mut s: WindowState<T> = WindowState::new();
+        self.time_sliding_aligned_holistic_window_with_lateness(
+            ctx,
+            duration,
+            step,
+            Lateness::Drop,
+            compute,
+        )
+        .0
+    }
+
+    /// Like `time_sliding_aligned_holistic_window`, but events whose windows
+    /// have all fired are handled by the lateness policy. Slices are kept
+    /// until the allowed lateness has passed, and windows which receive late
+    /// events fire again. The second stream is the late output.
+    pub fn time_sliding_aligned_holistic_window_with_lateness<O>(
+        mut self,
+        ctx: &mut Context,
+        duration: Duration,
+        step: Duration,
+        lateness: Lateness,
+        compute: impl Fn(Window<T>, WindowRange) -> O + Send + 'static,
+    ) -> (Stream<O>, Stream<T>)
+    where
+        O: Data,
+    {
+        let state = ctx.operator_state();
+        let allowed = lateness.allowed_lateness();
+        ctx.co_operator(move |tx, late| async move {
+            let (mut s, mut watermark): (WindowState<T>, Time) = state
+                .restore()
+                .unwrap_or_else(|| (WindowState::new(), Time::zero()));
             loop {
                 match self.recv().await {
                     Event::Data(time, data) => {
-                        let t0 = align(time, step);
-                        s.get_mut(t0).0.push((time, data));
+                        let t = align(time, step);
+                        if t + duration + allowed <= watermark {
+                            if lateness.routes_late() {
+                                late.send(Event::Data(time, data)).await.ok();
+                            }
+                            continue;
+                        }
+                        s.get_mut(t).0.push((time, data));
+                        // Windows which contain the slice and have already fired, fire again.
+                        for t0 in s.0.range(..=t).map(|(t0, _)| *t0) {
+                            let wr = WindowRange::new(t0, t0 + duration);
+                            if wr.t1 <= t || wr.t1 > watermark {
+                                continue;
+                            }
+                            let data = compute(Window::new(&s.0, wr), wr);
+                            tx.send(Event::Data(wr.t1, data)).await?;
+                        }
                     }
                     Event::Watermark(time) => {
-                        while let Some(entry) = s.0.first_entry() {
-                            let t0 = *entry.key();
-                            let wr = WindowRange::new(t0, t0 + duration);
+                        for t0 in s.0.keys() {
+                            let wr = WindowRange::new(*t0, *t0 + duration);
                             if wr.t1 > time {
                                 break;
                             }
-                            let win = Window::new(&s.0, wr.t1);
-                            let data = compute(win, wr);
-                            s.0.pop_first();
+                            if wr.t1 <= watermark {
+                                continue;
+                            }
+                            let data = compute(Window::new(&s.0, wr), wr);
                             tx.send(Event::Data(wr.t1, data)).await?;
                         }
+                        while let Some(entry) = s.0.first_entry() {
+                            if *entry.key() + duration + allowed <= time {
+                                entry.remove();
+                            } else {
+                                break;
+                            }
+                        }
+                        watermark = time;
                         tx.send(Event::Watermark(time)).await?;
+                        late.send(Event::Watermark(time)).await.ok();
                     }
                     Event::Snapshot(i) => {
+                        state.snapshot(i, &(&s, watermark));
                         tx.send(Event::Snapshot(i)).await?;
+                        late.send(Event::Snapshot(i)).await.ok();
                     }
                     Event::Sentinel => {
+                        late.send(Event::Sentinel).await.ok();
                         tx.send(Event::Sentinel).await?;
                         break;
                     }
@@ -60,7 +120,7 @@ impl<T: Data> Stream<T> {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 struct WindowState<T>(BTreeMap<Time, Slice<T>>);
 
 impl<T> WindowState<T> {
@@ -75,15 +135,15 @@ impl<T> WindowState<T> {
 #[derive(Debug)]
 pub struct Window<'a, T> {
     buffer: &'a BTreeMap<Time, Slice<T>>,
-    t1: Time,
+    wr: WindowRange,
 }
 
 impl<'a, T> Window<'a, T> {
-    fn new(buffer: &'a BTreeMap<Time, Slice<T>>, t1: Time) -> Self {
-        Self { buffer, t1 }
+    fn new(buffer: &'a BTreeMap<Time, Slice<T>>, wr: WindowRange) -> Self {
+        Self { buffer, wr }
     }
-    pub fn iter(&self) -> WindowIter<T> {
-        WindowIter::new(self.buffer.range(..self.t1))
+    pub fn iter(&self) -> WindowIter<'_, T> {
+        WindowIter::new(self.buffer.range(self.wr.t0..self.wr.t1))
     }
 }
 
@@ -96,7 +156,7 @@ impl<'a, T> IntoIterator for &'a Window<'a, T> {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 struct Slice<T>(Vec<(Time, T)>);
 
 impl<T> Default for Slice<T> {
@@ -1,10 +1,14 @@
 use crate::builtins::duration::Duration;
+use crate::builtins::lateness::FireOnceLateness;
 use crate::builtins::stream::Event;
 use crate::builtins::stream::Stream;
 use crate::builtins::time::Time;
 use crate::runner::context::Context;
 use crate::traits::Data;
 
+use serde::Deserialize;
+use serde::Serialize;
+
 use super::align;
 use super::WindowRange;
 
@@ -13,7 +17,7 @@ impl<T: Data> Stream<T> {
     /// each slice is a collection of (Time, Data) pairs. When a watermark is received, the window
     /// iterates over the slices before the watermark and sends the result of the compute function
     pub fn time_sliding_aligned_holistic_vec_window<O>(
-        mut self,
+        self,
         ctx: &mut Context,
         duration: Duration,
         step: Duration,
@@ -22,14 +26,46 @@ impl<T: Data> Stream<T> {
     where
         O: Data,
     {
-        ctx.operator(move |tx| async move {
-            let mut buffer: Vec<(Time, Slice<T>)> = Vec::new();
-            // Slices before this time are sorted
-            let mut t_sorted: Time = Time::zero();
+        self.time_sliding_aligned_holistic_vec_window_with_lateness(
+            ctx,
+            duration,
+            step,
+            FireOnceLateness::Drop,
+            compute,
+        )
+        .0
+    }
+
+    /// Like `time_sliding_aligned_holistic_vec_window`, but events whose slice
+    /// has already been evicted are handled by the lateness policy. The second
+    /// stream is the late output.
+    pub fn time_sliding_aligned_holistic_vec_window_with_lateness<O>(
+        mut self,
+        ctx: &mut Context,
+        duration: Duration,
+        step: Duration,
+        lateness: FireOnceLateness,
+        compute: impl Fn(Window<T>, WindowRange) -> O + Send + 'static,
+    ) -> (Stream<O>, Stream<T>)
+    where
+        O: Data,
+    {
+        let state = ctx.operator_state();
+        ctx.co_operator(move |tx, late| async move {
+            // Slices before `t_sorted` are sorted
+            let (mut buffer, mut t_sorted): (Vec<(Time, Slice<T>)>, Time) = state
+                .restore()
+                .unwrap_or_else(|| (Vec::new(), Time::zero()));
             loop {
                 match self.recv().await {
                     Event::Data(time, data) => {
                         let t0 = align(time, step);
+                        if t0 < t_sorted {
+                            if lateness.routes_late() {
+                                late.send(Event::Data(time, data)).await.ok();
+                            }
+                            continue;
+                        }
                         match buffer.binary_search_by_key(&t0, |(t, _)| *t) {
                             Ok(i) => {
                                 (buffer[i].1).0.push((time, data));
@@ -62,11 +98,15 @@ impl<T: Data> Stream<T> {
                         }
                         buffer.retain(|(t, _)| *t >= t_safe);
                         tx.send(Event::Watermark(time)).await?;
+                        late.send(Event::Watermark(time)).await.ok();
                     }
                     Event::Snapshot(i) => {
+                        state.snapshot(i, &(&buffer, t_sorted));
                         tx.send(Event::Snapshot(i)).await?;
+                        late.send(Event::Snapshot(i)).await.ok();
                     }
                     Event::Sentinel => {
+                        late.send(Event::Sentinel).await.ok();
                         tx.send(Event::Sentinel).await?;
                         break;
                     }
@@ -85,7 +125,7 @@ impl<'a, T> Window<'a, T> {
     fn new(buffer: &'a [(Time, Slice<T>)]) -> Self {
         Self { buffer }
     }
-    pub fn iter(&self) -> WindowIter<T> {
+    pub fn iter(&self) -> WindowIter<'_, T> {
         WindowIter::new(self.buffer.iter())
     }
 }
@@ -99,6 +139,7 @@ impl<'a, T> IntoIterator for &'a Window<'a, T> {
     }
 }
 
+#[derive(Serialize, Deserialize)]
 struct Slice<T>(Vec<(Time, T)>);
 
 impl<T> Slice<T> {
@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 
 use crate::builtins::duration::Duration;
+use crate::builtins::lateness::FireOnceLateness;
 use crate::builtins::stream::Event;
 use crate::builtins::stream::Stream;
 use crate::builtins::time::Time;
@@ -14,7 +15,7 @@ impl<T: Data> Stream<T> {
     // * Inverse: We can undo aggregations.
     #[allow(clippy::too_many_arguments)]
     pub fn time_sliding_commutative_invertible_window<P, O>(
-        mut self,
+        self,
         ctx: &mut Context,
         duration: Duration,
         step: Duration,
@@ -28,13 +29,59 @@ impl<T: Data> Stream<T> {
         O: Data,
         P: Data,
     {
-        ctx.operator(move |tx| async move {
-            let mut buffer: BTreeMap<Time, P> = BTreeMap::new();
-            let mut first: Option<WindowRange> = None;
-            let mut agg: P = init;
+        self.time_sliding_commutative_invertible_window_with_lateness(
+            ctx,
+            duration,
+            step,
+            FireOnceLateness::Drop,
+            init,
+            lift,
+            combine,
+            lower,
+            inverse,
+        )
+        .0
+    }
+
+    /// Like `time_sliding_commutative_invertible_window`, but events whose last
+    /// window has already fired are handled by the lateness policy. The second
+    /// stream is the late output.
+    #[allow(clippy::too_many_arguments)]
+    pub fn time_sliding_commutative_invertible_window_with_lateness<P, O>(
+        mut self,
+        ctx: &mut Context,
+        duration: Duration,
+        step: Duration,
+        lateness: FireOnceLateness,
+        init: P,
+        lift: impl Fn(&T) -> P + Send + 'static,
+        combine: impl Fn(&P, &P) -> P + Send + 'static,
+        lower: impl Fn(&P, WindowRange) -> O + Send + 'static,
+        inverse: impl Fn(&P, &P) -> P + Send + 'static,
+    ) -> (Stream<O>, Stream<T>)
+    where
+        O: Data,
+        P: Data,
+    {
+        let state = ctx.operator_state();
+        ctx.co_operator(move |tx, late| async move {
+            let (mut buffer, mut first, mut agg, mut watermark): (
+                BTreeMap<Time, P>,
+                Option<WindowRange>,
+                P,
+                Time,
+            ) = state
+                .restore()
+                .unwrap_or_else(|| (BTreeMap::new(), None, init, Time::zero()));
             loop {
                 match self.recv().await {
                     Event::Data(time, data) => {
+                        if WindowRange::of(time, duration, step).t1 <= watermark {
+                            if lateness.routes_late() {
+                                late.send(Event::Data(time, data)).await.ok();
+                            }
+                            continue;
+                        }
                         // With commutativity, we can pre-aggregate data for the first window
                         let data = lift(&data);
                         if let Some(first) = first.as_mut() {
@@ -74,14 +121,21 @@ impl<T: Data> Stream<T> {
                                 for (_, p) in std::mem::replace(&mut buffer, after) {
                                     agg = inverse(&agg, &p);
                                 }
+                            } else {
+                                break;
                             }
                         }
+                        watermark = time;
                         tx.send(Event::Watermark(time)).await?;
+                        late.send(Event::Watermark(time)).await.ok();
                     }
                     Event::Snapshot(i) => {
+                        state.snapshot(i, &(&buffer, first, &agg, watermark));
                         tx.send(Event::Snapshot(i)).await?;
+                        late.send(Event::Snapshot(i)).await.ok();
                     }
                     Event::Sentinel => {
+                        late.send(Event::Sentinel).await.ok();
                         tx.send(Event::Sentinel).await?;
                         break;
                     }
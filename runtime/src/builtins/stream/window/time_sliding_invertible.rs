@@ -1,4 +1,5 @@
 use crate::builtins::duration::Duration;
+use crate::builtins::lateness::FireOnceLateness;
 use crate::builtins::stream::Event;
 use crate::builtins::stream::Stream;
 use crate::builtins::time::Time;
@@ -13,7 +14,7 @@ impl<T: Data> Stream<T> {
     // * Inverse: We can undo aggregations.
     #[allow(clippy::too_many_arguments)]
     pub fn time_sliding_invertible_window<P, O>(
-        mut self,
+        self,
         ctx: &mut Context,
         duration: Duration,
         step: Duration,
@@ -27,12 +28,54 @@ impl<T: Data> Stream<T> {
         O: Data,
         P: Data,
     {
-        ctx.operator(move |tx| async move {
-            let mut buffer: BTreeMap<Time, P> = BTreeMap::new();
-            let mut agg: P = init;
+        self.time_sliding_invertible_window_with_lateness(
+            ctx,
+            duration,
+            step,
+            FireOnceLateness::Drop,
+            init,
+            lift,
+            combine,
+            lower,
+            inverse,
+        )
+        .0
+    }
+
+    /// Like `time_sliding_invertible_window`, but events whose last window has
+    /// already fired are handled by the lateness policy. The second stream is
+    /// the late output.
+    #[allow(clippy::too_many_arguments)]
+    pub fn time_sliding_invertible_window_with_lateness<P, O>(
+        mut self,
+        ctx: &mut Context,
+        duration: Duration,
+        step: Duration,
+        lateness: FireOnceLateness,
+        init: P,
+        lift: impl Fn(&T) -> P + Send + 'static,
+        combine: impl Fn(&P, &P) -> P + Send + 'static,
+        lower: impl Fn(&P, WindowRange) -> O + Send + 'static,
+        inverse: impl Fn(&P, &P) -> P + Send + 'static,
+    ) -> (Stream<O>, Stream<T>)
+    where
+        O: Data,
+        P: Data,
+    {
+        let state = ctx.operator_state();
+        ctx.co_operator(move |tx, late| async move {
+            let (mut buffer, mut agg, mut watermark): (BTreeMap<Time, P>, P, Time) = state
+                .restore()
+                .unwrap_or_else(|| (BTreeMap::new(), init, Time::zero()));
             loop {
                 match self.recv().await {
                     Event::Data(time, data) => {
+                        if WindowRange::of(time, duration, step).t1 < watermark {
+                            if lateness.routes_late() {
+                                late.send(Event::Data(time, data)).await.ok();
+                            }
+                            continue;
+                        }
                         buffer.insert(time, lift(&data));
                     }
                     Event::Watermark(time) => {
@@ -49,14 +92,21 @@ impl<T: Data> Stream<T> {
                                 for (_, p) in std::mem::replace(&mut buffer, after) {
                                     agg = inverse(&agg, &p);
                                 }
+                            } else {
+                                break;
                             }
                         }
+                        watermark = time;
                         tx.send(Event::Watermark(time)).await?;
+                        late.send(Event::Watermark(time)).await.ok();
                     }
                     Event::Snapshot(i) => {
+                        state.snapshot(i, &(&buffer, &agg, watermark));
                         tx.send(Event::Snapshot(i)).await?;
+                        late.send(Event::Snapshot(i)).await.ok();
                     }
                     Event::Sentinel => {
+                        late.send(Event::Sentinel).await.ok();
                         tx.send(Event::Sentinel).await?;
                         break;
                     }
@@ -1,4 +1,5 @@
 use crate::builtins::duration::Duration;
+use crate::builtins::lateness::Lateness;
 use crate::builtins::stream::Event;
 use crate::builtins::stream::Stream;
 use crate::builtins::time::Time;
@@ -11,7 +12,7 @@ use super::WindowRange;
 
 impl<T: Data> Stream<T> {
     pub fn time_tumbling_holistic_window<O>(
-        mut self,
+        self,
         ctx: &mut Context,
         duration: Duration,
         compute: impl Fn(&[T], WindowRange) -> O + Send + 'static,
@@ -19,32 +20,78 @@ impl<T: Data> Stream<T> {
     where
         O: Data,
     {
-        ctx.operator(move |tx| async move {
-            let mut buffer: BTreeMap<Time, Vec<T>> = BTreeMap::new();
+        self.time_tumbling_holistic_window_with_lateness(ctx, duration, Lateness::Drop, compute)
+            .0
+    }
+
+    /// Like `time_tumbling_holistic_window`, but events whose window has
+    /// already fired are handled by the lateness policy. The second stream is
+    /// the late output.
+    pub fn time_tumbling_holistic_window_with_lateness<O>(
+        mut self,
+        ctx: &mut Context,
+        duration: Duration,
+        lateness: Lateness,
+        compute: impl Fn(&[T], WindowRange) -> O + Send + 'static,
+    ) -> (Stream<O>, Stream<T>)
+    where
+        O: Data,
+    {
+        let state = ctx.operator_state();
+        let allowed = lateness.allowed_lateness();
+        ctx.co_operator(move |tx, late| async move {
+            let (mut buffer, mut watermark): (BTreeMap<Time, Vec<T>>, Time) = state
+                .restore()
+                .unwrap_or_else(|| (BTreeMap::new(), Time::zero()));
             loop {
                 match self.recv().await {
                     Event::Data(time, data) => {
                         let t0 = align(time, duration);
-                        buffer.entry(t0).or_default().push(data);
+                        let wr = WindowRange::new(t0, t0 + duration);
+                        if wr.t1 + allowed <= watermark {
+                            if lateness.routes_late() {
+                                late.send(Event::Data(time, data)).await.ok();
+                            }
+                            continue;
+                        }
+                        let vs = buffer.entry(t0).or_default();
+                        vs.push(data);
+                        if wr.t1 <= watermark {
+                            // The window has already fired, so fire it again.
+                            let data = compute(vs, wr);
+                            tx.send(Event::Data(wr.t1, data)).await?;
+                        }
                     }
                     Event::Watermark(time) => {
-                        while let Some(entry) = buffer.first_entry() {
-                            let t0 = *entry.key();
-                            let t1 = t0 + duration;
-                            let wr = WindowRange::new(t0, t1);
+                        for (t0, vs) in &buffer {
+                            let wr = WindowRange::new(*t0, *t0 + duration);
                             if wr.t1 > time {
                                 break;
                             }
-                            let vs = entry.remove();
-                            let data = compute(&vs, wr);
+                            if wr.t1 <= watermark {
+                                continue;
+                            }
+                            let data = compute(vs, wr);
                             tx.send(Event::Data(wr.t1, data.clone())).await?;
                         }
+                        while let Some(entry) = buffer.first_entry() {
+                            if *entry.key() + duration + allowed <= time {
+                                entry.remove();
+                            } else {
+                                break;
+                            }
+                        }
+                        watermark = time;
                         tx.send(Event::Watermark(time)).await?;
+                        late.send(Event::Watermark(time)).await.ok();
                     }
                     Event::Snapshot(i) => {
+                        state.snapshot(i, &(&buffer, watermark));
                         tx.send(Event::Snapshot(i)).await?;
+                        late.send(Event::Snapshot(i)).await.ok();
                     }
                     Event::Sentinel => {
+                        late.send(Event::Sentinel).await.ok();
                         tx.send(Event::Sentinel).await?;
                         break;
                     }
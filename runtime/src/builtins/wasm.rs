@@ -0,0 +1,223 @@
+mod val;
+
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use wasmtime::component::Component;
+use wasmtime::component::ComponentNamedList;
+use wasmtime::component::Func;
+use wasmtime::component::Lift;
+use wasmtime::component::Linker;
+use wasmtime::component::Lower;
+use wasmtime::component::ResourceTable;
+use wasmtime::component::Type;
+use wasmtime::component::TypedFunc;
+use wasmtime::component::Val;
+use wasmtime::Engine;
+use wasmtime::Store;
+use wasmtime_wasi::IoView;
+use wasmtime_wasi::WasiCtx;
+use wasmtime_wasi::WasiCtxBuilder;
+use wasmtime_wasi::WasiView;
+
+use crate::error::Error;
+
+/// The engine which compiles and runs all components of the process.
+fn engine() -> &'static Engine {
+    static ENGINE: OnceLock<Engine> = OnceLock::new();
+    ENGINE.get_or_init(Engine::default)
+}
+
+/// The state of a component instance, which gives it access to WASI.
+pub struct Host {
+    ctx: WasiCtx,
+    table: ResourceTable,
+}
+
+impl IoView for Host {
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+}
+
+impl WasiView for Host {
+    fn ctx(&mut self) -> &mut WasiCtx {
+        &mut self.ctx
+    }
+}
+
+/// A compiled WASM component, whose exported functions can be run by the
+/// `wasm_*` operators of `Stream`. Clones share the compiled code.
+#[derive(Clone)]
+pub struct WasmComponent {
+    component: Component,
+    linker: Arc<Linker<Host>>,
+}
+
+impl WasmComponent {
+    /// Compiles a component from its binary or text format.
+    pub fn new(bytes: impl AsRef<[u8]>) -> Result<Self, Error> {
+        let component = Component::new(engine(), bytes).map_err(Error::custom)?;
+        let mut linker = Linker::new(engine());
+        wasmtime_wasi::add_to_linker_sync(&mut linker).map_err(Error::custom)?;
+        Ok(Self {
+            component,
+            linker: Arc::new(linker),
+        })
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::new(std::fs::read(path)?)
+    }
+
+    /// Instantiates the component, and looks up an exported function with
+    /// parameters `P` and results `R`. Exports of an interface are named
+    /// `interface#function`, e.g. `pkg:component/nexmark#qs`. Fails if the
+    /// export does not exist or has a different type.
+    pub(crate) fn export<P, R>(&self, name: &str) -> Result<Export<P, R>, Error>
+    where
+        P: ComponentNamedList + Lower,
+        R: ComponentNamedList + Lift,
+    {
+        let (mut store, function) = self.instantiate(name)?;
+        let func = function
+            .typed::<P, R>(&mut store)
+            .map_err(|e| Error::custom(format!("Export `{}` has the wrong type: {}", name, e)))?;
+        Ok(Export { store, func })
+    }
+
+    /// Like `export`, but the type of the function is only known at runtime.
+    pub(crate) fn dyn_export(&self, name: &str) -> Result<DynExport, Error> {
+        let (store, func) = self.instantiate(name)?;
+        let params = func
+            .params(&store)
+            .into_vec()
+            .into_iter()
+            .map(|(_, ty)| ty)
+            .collect();
+        let results = func.results(&store).into_vec();
+        Ok(DynExport {
+            store,
+            func,
+            params,
+            results,
+        })
+    }
+
+    fn instantiate(&self, name: &str) -> Result<(Store<Host>, Func), Error> {
+        let host = Host {
+            ctx: WasiCtxBuilder::new().inherit_stdio().build(),
+            table: ResourceTable::new(),
+        };
+        let mut store = Store::new(engine(), host);
+        let instance = self
+            .linker
+            .instantiate(&mut store, &self.component)
+            .map_err(Error::custom)?;
+        let (interface, function) = match name.split_once('#') {
+            Some((interface, function)) => (Some(interface), function),
+            None => (None, name),
+        };
+        let interface = match interface {
+            Some(interface) => Some(
+                instance
+                    .get_export(&mut store, None, interface)
+                    .ok_or_else(|| Error::custom(format!("Export `{}` not found", interface)))?,
+            ),
+            None => None,
+        };
+        let function = instance
+            .get_export(&mut store, interface.as_ref(), function)
+            .and_then(|index| instance.get_func(&mut store, index))
+            .ok_or_else(|| Error::custom(format!("Export `{}` not found", name)))?;
+        Ok((store, function))
+    }
+}
+
+/// A typed function of a component instance.
+pub(crate) struct Export<P, R> {
+    store: Store<Host>,
+    func: TypedFunc<P, R>,
+}
+
+impl<P, R> Export<P, R>
+where
+    P: ComponentNamedList + Lower,
+    R: ComponentNamedList + Lift,
+{
+    /// Calls the function. A trap of the guest is returned as an error.
+    pub(crate) fn call(&mut self, params: P) -> Result<R, Error> {
+        let results = self
+            .func
+            .call(&mut self.store, params)
+            .map_err(Error::custom)?;
+        self.func
+            .post_return(&mut self.store)
+            .map_err(Error::custom)?;
+        Ok(results)
+    }
+}
+
+/// A function of a component instance, whose parameters and results are
+/// converted from and to serde-encoded records by `val`.
+pub(crate) struct DynExport {
+    store: Store<Host>,
+    func: Func,
+    params: Vec<Type>,
+    results: Vec<Type>,
+}
+
+impl DynExport {
+    pub(crate) fn results(&self) -> &[Type] {
+        &self.results
+    }
+
+    /// Calls the function with `input`. A function with one parameter takes
+    /// the whole record. A function with several parameters takes a tuple with
+    /// one element per parameter. Several results are returned as a tuple.
+    pub(crate) fn call<I, O>(&mut self, input: &I) -> Result<O, Error>
+    where
+        I: Serialize,
+        O: DeserializeOwned,
+    {
+        let input = serde_json::to_value(input).map_err(Error::custom)?;
+        let params = match (self.params.as_slice(), &input) {
+            ([], _) => vec![],
+            ([ty], input) => vec![val::to_val(input, ty)?],
+            (tys, Value::Array(inputs)) if tys.len() == inputs.len() => tys
+                .iter()
+                .zip(inputs)
+                .map(|(ty, input)| val::to_val(input, ty))
+                .collect::<Result<_, _>>()?,
+            (tys, _) => {
+                return Err(Error::custom(format!(
+                    "Expected a tuple of {} parameters, found `{}`",
+                    tys.len(),
+                    input
+                )))
+            }
+        };
+        let mut results = vec![Val::Bool(false); self.results.len()];
+        self.func
+            .call(&mut self.store, &params, &mut results)
+            .map_err(Error::custom)?;
+        self.func
+            .post_return(&mut self.store)
+            .map_err(Error::custom)?;
+        let output = match results.len() {
+            0 => Value::Null,
+            1 => val::from_val(results.pop().unwrap())?,
+            _ => Value::Array(
+                results
+                    .into_iter()
+                    .map(val::from_val)
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+        serde_json::from_value(output).map_err(Error::custom)
+    }
+}
@@ -0,0 +1,267 @@
+//! Conversions between serde-encoded records and component values.
+//!
+//! Records are encoded with `serde_json::Value` as an intermediate, and are
+//! matched against the types of an export:
+//! * Struct fields match record fields, e.g. `bid_price` matches `bid-price`.
+//! * Enum variants match variant and enum cases, e.g. `NewBid` matches
+//!   `new-bid`. Unit variants are encoded as strings, and other variants as
+//!   `{ "Variant": payload }`.
+//! * `Option` matches `option`, `Result` matches `result`, and sequences match
+//!   `list` and `tuple`. Flags are encoded as a sequence of their names.
+//!
+//! Resources are not supported.
+
+use std::convert::TryFrom;
+
+use serde_json::Map;
+use serde_json::Number;
+use serde_json::Value;
+use wasmtime::component::Type;
+use wasmtime::component::Val;
+
+use crate::error::Error;
+
+/// Converts `value` to a component value of type `ty`.
+pub(crate) fn to_val(value: &Value, ty: &Type) -> Result<Val, Error> {
+    let mismatch = || Error::custom(format!("Expected {}, found `{}`", describe(ty), value));
+    let val = match ty {
+        Type::Bool => Val::Bool(value.as_bool().ok_or_else(mismatch)?),
+        Type::S8 => Val::S8(int(value).ok_or_else(mismatch)?),
+        Type::U8 => Val::U8(int(value).ok_or_else(mismatch)?),
+        Type::S16 => Val::S16(int(value).ok_or_else(mismatch)?),
+        Type::U16 => Val::U16(int(value).ok_or_else(mismatch)?),
+        Type::S32 => Val::S32(int(value).ok_or_else(mismatch)?),
+        Type::U32 => Val::U32(int(value).ok_or_else(mismatch)?),
+        Type::S64 => Val::S64(int(value).ok_or_else(mismatch)?),
+        Type::U64 => Val::U64(int(value).ok_or_else(mismatch)?),
+        Type::Float32 => Val::Float32(value.as_f64().ok_or_else(mismatch)? as f32),
+        Type::Float64 => Val::Float64(value.as_f64().ok_or_else(mismatch)?),
+        Type::Char => {
+            let s = value.as_str().ok_or_else(mismatch)?;
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Val::Char(c),
+                _ => return Err(mismatch()),
+            }
+        }
+        Type::String => Val::String(value.as_str().ok_or_else(mismatch)?.to_string()),
+        Type::List(list) => {
+            let ty = list.ty();
+            let values = value.as_array().ok_or_else(mismatch)?;
+            Val::List(
+                values
+                    .iter()
+                    .map(|v| to_val(v, &ty))
+                    .collect::<Result<_, _>>()?,
+            )
+        }
+        Type::Record(record) => {
+            let fields = value.as_object().ok_or_else(mismatch)?;
+            Val::Record(
+                record
+                    .fields()
+                    .map(|field| {
+                        let v = fields
+                            .iter()
+                            .find(|(k, _)| same_name(k, field.name))
+                            .map(|(_, v)| v)
+                            .unwrap_or(&Value::Null);
+                        Ok((field.name.to_string(), to_val(v, &field.ty)?))
+                    })
+                    .collect::<Result<_, Error>>()?,
+            )
+        }
+        Type::Tuple(tuple) => {
+            let values = value.as_array().ok_or_else(mismatch)?;
+            if values.len() != tuple.types().len() {
+                return Err(mismatch());
+            }
+            Val::Tuple(
+                values
+                    .iter()
+                    .zip(tuple.types())
+                    .map(|(v, ty)| to_val(v, &ty))
+                    .collect::<Result<_, _>>()?,
+            )
+        }
+        Type::Variant(variant) => {
+            let (name, payload) = tagged(value).ok_or_else(mismatch)?;
+            let case = variant
+                .cases()
+                .find(|case| same_name(name, case.name))
+                .ok_or_else(mismatch)?;
+            let payload = match (&case.ty, payload) {
+                (Some(ty), Some(payload)) => Some(Box::new(to_val(payload, ty)?)),
+                (None, None) => None,
+                _ => return Err(mismatch()),
+            };
+            Val::Variant(case.name.to_string(), payload)
+        }
+        Type::Enum(e) => {
+            let name = value.as_str().ok_or_else(mismatch)?;
+            let name = e
+                .names()
+                .find(|n| same_name(name, n))
+                .ok_or_else(mismatch)?;
+            Val::Enum(name.to_string())
+        }
+        Type::Option(option) => match value {
+            Value::Null => Val::Option(None),
+            value => Val::Option(Some(Box::new(to_val(value, &option.ty())?))),
+        },
+        Type::Result(result) => {
+            let payload = |ty: Option<Type>, v: Option<&Value>| match (ty, v) {
+                (Some(ty), Some(v)) => Ok(Some(Box::new(to_val(v, &ty)?))),
+                (None, None | Some(Value::Null)) => Ok(None),
+                _ => Err(mismatch()),
+            };
+            match tagged(value).ok_or_else(mismatch)? {
+                ("Ok", v) => Val::Result(Ok(payload(result.ok(), v)?)),
+                ("Err", v) => Val::Result(Err(payload(result.err(), v)?)),
+                _ => return Err(mismatch()),
+            }
+        }
+        Type::Flags(flags) => {
+            let values = value.as_array().ok_or_else(mismatch)?;
+            Val::Flags(
+                values
+                    .iter()
+                    .map(|v| {
+                        let name = v.as_str().ok_or_else(mismatch)?;
+                        let name = flags.names().find(|n| same_name(name, n));
+                        Ok(name.ok_or_else(mismatch)?.to_string())
+                    })
+                    .collect::<Result<_, Error>>()?,
+            )
+        }
+        Type::Own(_) | Type::Borrow(_) => return Err(mismatch()),
+    };
+    Ok(val)
+}
+
+/// Converts a component value to the encoding which serde expects of the
+/// corresponding Rust type.
+pub(crate) fn from_val(val: Val) -> Result<Value, Error> {
+    let value = match val {
+        Val::Bool(v) => Value::Bool(v),
+        Val::S8(v) => Value::from(v),
+        Val::U8(v) => Value::from(v),
+        Val::S16(v) => Value::from(v),
+        Val::U16(v) => Value::from(v),
+        Val::S32(v) => Value::from(v),
+        Val::U32(v) => Value::from(v),
+        Val::S64(v) => Value::from(v),
+        Val::U64(v) => Value::from(v),
+        Val::Float32(v) => float(v as f64)?,
+        Val::Float64(v) => float(v)?,
+        Val::Char(v) => Value::String(v.to_string()),
+        Val::String(v) => Value::String(v),
+        Val::List(vs) | Val::Tuple(vs) => {
+            Value::Array(vs.into_iter().map(from_val).collect::<Result<_, _>>()?)
+        }
+        Val::Record(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(k, v)| Ok((k.replace('-', "_"), from_val(v)?)))
+                .collect::<Result<_, Error>>()?,
+        ),
+        Val::Variant(name, None) | Val::Enum(name) => Value::String(camel_case(&name)),
+        Val::Variant(name, Some(v)) => tag(camel_case(&name), from_val(*v)?),
+        Val::Option(None) => Value::Null,
+        Val::Option(Some(v)) => from_val(*v)?,
+        Val::Result(Ok(v)) => tag("Ok".to_string(), from_payload(v)?),
+        Val::Result(Err(v)) => tag("Err".to_string(), from_payload(v)?),
+        Val::Flags(names) => Value::Array(names.into_iter().map(Value::String).collect()),
+        Val::Resource(_) => return Err(Error::custom("Resources are not supported")),
+    };
+    Ok(value)
+}
+
+fn from_payload(val: Option<Box<Val>>) -> Result<Value, Error> {
+    val.map_or(Ok(Value::Null), |v| from_val(*v))
+}
+
+fn int<T: TryFrom<i64> + TryFrom<u64>>(value: &Value) -> Option<T> {
+    match value {
+        Value::Number(n) => match (n.as_u64(), n.as_i64()) {
+            (Some(n), _) => T::try_from(n).ok(),
+            (_, Some(n)) => T::try_from(n).ok(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn float(v: f64) -> Result<Value, Error> {
+    Number::from_f64(v)
+        .map(Value::Number)
+        .ok_or_else(|| Error::custom(format!("Cannot encode {}", v)))
+}
+
+/// Splits an externally tagged enum into its variant and payload.
+fn tagged(value: &Value) -> Option<(&str, Option<&Value>)> {
+    match value {
+        Value::String(name) => Some((name, None)),
+        Value::Object(map) if map.len() == 1 => {
+            map.iter().next().map(|(k, v)| (k.as_str(), Some(v)))
+        }
+        _ => None,
+    }
+}
+
+fn tag(name: String, payload: Value) -> Value {
+    let mut map = Map::new();
+    map.insert(name, payload);
+    Value::Object(map)
+}
+
+/// Compares a Rust name with a WIT name, ignoring case and separators.
+fn same_name(rust: &str, wit: &str) -> bool {
+    let normalize = |s: &str| {
+        s.chars()
+            .filter(|c| *c != '-' && *c != '_')
+            .map(|c| c.to_ascii_lowercase())
+            .collect::<String>()
+    };
+    normalize(rust) == normalize(wit)
+}
+
+/// Converts a WIT name such as `new-bid` to `NewBid`.
+fn camel_case(name: &str) -> String {
+    name.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(c) => c.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn describe(ty: &Type) -> String {
+    match ty {
+        Type::Bool => "bool".to_string(),
+        Type::S8 => "s8".to_string(),
+        Type::U8 => "u8".to_string(),
+        Type::S16 => "s16".to_string(),
+        Type::U16 => "u16".to_string(),
+        Type::S32 => "s32".to_string(),
+        Type::U32 => "u32".to_string(),
+        Type::S64 => "s64".to_string(),
+        Type::U64 => "u64".to_string(),
+        Type::Float32 => "f32".to_string(),
+        Type::Float64 => "f64".to_string(),
+        Type::Char => "char".to_string(),
+        Type::String => "string".to_string(),
+        Type::List(list) => format!("list<{}>", describe(&list.ty())),
+        Type::Record(_) => "record".to_string(),
+        Type::Tuple(_) => "tuple".to_string(),
+        Type::Variant(_) => "variant".to_string(),
+        Type::Enum(_) => "enum".to_string(),
+        Type::Option(option) => format!("option<{}>", describe(&option.ty())),
+        Type::Result(_) => "result".to_string(),
+        Type::Flags(_) => "flags".to_string(),
+        Type::Own(_) | Type::Borrow(_) => "resource".to_string(),
+    }
+}
@@ -0,0 +1,191 @@
+use std::time::Instant;
+
+use crate::builtins::duration::Duration;
+use crate::builtins::time::Time;
+use crate::HashMap;
+
+/// Reads a watermark from a record, if the record carries one.
+pub type Punctuation<T> = Box<dyn FnMut(&T) -> Option<Time> + Send>;
+
+/// How a source derives watermarks from the event times of its records.
+///
+/// A `Duration` converts into a bounded out-of-orderness strategy, so existing
+/// code which passes a slack keeps working.
+pub enum WatermarkStrategy<T> {
+    /// The watermark trails the latest event time by `slack`.
+    BoundedOutOfOrderness { slack: Duration },
+    /// The watermark is read from records. Records for which the function
+    /// returns `None` do not move the watermark.
+    Punctuated {
+        watermark: Punctuation<T>,
+    },
+    /// The watermark of each partition trails its latest event time by
+    /// `slack`, and the source emits the minimum over all partitions.
+    PerPartition {
+        partition: Box<dyn Fn(&T) -> u64 + Send>,
+        slack: Duration,
+    },
+    /// Partitions which have produced no records for `timeout` of processing
+    /// time are idle, and are left out of the minimum. When every partition is
+    /// idle, the watermark advances with processing time.
+    Idleness {
+        strategy: Box<WatermarkStrategy<T>>,
+        timeout: Duration,
+    },
+}
+
+impl<T> From<Duration> for WatermarkStrategy<T> {
+    fn from(slack: Duration) -> Self {
+        Self::bounded_out_of_orderness(slack)
+    }
+}
+
+impl<T> std::fmt::Debug for WatermarkStrategy<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::BoundedOutOfOrderness { slack } => write!(f, "BoundedOutOfOrderness({})", slack),
+            Self::Punctuated { .. } => write!(f, "Punctuated"),
+            Self::PerPartition { slack, .. } => write!(f, "PerPartition({})", slack),
+            Self::Idleness { strategy, timeout } => {
+                write!(f, "Idleness({:?}, {})", strategy, timeout)
+            }
+        }
+    }
+}
+
+impl<T> WatermarkStrategy<T> {
+    pub fn bounded_out_of_orderness(slack: Duration) -> Self {
+        Self::BoundedOutOfOrderness { slack }
+    }
+
+    pub fn punctuated(watermark: impl FnMut(&T) -> Option<Time> + Send + 'static) -> Self {
+        Self::Punctuated {
+            watermark: Box::new(watermark),
+        }
+    }
+
+    pub fn per_partition(partition: impl Fn(&T) -> u64 + Send + 'static, slack: Duration) -> Self {
+        Self::PerPartition {
+            partition: Box::new(partition),
+            slack,
+        }
+    }
+
+    pub fn with_idleness(self, timeout: Duration) -> Self {
+        Self::Idleness {
+            strategy: Box::new(self),
+            timeout,
+        }
+    }
+}
+
+/// The part of a `Watermarks` which is stored in snapshots: the latest time
+/// of each partition and the last emitted watermark.
+pub(crate) type WatermarkState = (HashMap<u64, Time>, Time);
+
+/// Tracks the records of a source and decides when to emit watermarks.
+pub(crate) struct Watermarks<T> {
+    strategy: WatermarkStrategy<T>,
+    timeout: Option<std::time::Duration>,
+    latest: HashMap<u64, Time>,
+    activity: HashMap<u64, Instant>,
+    watermark: Time,
+}
+
+impl<T> Watermarks<T> {
+    pub(crate) fn new(mut strategy: WatermarkStrategy<T>) -> Self {
+        let mut timeout = None;
+        while let WatermarkStrategy::Idleness {
+            strategy: inner,
+            timeout: t,
+        } = strategy
+        {
+            timeout = timeout.or(Some(t.to_std()));
+            strategy = *inner;
+        }
+        Self {
+            strategy,
+            timeout,
+            latest: HashMap::default(),
+            activity: HashMap::default(),
+            watermark: Time::zero(),
+        }
+    }
+
+    /// The last emitted watermark.
+    pub(crate) fn watermark(&self) -> Time {
+        self.watermark
+    }
+
+    pub(crate) fn state(&self) -> WatermarkState {
+        (self.latest.clone(), self.watermark)
+    }
+
+    pub(crate) fn restore(&mut self, (latest, watermark): WatermarkState) {
+        let now = Instant::now();
+        self.activity = latest.keys().map(|p| (*p, now)).collect();
+        self.latest = latest;
+        self.watermark = watermark;
+    }
+
+    /// Records an event. Returns a watermark to emit right away, which only
+    /// happens for punctuated watermarks.
+    pub(crate) fn on_event(&mut self, data: &T, time: Time) -> Option<Time> {
+        let (partition, time) = match &mut self.strategy {
+            WatermarkStrategy::BoundedOutOfOrderness { .. } => (0, Some(time)),
+            WatermarkStrategy::Punctuated { watermark } => (0, watermark(data)),
+            WatermarkStrategy::PerPartition { partition, .. } => (partition(data), Some(time)),
+            WatermarkStrategy::Idleness { .. } => unreachable!(),
+        };
+        self.activity.insert(partition, Instant::now());
+        if let Some(time) = time {
+            let latest = self.latest.entry(partition).or_insert(time);
+            if time > *latest {
+                *latest = time;
+            }
+        }
+        match self.strategy {
+            WatermarkStrategy::Punctuated { .. } => self.advance(),
+            _ => None,
+        }
+    }
+
+    /// Returns a watermark to emit periodically, if it has advanced since the
+    /// last one.
+    pub(crate) fn on_periodic(&mut self) -> Option<Time> {
+        self.advance()
+    }
+
+    fn advance(&mut self) -> Option<Time> {
+        let slack = match &self.strategy {
+            WatermarkStrategy::BoundedOutOfOrderness { slack } => *slack,
+            WatermarkStrategy::PerPartition { slack, .. } => *slack,
+            _ => Duration::zero(),
+        };
+        let now = Instant::now();
+        let idle = |p: &u64| match (self.timeout, self.activity.get(p)) {
+            (Some(timeout), Some(activity)) => now.duration_since(*activity) >= timeout,
+            _ => false,
+        };
+        let active = self
+            .latest
+            .iter()
+            .filter(|(p, _)| !idle(p))
+            .map(|(_, t)| *t - slack)
+            .min();
+        let watermark = match active {
+            Some(watermark) => watermark,
+            None => {
+                let latest = self.latest.values().max()?;
+                let activity = self.activity.values().max()?;
+                Time(latest.0 + now.duration_since(*activity)) - slack
+            }
+        };
+        if watermark > self.watermark {
+            self.watermark = watermark;
+            Some(watermark)
+        } else {
+            None
+        }
+    }
+}
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 
 use serde::Deserialize;
 use serde::Serialize;
+use url::Url;
 
 use crate::traits::DeepClone;
 
@@ -11,6 +12,7 @@ use crate::traits::DeepClone;
 pub enum Writer {
     Stdout,
     File { path: PathBuf },
+    Http { url: Url },
     Tcp { addr: SocketAddr },
     Kafka { addr: SocketAddr, topic: String },
 }
@@ -26,7 +28,7 @@ impl std::fmt::Display for Writer {
         match self {
             Writer::Stdout => write!(f, "stdout()"),
             Writer::File { path } => write!(f, "file(path={})", path.display()),
-            // Writer::Http { url } => write!(f, "http(url={url})"),
+            Writer::Http { url } => write!(f, "http(url={url})"),
             Writer::Tcp { addr } => write!(f, "tcp(addr={addr})"),
             Writer::Kafka { addr, topic } => write!(f, "kafka(addr={addr}, topic={topic})"),
         }
@@ -37,8 +39,11 @@ impl Writer {
     pub fn stdout() -> Self {
         Self::Stdout
     }
-    pub fn file(path: PathBuf) -> Self {
-        Self::File { path }
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self::File { path: path.into() }
+    }
+    pub fn http(url: Url) -> Self {
+        Self::Http { url }
     }
     pub fn tcp(addr: SocketAddr) -> Self {
         Self::Tcp { addr }
@@ -0,0 +1,64 @@
+/// An error which terminates an operator.
+///
+/// `Closed` is the normal way for an operator to stop once its output has been
+/// dropped, and is not reported. Any other error cancels the dataflow, and is
+/// returned by the runner.
+#[derive(Debug)]
+pub enum Error {
+    /// The downstream operator has terminated.
+    Closed,
+    /// Reading or writing an external resource failed.
+    Io(std::io::Error),
+    /// An operator task panicked.
+    Panic(String),
+    /// A user function failed.
+    Custom(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl Error {
+    pub fn custom(e: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self::Custom(e.into())
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Error::Closed => write!(f, "Channel closed"),
+            Error::Io(e) => write!(f, "IO error: {}", e),
+            Error::Panic(msg) => write!(f, "Task panicked: {}", msg),
+            Error::Custom(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Custom(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<tokio::task::JoinError> for Error {
+    fn from(e: tokio::task::JoinError) -> Self {
+        match e.try_into_panic() {
+            Ok(payload) => match payload.downcast::<String>() {
+                Ok(msg) => Self::Panic(*msg),
+                Err(payload) => match payload.downcast::<&str>() {
+                    Ok(msg) => Self::Panic(msg.to_string()),
+                    Err(_) => Self::Panic(String::new()),
+                },
+            },
+            Err(e) => Self::custom(e),
+        }
+    }
+}
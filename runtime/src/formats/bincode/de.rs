@@ -0,0 +1,43 @@
+use ::bincode::Options;
+
+use crate::formats::unframe;
+use crate::formats::Decode;
+
+#[derive(Default)]
+pub struct Reader {}
+
+impl Reader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Decode for Reader {
+    type Error = ::bincode::Error;
+
+    fn decode<'de, T>(&mut self, input: &'de [u8]) -> Result<T, Self::Error>
+    where
+        T: serde::Deserialize<'de>,
+    {
+        ::bincode::deserialize(unframe::<<Self as Decode>::Error>(input)?)
+    }
+
+    fn decode_dyn<'de, T, Tag>(
+        &mut self,
+        input: &'de [u8],
+        tag: Tag,
+    ) -> Result<T, <Self as Decode>::Error>
+    where
+        Tag: Clone + serde::de::DeserializeSeed<'de, Value = T>,
+    {
+        // The same options as `bincode::deserialize`.
+        let options = ::bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .allow_trailing_bytes();
+        let mut deserializer = ::bincode::Deserializer::from_slice(
+            unframe::<<Self as Decode>::Error>(input)?,
+            options,
+        );
+        serde::de::DeserializeSeed::deserialize(tag, &mut deserializer)
+    }
+}
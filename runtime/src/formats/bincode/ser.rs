@@ -1,3 +1,4 @@
+use crate::formats::frame;
 use crate::formats::Encode;
 
 #[derive(Default)]
@@ -10,18 +11,16 @@ impl Writer {
 }
 
 impl Encode for Writer {
-    type Error = serde_json::Error;
+    type Error = ::bincode::Error;
 
     fn encode<T>(&mut self, input: &T, output: &mut Vec<u8>) -> Result<usize, Self::Error>
     where
         T: serde::Serialize + ?Sized,
     {
-        let mut serializer = serde_json::Serializer::new(output);
-        input.serialize(&mut serializer)?;
-        Ok(1)
+        frame(output, |output| ::bincode::serialize_into(output, input))
     }
 
     fn content_type(&self) -> &'static str {
-        "application/json"
+        "application/octet-stream"
     }
 }
@@ -0,0 +1,153 @@
+use std::convert::TryFrom;
+
+use ciborium::value::Value;
+use serde::de::value::MapAccessDeserializer;
+use serde::de::value::MapDeserializer;
+use serde::de::value::SeqDeserializer;
+use serde::de::IntoDeserializer;
+use serde::de::Visitor;
+
+use crate::formats::unframe;
+use crate::formats::Decode;
+
+type Error = ciborium::de::Error<std::io::Error>;
+
+#[derive(Default)]
+pub struct Reader {}
+
+impl Reader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn value(input: &[u8]) -> Result<Deserializer, Error> {
+        ciborium::de::from_reader(unframe::<Error>(input)?).map(Deserializer)
+    }
+}
+
+/// ciborium only deserializes owned types, and only without a seed, so
+/// records are first decoded into a `Value`.
+impl Decode for Reader {
+    type Error = Error;
+
+    fn decode<'de, T>(&mut self, input: &'de [u8]) -> Result<T, Self::Error>
+    where
+        T: serde::Deserialize<'de>,
+    {
+        T::deserialize(Self::value(input)?)
+    }
+
+    fn decode_dyn<'de, T, Tag>(
+        &mut self,
+        input: &'de [u8],
+        tag: Tag,
+    ) -> Result<T, <Self as Decode>::Error>
+    where
+        Tag: Clone + serde::de::DeserializeSeed<'de, Value = T>,
+    {
+        tag.deserialize(Self::value(input)?)
+    }
+}
+
+const BIGPOS: u64 = 2;
+const BIGNEG: u64 = 3;
+
+/// Integers which do not fit into 64 bits are tagged big-endian bytes.
+fn bignum(bytes: &[u8]) -> Result<u128, Error> {
+    if bytes.len() > 16 {
+        return Err(serde::de::Error::custom("Integer out of range"));
+    }
+    Ok(bytes.iter().fold(0, |n, b| n << 8 | *b as u128))
+}
+
+/// Deserializes a decoded `Value` in the data model of ciborium's serializer.
+struct Deserializer(Value);
+
+impl<'de> IntoDeserializer<'de, Error> for Deserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> serde::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Integer(i) => match (u64::try_from(i), i64::try_from(i)) {
+                (Ok(u), _) => visitor.visit_u64(u),
+                (_, Ok(i)) => visitor.visit_i64(i),
+                _ => visitor.visit_i128(i128::from(i)),
+            },
+            Value::Bytes(b) => visitor.visit_byte_buf(b),
+            Value::Float(f) => visitor.visit_f64(f),
+            Value::Text(s) => visitor.visit_string(s),
+            Value::Bool(b) => visitor.visit_bool(b),
+            Value::Null => visitor.visit_unit(),
+            Value::Tag(tag, v) => match (tag, *v) {
+                (BIGPOS, Value::Bytes(b)) => visitor.visit_u128(bignum(&b)?),
+                (BIGNEG, Value::Bytes(b)) => match i128::try_from(bignum(&b)?) {
+                    Ok(n) => visitor.visit_i128(-1 - n),
+                    Err(_) => Err(serde::de::Error::custom("Integer out of range")),
+                },
+                (_, v) => Deserializer(v).deserialize_any(visitor),
+            },
+            Value::Array(a) => {
+                let mut seq = SeqDeserializer::new(a.into_iter().map(Deserializer));
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Value::Map(m) => {
+                let entries = m
+                    .into_iter()
+                    .map(|(k, v)| (Deserializer(k), Deserializer(v)));
+                let mut map = MapDeserializer::new(entries);
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+            _ => Err(serde::de::Error::custom("Unsupported CBOR value")),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    /// Unit variants are encoded by name, and other variants as a map from
+    /// their name to their fields.
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Text(s) => visitor.visit_enum(s.into_deserializer()),
+            Value::Map(m) if m.len() == 1 => {
+                let entries = m
+                    .into_iter()
+                    .map(|(k, v)| (Deserializer(k), Deserializer(v)));
+                visitor.visit_enum(MapAccessDeserializer::new(MapDeserializer::new(entries)))
+            }
+            Value::Tag(_, v) => Deserializer(*v).deserialize_enum(name, variants, visitor),
+            _ => Err(serde::de::Error::custom("Expected an enum")),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple tuple_struct
+        map struct identifier ignored_any
+    }
+}
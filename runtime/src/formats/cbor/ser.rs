@@ -0,0 +1,26 @@
+use crate::formats::frame;
+use crate::formats::Encode;
+
+#[derive(Default)]
+pub struct Writer {}
+
+impl Writer {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Encode for Writer {
+    type Error = ciborium::ser::Error<std::io::Error>;
+
+    fn encode<T>(&mut self, input: &T, output: &mut Vec<u8>) -> Result<usize, Self::Error>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        frame(output, |output| ciborium::ser::into_writer(input, output))
+    }
+
+    fn content_type(&self) -> &'static str {
+        "application/cbor"
+    }
+}
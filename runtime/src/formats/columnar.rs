@@ -0,0 +1,101 @@
+//! Readers for Arrow IPC and Parquet files. Unlike the other formats, these
+//! are not framed record by record, so they are read from files through
+//! `Stream::from_columnar` rather than through a `Reader`.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Seek;
+use std::path::Path;
+
+use arrow::array::RecordBatch;
+use arrow::error::ArrowError;
+use arrow::ipc::reader::FileReader;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReader;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use serde::de::DeserializeOwned;
+
+use crate::error::Error;
+
+const ARROW_MAGIC: &[u8; 6] = b"ARROW1";
+const PARQUET_MAGIC: &[u8; 4] = b"PAR1";
+
+/// Record batches of an Arrow IPC or Parquet file.
+pub enum Batches {
+    Arrow(FileReader<BufReader<File>>),
+    Parquet(ParquetRecordBatchReader),
+}
+
+impl Batches {
+    /// Opens a file, whose format is detected from its magic bytes.
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let mut file = File::open(path)?;
+        let mut magic = [0; ARROW_MAGIC.len()];
+        let n = file.read(&mut magic)?;
+        file.rewind()?;
+        if magic[..n].starts_with(ARROW_MAGIC) {
+            FileReader::try_new(BufReader::new(file), None)
+                .map(Batches::Arrow)
+                .map_err(std::io::Error::other)
+        } else if magic[..n].starts_with(PARQUET_MAGIC) {
+            ParquetRecordBatchReaderBuilder::try_new(file)
+                .and_then(|builder| builder.build())
+                .map(Batches::Parquet)
+                .map_err(std::io::Error::other)
+        } else {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Expected an Arrow IPC or Parquet file",
+            ))
+        }
+    }
+}
+
+impl Iterator for Batches {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Batches::Arrow(reader) => reader.next(),
+            Batches::Parquet(reader) => reader.next(),
+        }
+    }
+}
+
+/// Returns the record batches of a file, deserialized into rows. A batch
+/// which cannot be read or does not match `T` is an error.
+pub fn batches<T: DeserializeOwned>(
+    path: &Path,
+) -> std::io::Result<impl Iterator<Item = Result<Vec<T>, Error>> + Send> {
+    Ok(Batches::open(path)?.map(|batch| {
+        let batch = batch.map_err(Error::custom)?;
+        serde_arrow::from_record_batch(&batch).map_err(Error::custom)
+    }))
+}
+
+/// Returns the records of a file. Errors are reported like by `batches`.
+pub fn records<T: DeserializeOwned + Send>(
+    path: &Path,
+) -> std::io::Result<impl Iterator<Item = Result<T, Error>> + Send> {
+    Ok(batches(path)?.flat_map(|batch| {
+        let (rows, error) = match batch {
+            Ok(rows) => (rows, None),
+            Err(e) => (Vec::new(), Some(e)),
+        };
+        rows.into_iter().map(Ok).chain(error.map(Err))
+    }))
+}
+
+/// Returns the records of `<dir>/<name>.parquet` or `<dir>/<name>.arrow`, as
+/// written by `data-generator --format parquet|arrow`, or `None` if neither
+/// exists. Errors are reported like by `batches`.
+pub fn find<T: DeserializeOwned + Send>(
+    dir: &Path,
+    name: &str,
+) -> Option<std::io::Result<impl Iterator<Item = Result<T, Error>> + Send>> {
+    ["parquet", "arrow"]
+        .iter()
+        .map(|extension| dir.join(name).with_extension(extension))
+        .find(|path| path.exists())
+        .map(|path| records(&path))
+}
@@ -38,9 +38,9 @@ impl<const N: usize> Decode for Reader<N> {
         T::deserialize(&mut deserializer)
     }
 
-    fn decode_dyn<'de, T, Tag: Clone>(&mut self, input: &'de [u8], tag: Tag) -> Result<T>
+    fn decode_dyn<'de, T, Tag>(&mut self, input: &'de [u8], tag: Tag) -> Result<T>
     where
-        Tag: serde::de::DeserializeSeed<'de, Value = T>,
+        Tag: Clone + serde::de::DeserializeSeed<'de, Value = T>,
     {
         let mut deserializer = Deserializer::new(self, input);
         serde::de::DeserializeSeed::deserialize(tag, &mut deserializer)
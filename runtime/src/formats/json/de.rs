@@ -24,13 +24,13 @@ impl Decode for Reader {
         Ok(value)
     }
 
-    fn decode_dyn<'de, T, Tag: Clone>(
+    fn decode_dyn<'de, T, Tag>(
         &mut self,
         input: &'de [u8],
         tag: Tag,
     ) -> Result<T, <Self as Decode>::Error>
     where
-        Tag: serde::de::DeserializeSeed<'de, Value = T>,
+        Tag: Clone + serde::de::DeserializeSeed<'de, Value = T>,
     {
         let mut deserializer = Deserializer::from_slice(input);
         let value = serde::de::DeserializeSeed::deserialize(tag, &mut deserializer)?;
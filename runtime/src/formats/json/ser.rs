@@ -12,13 +12,16 @@ impl Writer {
 impl Encode for Writer {
     type Error = serde_json::Error;
 
+    /// Writes the value to the start of `output`, terminated by a newline.
     fn encode<T>(&mut self, input: &T, output: &mut Vec<u8>) -> Result<usize, Self::Error>
     where
         T: serde::Serialize + ?Sized,
     {
-        let mut serializer = serde_json::Serializer::new(output);
+        output.clear();
+        let mut serializer = serde_json::Serializer::new(&mut *output);
         input.serialize(&mut serializer)?;
-        Ok(1)
+        output.push(b'\n');
+        Ok(output.len())
     }
 
     fn content_type(&self) -> &'static str {
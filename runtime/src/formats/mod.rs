@@ -1,6 +1,8 @@
 use serde::de::DeserializeSeed;
 use serde::Deserialize;
 use serde::Serialize;
+use tokio_util::bytes::Buf;
+use tokio_util::bytes::BytesMut;
 
 pub mod csv {
     pub mod de;
@@ -12,6 +14,24 @@ pub mod json {
     pub mod ser;
 }
 
+pub mod msgpack {
+    pub mod de;
+    pub mod ser;
+}
+
+pub mod cbor {
+    pub mod de;
+    pub mod ser;
+}
+
+pub mod bincode {
+    pub mod de;
+    pub mod ser;
+}
+
+#[cfg(feature = "columnar")]
+pub mod columnar;
+
 pub trait Decode {
     type Error: std::error::Error + Send;
     fn decode<'de, T>(&mut self, input: &'de [u8]) -> Result<T, Self::Error>
@@ -34,3 +54,96 @@ pub trait Encode {
         T: Serialize;
     fn content_type(&self) -> &'static str;
 }
+
+/// How records are delimited in a stream of bytes. Encoders write, and
+/// decoders read, one complete frame per record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Framing {
+    /// Each record is terminated by a newline.
+    Newline,
+    /// Each record is prefixed by its length as a big-endian `u32`, since
+    /// binary payloads may contain newlines.
+    LengthPrefixed,
+    /// Each record is a JSON value, which may span several lines. Values are
+    /// separated by whitespace. Invalid input is skipped up to the next newline.
+    Json,
+}
+
+const PREFIX: usize = std::mem::size_of::<u32>();
+
+impl Framing {
+    /// Returns the length of the first complete frame in `input`.
+    pub fn split(self, input: &[u8]) -> Option<usize> {
+        match self {
+            Framing::Newline => input.iter().position(|c| *c == b'\n').map(|i| i + 1),
+            Framing::LengthPrefixed => {
+                let n = PREFIX + input.get(..PREFIX)?.get_u32() as usize;
+                (input.len() >= n).then_some(n)
+            }
+            Framing::Json => {
+                let mut values = serde_json::Deserializer::from_slice(input)
+                    .into_iter::<serde::de::IgnoredAny>();
+                match values.next()? {
+                    Ok(_) => Some(values.byte_offset()),
+                    Err(e) if e.is_eof() => None,
+                    Err(_) => Framing::Newline.split(input),
+                }
+            }
+        }
+    }
+}
+
+/// Frames are split off the front of a buffer, so the same framing can be
+/// used by `FramedRead` and by sources which receive whole messages.
+impl tokio_util::codec::Decoder for Framing {
+    type Item = BytesMut;
+    type Error = std::io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<BytesMut>, Self::Error> {
+        Ok(self.split(src).map(|n| src.split_to(n)))
+    }
+
+    /// A trailing record without a newline is terminated, but a truncated
+    /// length-prefixed or JSON record is an error.
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<BytesMut>, Self::Error> {
+        if let Some(frame) = self.decode(src)? {
+            return Ok(Some(frame));
+        }
+        match self {
+            _ if src.is_empty() => Ok(None),
+            Framing::Newline => {
+                src.extend_from_slice(b"\n");
+                Ok(Some(src.split()))
+            }
+            Framing::Json if src.trim_ascii().is_empty() => {
+                src.clear();
+                Ok(None)
+            }
+            Framing::LengthPrefixed | Framing::Json => {
+                Err(std::io::ErrorKind::UnexpectedEof.into())
+            }
+        }
+    }
+}
+
+/// Writes a length-prefixed frame to the start of `output`, whose payload is
+/// written by `f`. Returns the length of the frame.
+pub(crate) fn frame<E>(
+    output: &mut Vec<u8>,
+    f: impl FnOnce(&mut Vec<u8>) -> Result<(), E>,
+) -> Result<usize, E> {
+    output.clear();
+    output.extend_from_slice(&[0; PREFIX]);
+    f(output)?;
+    let n = (output.len() - PREFIX) as u32;
+    output[..PREFIX].copy_from_slice(&n.to_be_bytes());
+    Ok(output.len())
+}
+
+/// Returns the payload of a length-prefixed frame.
+pub(crate) fn unframe<E: serde::de::Error>(input: &[u8]) -> Result<&[u8], E> {
+    match Framing::LengthPrefixed.split(input) {
+        Some(n) if n == input.len() => Ok(&input[PREFIX..]),
+        _ => Err(E::custom("Invalid length prefix")),
+    }
+}
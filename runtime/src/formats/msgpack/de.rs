@@ -0,0 +1,35 @@
+use crate::formats::unframe;
+use crate::formats::Decode;
+
+#[derive(Default)]
+pub struct Reader {}
+
+impl Reader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Decode for Reader {
+    type Error = rmp_serde::decode::Error;
+
+    fn decode<'de, T>(&mut self, input: &'de [u8]) -> Result<T, Self::Error>
+    where
+        T: serde::Deserialize<'de>,
+    {
+        rmp_serde::from_slice(unframe::<<Self as Decode>::Error>(input)?)
+    }
+
+    fn decode_dyn<'de, T, Tag>(
+        &mut self,
+        input: &'de [u8],
+        tag: Tag,
+    ) -> Result<T, <Self as Decode>::Error>
+    where
+        Tag: Clone + serde::de::DeserializeSeed<'de, Value = T>,
+    {
+        let mut deserializer =
+            rmp_serde::Deserializer::from_read_ref(unframe::<<Self as Decode>::Error>(input)?);
+        serde::de::DeserializeSeed::deserialize(tag, &mut deserializer)
+    }
+}
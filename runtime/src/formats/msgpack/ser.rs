@@ -0,0 +1,26 @@
+use crate::formats::frame;
+use crate::formats::Encode;
+
+#[derive(Default)]
+pub struct Writer {}
+
+impl Writer {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Encode for Writer {
+    type Error = rmp_serde::encode::Error;
+
+    fn encode<T>(&mut self, input: &T, output: &mut Vec<u8>) -> Result<usize, Self::Error>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        frame(output, |output| rmp_serde::encode::write(output, input))
+    }
+
+    fn content_type(&self) -> &'static str {
+        "application/msgpack"
+    }
+}
@@ -0,0 +1,24 @@
+//! Byte IO of the sources and sinks which read from stdin or files, and write
+//! to stdout or files. The backend is selected by feature:
+//!
+//! * By default, reads and writes block the thread of the operator, which has
+//!   the least overhead when a pipeline runs on a dedicated thread.
+//! * With `io-tokio`, reads and writes are asynchronous, so that they do not
+//!   stall other operators on the same thread.
+//!
+//! Both backends provide `Input` and `Output` with the same interface, so all
+//! operators are shared between them.
+
+#[cfg(not(feature = "io-tokio"))]
+mod std_io;
+#[cfg(not(feature = "io-tokio"))]
+pub(crate) use std_io::Input;
+#[cfg(not(feature = "io-tokio"))]
+pub(crate) use std_io::Output;
+
+#[cfg(feature = "io-tokio")]
+mod tokio_io;
+#[cfg(feature = "io-tokio")]
+pub(crate) use tokio_io::Input;
+#[cfg(feature = "io-tokio")]
+pub(crate) use tokio_io::Output;
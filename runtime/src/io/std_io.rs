@@ -0,0 +1,54 @@
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+
+pub(crate) struct Input(Box<dyn Read + Send>);
+
+impl Input {
+    pub(crate) fn stdin() -> Self {
+        Self(Box::new(std::io::stdin()))
+    }
+
+    /// Opens a file, and skips its first `offset` bytes.
+    pub(crate) async fn open_at(path: &Path, offset: u64) -> std::io::Result<Self> {
+        let mut file = std::fs::File::open(path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        Ok(Self(Box::new(file)))
+    }
+
+    pub(crate) async fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+pub(crate) struct Output(BufWriter<Box<dyn Write + Send>>);
+
+impl Output {
+    pub(crate) fn stdout() -> Self {
+        Self(BufWriter::new(Box::new(std::io::stdout())))
+    }
+
+    /// Opens a file, creating it if needed, and truncates it to its first
+    /// `offset` bytes, after which it is written.
+    pub(crate) async fn create_at(path: &Path, offset: u64) -> std::io::Result<Self> {
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        file.set_len(offset)?;
+        file.seek(SeekFrom::Start(offset))?;
+        Ok(Self(BufWriter::new(Box::new(file))))
+    }
+
+    pub(crate) async fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.0.write_all(buf)
+    }
+
+    pub(crate) async fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()
+    }
+}
@@ -0,0 +1,58 @@
+use std::io::SeekFrom;
+use std::path::Path;
+
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufWriter;
+
+pub(crate) struct Input(Box<dyn AsyncRead + Send + Unpin>);
+
+impl Input {
+    pub(crate) fn stdin() -> Self {
+        Self(Box::new(tokio::io::stdin()))
+    }
+
+    /// Opens a file, and skips its first `offset` bytes.
+    pub(crate) async fn open_at(path: &Path, offset: u64) -> std::io::Result<Self> {
+        let mut file = tokio::fs::File::open(path).await?;
+        file.seek(SeekFrom::Start(offset)).await?;
+        Ok(Self(Box::new(file)))
+    }
+
+    pub(crate) async fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf).await
+    }
+}
+
+pub(crate) struct Output(BufWriter<Box<dyn AsyncWrite + Send + Unpin>>);
+
+impl Output {
+    pub(crate) fn stdout() -> Self {
+        Self(BufWriter::new(Box::new(tokio::io::stdout())))
+    }
+
+    /// Opens a file, creating it if needed, and truncates it to its first
+    /// `offset` bytes, after which it is written.
+    pub(crate) async fn create_at(path: &Path, offset: u64) -> std::io::Result<Self> {
+        let mut file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .await?;
+        file.set_len(offset).await?;
+        file.seek(SeekFrom::Start(offset)).await?;
+        Ok(Self(BufWriter::new(Box::new(file))))
+    }
+
+    pub(crate) async fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.0.write_all(buf).await
+    }
+
+    pub(crate) async fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush().await
+    }
+}
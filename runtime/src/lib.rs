@@ -1,7 +1,9 @@
 pub mod builtins;
+pub mod error;
 pub mod formats;
+pub(crate) mod io;
 pub mod runner;
-// pub mod state;
+pub mod state;
 pub mod traits;
 // pub mod logging;
 
@@ -53,18 +55,28 @@ pub mod prelude {
     pub use crate::builtins::format::Format;
     pub use crate::builtins::window::Window;
     pub use crate::builtins::keyed_stream::KeyedStream;
+    pub use crate::builtins::lateness::FireOnceLateness;
+    pub use crate::builtins::lateness::Lateness;
     pub use crate::builtins::reader::Reader;
     pub use crate::builtins::stream::Stream;
     pub use crate::builtins::time::Time;
+    #[cfg(feature = "wasm")]
+    pub use crate::builtins::wasm::WasmComponent;
+    pub use crate::builtins::watermark::WatermarkStrategy;
     pub use crate::builtins::writer::Writer;
+    pub use crate::error::Error;
     pub use crate::traits::Data;
     pub use crate::traits::DeepClone;
 
     pub use crate::runner::context::Context;
+    pub use crate::state::Checkpoint;
     pub use crate::runner::current_thread::CurrentThreadRunner;
     pub use crate::runner::data_parallel::DataParallelRunner;
+    pub use crate::runner::pinning::Placement;
     pub use crate::runner::task_parallel::TaskParallelRunner;
 
+    pub use std::path::Path;
+
     pub use serde;
     pub use tokio;
 
@@ -1,17 +1,30 @@
 use std::future::Future;
 
+use tokio_util::sync::CancellationToken;
+
 use crate::builtins::keyed_stream::KeyedCollector;
 use crate::builtins::keyed_stream::KeyedStream;
 use crate::builtins::stream::Collector;
-use crate::builtins::stream::SendError;
 use crate::builtins::stream::Stream;
+use crate::error::Error;
+use crate::runner::exchange::Endpoints;
+use crate::runner::exchange::Exchange;
+use crate::state::Checkpoint;
+use crate::state::Checkpointer;
+use crate::state::OperatorState;
 use crate::traits::Data;
 
 pub struct Context {
-    join_set: tokio::task::JoinSet<()>,
+    join_set: tokio::task::JoinSet<Result<(), Error>>,
     local_set: Option<tokio::task::LocalSet>,
     tx: tokio::sync::broadcast::Sender<()>,
     rx: tokio::sync::broadcast::Receiver<()>,
+    checkpointer: Option<Checkpointer>,
+    operators: usize,
+    sinks: usize,
+    exchange: Option<Exchange>,
+    exchanges: usize,
+    cancel: CancellationToken,
 }
 
 impl Default for Context {
@@ -22,6 +35,12 @@ impl Default for Context {
             local_set: None,
             tx,
             rx,
+            checkpointer: None,
+            operators: 0,
+            sinks: 0,
+            exchange: None,
+            exchanges: 0,
+            cancel: CancellationToken::new(),
         }
     }
 }
@@ -39,8 +58,38 @@ impl Context {
         Self::default()
     }
 
+    /// Fails if the checkpoint restores a snapshot which did not complete.
+    pub fn with_checkpoint(checkpoint: Checkpoint) -> Result<Self, Error> {
+        Ok(Self::with_checkpointer(Checkpointer::new(checkpoint)?))
+    }
+
+    pub(crate) fn with_checkpointer(checkpointer: Checkpointer) -> Self {
+        Self {
+            checkpointer: Some(checkpointer),
+            ..Self::default()
+        }
+    }
+
+    /// Connects the context to the other workers of a data-parallel runner.
+    pub(crate) fn with_exchange(self, exchange: Exchange) -> Self {
+        Self {
+            exchange: Some(exchange),
+            ..self
+        }
+    }
+
+    /// Shares cancellation with other contexts, so that an error in one of
+    /// them cancels all of them.
+    pub(crate) fn with_cancellation(self, cancel: CancellationToken) -> Self {
+        Self { cancel, ..self }
+    }
+
     pub async fn run_local(f: impl FnOnce(&mut Context)) -> Self {
-        let mut ctx = Self::new();
+        Self::new().local(f).await
+    }
+
+    pub(crate) async fn local(self, f: impl FnOnce(&mut Context)) -> Self {
+        let mut ctx = self;
         let local_set = tokio::task::LocalSet::new();
         local_set.run_until(async { f(&mut ctx) }).await;
         ctx.local_set = Some(local_set);
@@ -48,37 +97,101 @@ impl Context {
     }
 
     pub fn run(f: impl FnOnce(&mut Context) + Send + 'static) -> Self {
-        let mut ctx = Self::new();
-        f(&mut ctx);
-        ctx
+        Self::new().build(f)
+    }
+
+    pub(crate) fn build(mut self, f: impl FnOnce(&mut Context) + Send + 'static) -> Self {
+        f(&mut self);
+        self
     }
 
-    pub async fn await_termination(mut self) {
+    /// Starts the dataflow and waits until all operators have terminated.
+    /// If an operator fails or panics, the remaining operators are cancelled
+    /// and the first error is returned.
+    pub async fn await_termination(mut self) -> Result<(), Error> {
+        if let Some(checkpointer) = &self.checkpointer {
+            checkpointer.expect_sinks(self.sinks);
+        }
         self.tx.send(()).unwrap();
-        while let Some(result) = self.join_set.join_next().await {
-            result.expect("Task should not panic.");
+        let mut error = None;
+        let mut cancelled = false;
+        loop {
+            let result = tokio::select! {
+                result = self.join_set.join_next() => match result {
+                    Some(result) => result,
+                    None => break,
+                },
+                _ = self.cancel.cancelled(), if !cancelled => {
+                    cancelled = true;
+                    self.join_set.abort_all();
+                    continue;
+                }
+            };
+            let e = match result {
+                Ok(Ok(())) => continue,
+                Err(e) if e.is_cancelled() => continue,
+                Ok(Err(e)) => e,
+                Err(e) => Error::from(e),
+            };
+            tracing::info!("Operator failed: {}", e);
+            if error.is_none() {
+                error = Some(e);
+                self.cancel.cancel();
+            }
         }
         if let Some(local_set) = self.local_set {
             local_set.await;
         }
+        error.map_or(Ok(()), Err)
     }
 
     pub fn spawn<Fut>(&mut self, f: Fut)
     where
-        Fut: Future<Output = Result<(), SendError>> + Send + 'static,
+        Fut: Future<Output = Result<(), Error>> + Send + 'static,
     {
         let mut rx = self.rx.resubscribe();
         self.join_set.spawn(async move {
             rx.recv().await.expect("Channel should not be closed.");
-            f.await.ok();
+            match f.await {
+                Err(Error::Closed) => Ok(()),
+                result => result,
+            }
         });
     }
 
+    /// Allocates the state handle of the next stateful operator. Operators are
+    /// named by the order in which they are constructed.
+    pub fn operator_state(&mut self) -> OperatorState {
+        let name = format!("operator-{}", self.operators);
+        self.operators += 1;
+        OperatorState::new(name, self.checkpointer.clone())
+    }
+
+    /// Allocates the endpoints of the next exchange between workers, or `None`
+    /// if the context is not part of a data-parallel runner.
+    pub(crate) fn exchange<T: Send + 'static>(&mut self) -> Option<Endpoints<T>> {
+        let exchange = self.exchange.as_ref()?;
+        let id = self.exchanges;
+        self.exchanges += 1;
+        Some(exchange.endpoints(id))
+    }
+
+    /// Allocates the state handle of a sink, which acknowledges snapshots.
+    pub fn sink_state(&mut self) -> OperatorState {
+        self.sinks += 1;
+        self.operator_state()
+    }
+
+    /// The number of sinks of the dataflow.
+    pub(crate) fn sinks(&self) -> usize {
+        self.sinks
+    }
+
     /// An operator with one input and one output.
     pub fn operator<T, F, Fut>(&mut self, f: F) -> Stream<T>
     where
         F: FnOnce(Collector<T>) -> Fut + Send + 'static,
-        Fut: Future<Output = Result<(), SendError>> + Send + 'static,
+        Fut: Future<Output = Result<(), Error>> + Send + 'static,
         T: Data,
     {
         let (tx, rx) = Stream::new();
@@ -90,7 +203,7 @@ impl Context {
     pub fn keyed_operator<K, T, F, Fut>(&mut self, f: F) -> KeyedStream<K, T>
     where
         F: FnOnce(KeyedCollector<K, T>) -> Fut + Send + 'static,
-        Fut: Future<Output = Result<(), SendError>> + Send + 'static,
+        Fut: Future<Output = Result<(), Error>> + Send + 'static,
         K: Data,
         T: Data,
     {
@@ -103,7 +216,7 @@ impl Context {
     pub fn co_operator<T0, T1, F, Fut>(&mut self, f: F) -> (Stream<T0>, Stream<T1>)
     where
         F: FnOnce(Collector<T0>, Collector<T1>) -> Fut + Send + 'static,
-        Fut: Future<Output = Result<(), SendError>> + Send + 'static,
+        Fut: Future<Output = Result<(), Error>> + Send + 'static,
         T0: Data,
         T1: Data,
     {
@@ -119,7 +232,7 @@ impl Context {
         f: impl FnOnce(KeyedCollector<K0, T0>, KeyedCollector<K1, T1>) -> F,
     ) -> (KeyedStream<K0, T0>, KeyedStream<K1, T1>)
     where
-        F: Future<Output = Result<(), SendError>> + Send + 'static,
+        F: Future<Output = Result<(), Error>> + Send + 'static,
         T0: Data,
         T1: Data,
         K0: Data,
@@ -134,7 +247,7 @@ impl Context {
     /// An operator with one input and zero outputs.
     pub fn sink<F>(&mut self, f: impl FnOnce() -> F)
     where
-        F: Future<Output = Result<(), SendError>> + Send + 'static,
+        F: Future<Output = Result<(), Error>> + Send + 'static,
     {
         self.spawn(f());
     }
@@ -1,12 +1,30 @@
+use crate::error::Error;
 use crate::runner::context::Context;
+use crate::state::Checkpoint;
 
 pub struct CurrentThreadRunner {}
 
 impl CurrentThreadRunner {
-    pub fn run(f: impl FnOnce(&mut Context)) {
+    /// Runs a dataflow to completion. Returns the first error of an operator,
+    /// after the rest of the dataflow has been cancelled.
+    pub fn run(f: impl FnOnce(&mut Context)) -> Result<(), Error> {
+        Self::run_context(Context::new(), f)
+    }
+
+    /// Like `run`, but periodically snapshots the state of all operators. If
+    /// the checkpoint is configured to restore, operators and sources start
+    /// from the state of that snapshot.
+    pub fn run_with_checkpoint(
+        checkpoint: Checkpoint,
+        f: impl FnOnce(&mut Context),
+    ) -> Result<(), Error> {
+        Self::run_context(Context::with_checkpoint(checkpoint)?, f)
+    }
+
+    fn run_context(ctx: Context, f: impl FnOnce(&mut Context)) -> Result<(), Error> {
         let future = async {
-            let ctx = Context::run_local(f).await;
-            ctx.await_termination().await;
+            let ctx = ctx.local(f).await;
+            ctx.await_termination().await
         };
         tokio::runtime::Builder::new_current_thread()
             .enable_all()
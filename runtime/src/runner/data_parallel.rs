@@ -1,42 +1,139 @@
+use tokio_util::sync::CancellationToken;
+
+use crate::error::Error;
 use crate::runner::context::Context;
+use crate::runner::exchange::Exchange;
+use crate::runner::pinning;
+use crate::runner::pinning::Placement;
+use crate::state::Checkpoint;
+use crate::state::Checkpointer;
 
 pub struct DataParallelRunner {
     txs: Vec<std::sync::mpsc::Sender<()>>,
-    threads: Vec<std::thread::JoinHandle<()>>,
+    threads: Vec<std::thread::JoinHandle<Result<(), Error>>>,
+    checkpointer: Option<Checkpointer>,
+    // The number of sinks of each worker, once its dataflow is built.
+    sinks: std::sync::mpsc::Receiver<usize>,
 }
 
 impl DataParallelRunner {
     pub fn new<T: Send + 'static, const N: usize>(
         args: [T; N],
         f: impl Fn(T, &mut Context) + Clone + Send + 'static,
+    ) -> Self {
+        Self::new_workers(args, f, None, None)
+    }
+
+    /// Like `new`, but each worker thread is pinned to a core, so that it is
+    /// not migrated between cores while running. Fails if no core of the
+    /// placement is available. If a worker cannot be pinned, the runner fails.
+    pub fn new_pinned<T: Send + 'static, const N: usize>(
+        placement: Placement,
+        args: [T; N],
+        f: impl Fn(T, &mut Context) + Clone + Send + 'static,
+    ) -> Result<Self, Error> {
+        let cores = placement.cores(N)?;
+        Ok(Self::new_workers(args, f, None, Some(cores)))
+    }
+
+    /// Like `new`, but each worker periodically snapshots the state of its
+    /// operators, and restores it if the checkpoint is configured to. Fails if
+    /// the snapshot to restore did not complete.
+    pub fn new_with_checkpoint<T: Send + 'static, const N: usize>(
+        checkpoint: Checkpoint,
+        args: [T; N],
+        f: impl Fn(T, &mut Context) + Clone + Send + 'static,
+    ) -> Result<Self, Error> {
+        let checkpointer = Checkpointer::new(checkpoint)?;
+        Ok(Self::new_workers(args, f, Some(checkpointer), None))
+    }
+
+    /// Combines `new_pinned` and `new_with_checkpoint`.
+    pub fn new_pinned_with_checkpoint<T: Send + 'static, const N: usize>(
+        placement: Placement,
+        checkpoint: Checkpoint,
+        args: [T; N],
+        f: impl Fn(T, &mut Context) + Clone + Send + 'static,
+    ) -> Result<Self, Error> {
+        let cores = placement.cores(N)?;
+        let checkpointer = Checkpointer::new(checkpoint)?;
+        Ok(Self::new_workers(args, f, Some(checkpointer), Some(cores)))
+    }
+
+    fn new_workers<T: Send + 'static, const N: usize>(
+        args: [T; N],
+        f: impl Fn(T, &mut Context) + Clone + Send + 'static,
+        checkpointer: Option<Checkpointer>,
+        cores: Option<Vec<usize>>,
     ) -> Self {
         let mut threads = Vec::with_capacity(args.len());
         let mut txs = Vec::with_capacity(args.len());
-        for arg in args {
+        let exchanges = Exchange::new(args.len());
+        // An error in one worker cancels all of them.
+        let cancel = CancellationToken::new();
+        let (sinks_tx, sinks) = std::sync::mpsc::channel();
+        for ((i, arg), exchange) in IntoIterator::into_iter(args).enumerate().zip(exchanges) {
             let f = f.clone();
+            let checkpointer = checkpointer.as_ref().map(|c| c.worker(i));
+            let core = cores.as_ref().map(|cores| cores[i]);
+            let cancel = cancel.clone();
+            let sinks_tx = sinks_tx.clone();
             let (runner_tx, runner_rx) = std::sync::mpsc::channel();
             txs.push(runner_tx);
             threads.push(std::thread::spawn(move || {
+                if let Some(core) = core {
+                    if let Err(e) = pinning::pin(core) {
+                        // Take part in the start handshake, but cancel the
+                        // other workers.
+                        cancel.cancel();
+                        sinks_tx.send(0).unwrap();
+                        runner_rx.recv().unwrap();
+                        return Err(e);
+                    }
+                }
                 tokio::runtime::Builder::new_current_thread()
                     .enable_all()
                     .build()
                     .expect("Failed to build runtime")
                     .block_on(async {
-                        let ctx = Context::run_local(|ctx| f(arg, ctx)).await;
+                        let ctx = match checkpointer {
+                            Some(checkpointer) => Context::with_checkpointer(checkpointer),
+                            None => Context::new(),
+                        }
+                        .with_exchange(exchange)
+                        .with_cancellation(cancel);
+                        let ctx = ctx.local(|ctx| f(arg, ctx)).await;
+                        sinks_tx.send(ctx.sinks()).unwrap();
                         runner_rx.recv().unwrap();
-                        ctx.await_termination().await;
-                    });
+                        ctx.await_termination().await
+                    })
             }));
         }
-        Self { txs, threads }
+        Self {
+            txs,
+            threads,
+            checkpointer,
+            sinks,
+        }
     }
 
-    pub fn run(mut self) {
+    /// Runs all workers to completion. Returns the first error of an
+    /// operator, after the dataflows of all workers have been cancelled.
+    pub fn run(mut self) -> Result<(), Error> {
+        // A snapshot completes once it reaches the sinks of all workers, so
+        // no worker may start before all of them have been built.
+        let sinks = self.sinks.iter().take(self.threads.len()).sum();
+        if let Some(checkpointer) = &self.checkpointer {
+            checkpointer.expect_sinks(sinks);
+        }
         for tx in self.txs.iter() {
             tx.send(()).unwrap();
         }
+        let mut result = Ok(());
         for thread in self.threads.drain(..) {
-            thread.join().expect("Failed to join thread");
+            let r = thread.join().expect("Failed to join thread");
+            result = result.and(r);
         }
+        result
     }
 }
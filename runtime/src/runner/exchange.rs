@@ -0,0 +1,77 @@
+use std::any::Any;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use tokio::sync::mpsc::Receiver;
+use tokio::sync::mpsc::Sender;
+
+use crate::HashMap;
+
+/// Channels between the workers of a data-parallel runner. Every worker builds
+/// the same dataflow, so the n-th exchange of each worker refers to the same
+/// set of channels: one per worker, which all workers can send to.
+#[derive(Clone)]
+pub(crate) struct Exchange {
+    worker: usize,
+    workers: usize,
+    channels: Arc<Mutex<HashMap<usize, Box<dyn Any + Send>>>>,
+}
+
+struct Channels<T> {
+    txs: Vec<Sender<(usize, T)>>,
+    rxs: Vec<Option<Receiver<(usize, T)>>>,
+    taken: usize,
+}
+
+/// The endpoints of an exchange which belong to one worker. Senders are
+/// indexed by worker, and each message is tagged with the worker it is from.
+pub(crate) struct Endpoints<T> {
+    pub(crate) worker: usize,
+    pub(crate) txs: Vec<Sender<(usize, T)>>,
+    pub(crate) rx: Receiver<(usize, T)>,
+}
+
+impl Exchange {
+    /// Returns the exchanges of `workers` workers.
+    pub(crate) fn new(workers: usize) -> Vec<Self> {
+        let channels = Arc::new(Mutex::new(HashMap::default()));
+        (0..workers)
+            .map(|worker| Self {
+                worker,
+                workers,
+                channels: channels.clone(),
+            })
+            .collect()
+    }
+
+    pub(crate) fn endpoints<T: Send + 'static>(&self, id: usize) -> Endpoints<T> {
+        let mut channels = self.channels.lock().unwrap();
+        let entry = channels.entry(id).or_insert_with(|| {
+            let (txs, rxs) = (0..self.workers)
+                .map(|_| {
+                    let (tx, rx) = tokio::sync::mpsc::channel(100);
+                    (tx, Some(rx))
+                })
+                .unzip();
+            Box::new(Channels::<T> { txs, rxs, taken: 0 })
+        });
+        let entry = entry
+            .downcast_mut::<Channels<T>>()
+            .expect("Workers should build the same dataflow");
+        let rx = entry.rxs[self.worker]
+            .take()
+            .expect("Exchange should be taken once per worker");
+        let txs = entry.txs.clone();
+        entry.taken += 1;
+        // Drop the senders once every worker holds them, so that receivers
+        // observe when all senders have terminated.
+        if entry.taken == self.workers {
+            channels.remove(&id);
+        }
+        Endpoints {
+            worker: self.worker,
+            txs,
+            rx,
+        }
+    }
+}
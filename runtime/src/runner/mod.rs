@@ -1,8 +1,7 @@
 pub mod data_parallel;
 pub mod task_parallel;
 pub mod context;
-
-// #[cfg(feature = "thread-pinning")]
-// pub mod pinned_data_parallel;
+pub(crate) mod exchange;
+pub mod pinning;
 
 pub mod current_thread;
@@ -0,0 +1,147 @@
+use crate::error::Error;
+
+/// Where the workers of a `DataParallelRunner` are pinned. Only cores which
+/// the process may run on are used, and cores are reused round-robin if there
+/// are more workers than cores.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Placement {
+    /// Worker `i` is pinned to the `i`-th core of the list.
+    Cores(Vec<usize>),
+    /// Workers fill up one NUMA node before the next.
+    Compact,
+    /// Workers are distributed round-robin across NUMA nodes.
+    Spread,
+}
+
+impl Placement {
+    /// Returns the core of each worker. Fails if none of the cores may be
+    /// used.
+    pub fn cores(&self, workers: usize) -> Result<Vec<usize>, Error> {
+        let cores = match self {
+            Placement::Cores(cores) => {
+                let available = available_cores();
+                cores
+                    .iter()
+                    .copied()
+                    .filter(|c| available.contains(c))
+                    .collect()
+            }
+            Placement::Compact => numa_nodes().concat(),
+            Placement::Spread => {
+                let nodes = numa_nodes();
+                let n = nodes.iter().map(Vec::len).max().unwrap_or(0);
+                (0..n)
+                    .flat_map(|i| nodes.iter().filter_map(move |node| node.get(i).copied()))
+                    .collect()
+            }
+        };
+        if cores.is_empty() {
+            return Err(Error::custom(format!(
+                "None of the cores of {:?} is available",
+                self
+            )));
+        }
+        Ok(cores.iter().copied().cycle().take(workers).collect())
+    }
+}
+
+/// Returns the available cores of each NUMA node, or of a single node if the
+/// topology is unknown.
+pub fn numa_nodes() -> Vec<Vec<usize>> {
+    let available = available_cores();
+    let mut nodes = std::fs::read_dir("/sys/devices/system/node")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let id = name.strip_prefix("node")?.parse::<usize>().ok()?;
+            let cpulist = std::fs::read_to_string(entry.path().join("cpulist")).ok()?;
+            Some((id, parse_cpulist(&cpulist)))
+        })
+        .collect::<Vec<_>>();
+    nodes.sort();
+    let nodes = nodes
+        .into_iter()
+        .map(|(_, cores)| {
+            cores
+                .into_iter()
+                .filter(|c| available.contains(c))
+                .collect::<Vec<_>>()
+        })
+        .filter(|cores| !cores.is_empty())
+        .collect::<Vec<_>>();
+    if nodes.is_empty() {
+        vec![available]
+    } else {
+        nodes
+    }
+}
+
+/// Parses a list such as `0-3,8,10-11`.
+fn parse_cpulist(s: &str) -> Vec<usize> {
+    s.trim()
+        .split(',')
+        .filter_map(|range| match range.split_once('-') {
+            Some((a, b)) => Some(a.parse().ok()?..=b.parse().ok()?),
+            None => {
+                let c = range.parse().ok()?;
+                Some(c..=c)
+            }
+        })
+        .flatten()
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn available_cores() -> Vec<usize> {
+    // SAFETY: The set is zero-initialised and only accessed through libc.
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        if libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set) != 0 {
+            return fallback_cores();
+        }
+        (0..libc::CPU_SETSIZE as usize)
+            .filter(|c| libc::CPU_ISSET(*c, &set))
+            .collect()
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn available_cores() -> Vec<usize> {
+    fallback_cores()
+}
+
+fn fallback_cores() -> Vec<usize> {
+    let n = std::thread::available_parallelism().map_or(1, |n| n.get());
+    (0..n).collect()
+}
+
+/// Pins the calling thread to `core`.
+#[cfg(target_os = "linux")]
+pub(crate) fn pin(core: usize) -> Result<(), Error> {
+    if core >= libc::CPU_SETSIZE as usize {
+        return Err(Error::custom(format!("Core {} does not exist", core)));
+    }
+    // SAFETY: The set is zero-initialised and only accessed through libc.
+    let result = unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(core, &mut set);
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set)
+    };
+    if result != 0 {
+        let e = std::io::Error::last_os_error();
+        let msg = format!("Failed to pin thread to core {}: {}", core, e);
+        return Err(std::io::Error::new(e.kind(), msg).into());
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn pin(core: usize) -> Result<(), Error> {
+    tracing::warn!(
+        "Thread pinning is only supported on Linux, not pinning to core {}",
+        core
+    );
+    Ok(())
+}
@@ -1,41 +1,79 @@
+use crate::error::Error;
 use crate::runner::context::Context;
+use crate::state::Checkpoint;
+use crate::state::Checkpointer;
 
+/// Runs the operators of a dataflow as tasks on a multi-threaded, work-stealing
+/// runtime, so that the operators of a single pipeline can run on separate
+/// cores. The dataflow is built by `new`, and only starts once `run` is called.
 pub struct TaskParallelRunner {
-    tx: std::sync::mpsc::SyncSender<()>,
+    tx: tokio::sync::oneshot::Sender<()>,
+    thread: std::thread::JoinHandle<Result<(), Error>>,
 }
 
 impl TaskParallelRunner {
-    // #[cfg(feature = "multi-thread")]
-    // pub fn new(f: impl FnOnce(&mut Context) + Send + 'static) -> Self {
-    //     let (tx, rx) = std::sync::mpsc::sync_channel::<()>(1);
-    //     tokio::runtime::Builder::new_multi_thread()
-    //         .enable_all()
-    //         .build()
-    //         .expect("Failed to build runtime")
-    //         .block_on(async {
-    //             let ctx = Context::run(f);
-    //             rx.recv().unwrap();
-    //             ctx.await_termination().await;
-    //         });
-    //     Self { tx }
-    // }
-
-    // #[cfg(not(feature = "multi-thread"))]
+    /// Uses one worker thread per core.
     pub fn new(f: impl FnOnce(&mut Context) + Send + 'static) -> Self {
-        let (tx, rx) = std::sync::mpsc::sync_channel::<()>(1);
-        tokio::runtime::Builder::new_current_thread()
+        Self::new_context(Context::new, None, f)
+    }
+
+    /// Like `new`, but with `workers` worker threads.
+    pub fn new_with_workers(workers: usize, f: impl FnOnce(&mut Context) + Send + 'static) -> Self {
+        Self::new_context(Context::new, Some(workers), f)
+    }
+
+    /// Like `new`, but periodically snapshots the state of all operators, and
+    /// restores it if the checkpoint is configured to. Fails if the snapshot
+    /// to restore did not complete.
+    pub fn new_with_checkpoint(
+        checkpoint: Checkpoint,
+        f: impl FnOnce(&mut Context) + Send + 'static,
+    ) -> Result<Self, Error> {
+        let checkpointer = Checkpointer::new(checkpoint)?;
+        Ok(Self::new_context(
+            move || Context::with_checkpointer(checkpointer),
+            None,
+            f,
+        ))
+    }
+
+    fn new_context(
+        ctx: impl FnOnce() -> Context + Send + 'static,
+        workers: Option<usize>,
+        f: impl FnOnce(&mut Context) + Send + 'static,
+    ) -> Self {
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        if let Some(workers) = workers {
+            builder.worker_threads(workers);
+        }
+        let runtime = builder
             .enable_all()
             .build()
-            .expect("Failed to build runtime")
-            .block_on(async {
-                let ctx = Context::run(f);
-                rx.recv().unwrap();
-                ctx.await_termination().await;
-            });
-        Self { tx }
+            .expect("Failed to build runtime");
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let (built_tx, built_rx) = std::sync::mpsc::sync_channel(1);
+        let thread = std::thread::spawn(move || {
+            runtime.block_on(async {
+                let ctx = ctx().build(f);
+                built_tx.send(()).unwrap();
+                match rx.await {
+                    Ok(()) => ctx.await_termination().await,
+                    Err(_) => Ok(()),
+                }
+            })
+        });
+        // Wait until the dataflow is built, so that errors surface in `new`.
+        if built_rx.recv().is_err() {
+            std::panic::resume_unwind(thread.join().unwrap_err());
+        }
+        Self { tx, thread }
     }
 
-    pub fn run(self) {
+    /// Starts the dataflow, and blocks until all of its operators have terminated.
+    /// Returns the first error of an operator, after the rest of the dataflow
+    /// has been cancelled.
+    pub fn run(self) -> Result<(), Error> {
         self.tx.send(()).unwrap();
+        self.thread.join().expect("Failed to join thread")
     }
 }
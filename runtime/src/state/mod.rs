@@ -1,17 +1,22 @@
-#![allow(unused)]
-
 use std::collections::HashMap;
-use std::hash::Hash;
 use std::ops::Index;
 use std::ops::IndexMut;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
 
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
+use crate::builtins::duration::Duration;
+use crate::error::Error;
 use crate::traits::Data;
 use crate::traits::Key;
 
+const LATEST: &[u8] = b"latest";
+const COMPLETED: &str = "completed";
+
 #[derive(Clone)]
 pub struct Database(
     // tikv_client::RawClient,
@@ -40,6 +45,197 @@ impl Database {
     //     let db = sled::open(path).expect("Failed to connect to sled");
     //     Self(db)
     // }
+
+    /// Returns the id of the latest snapshot that was acknowledged by every sink.
+    pub fn latest(&self) -> Option<usize> {
+        let v = self.0.get(LATEST).expect("sled: failed to get");
+        deser(v.map(|v| v.to_vec())).expect("Failed to deserialize snapshot id")
+    }
+
+    fn put<T: Serialize>(&self, key: &str, snapshot: usize, data: &T) {
+        let key = ser((key, snapshot)).expect("Failed to serialize key");
+        let data = ser(data).expect("Failed to serialize state");
+        self.0.insert(key, data).expect("sled: failed to insert");
+    }
+
+    fn get<T: DeserializeOwned>(&self, key: &str, snapshot: usize) -> Option<T> {
+        let key = ser((key, snapshot)).expect("Failed to serialize key");
+        let v = self.0.get(key).expect("sled: failed to get");
+        deser(v.map(|v| v.to_vec())).expect("Failed to deserialize state")
+    }
+
+    /// Returns true if snapshot `snapshot` was acknowledged by every sink.
+    pub fn completed(&self, snapshot: usize) -> bool {
+        self.get::<()>(COMPLETED, snapshot).is_some()
+    }
+
+    fn commit(&self, snapshot: usize) {
+        self.put(COMPLETED, snapshot, &());
+        let data = ser(snapshot).expect("Failed to serialize snapshot id");
+        self.0.insert(LATEST, data).expect("sled: failed to insert");
+        self.0.flush().expect("sled: failed to flush");
+    }
+}
+
+/// Configuration for periodic aligned snapshots of a dataflow.
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    pub(crate) path: PathBuf,
+    pub(crate) interval: Duration,
+    pub(crate) restore: Restore,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Restore {
+    None,
+    Latest,
+    Snapshot(usize),
+}
+
+impl Checkpoint {
+    /// Snapshots are written to a local state backend at `path`. Sources inject
+    /// a snapshot barrier every `interval` of wall-clock time.
+    pub fn new(path: impl Into<PathBuf>, interval: Duration) -> Self {
+        Self {
+            path: path.into(),
+            interval,
+            restore: Restore::None,
+        }
+    }
+
+    /// Start the dataflow from the state written by snapshot `snapshot`. The
+    /// runner fails if the snapshot did not complete.
+    pub fn restore(mut self, snapshot: usize) -> Self {
+        self.restore = Restore::Snapshot(snapshot);
+        self
+    }
+
+    /// Start the dataflow from the latest completed snapshot, if there is one.
+    pub fn resume(mut self) -> Self {
+        self.restore = Restore::Latest;
+        self
+    }
+}
+
+/// Shared by all operators of a dataflow which takes snapshots.
+#[derive(Clone)]
+pub(crate) struct Checkpointer {
+    db: Database,
+    interval: Duration,
+    restore: Option<usize>,
+    prefix: String,
+    sinks: Arc<OnceLock<usize>>,
+    acks: Arc<Mutex<HashMap<usize, usize>>>,
+}
+
+impl Checkpointer {
+    pub(crate) fn new(checkpoint: Checkpoint) -> Result<Self, Error> {
+        let db = Database::new(checkpoint.path);
+        let restore = match checkpoint.restore {
+            Restore::None => None,
+            Restore::Latest => db.latest(),
+            Restore::Snapshot(snapshot) if db.completed(snapshot) => Some(snapshot),
+            Restore::Snapshot(snapshot) => {
+                return Err(Error::custom(format!(
+                    "Cannot restore snapshot {}, which did not complete",
+                    snapshot
+                )))
+            }
+        };
+        Ok(Self {
+            db,
+            interval: checkpoint.interval,
+            restore,
+            prefix: String::new(),
+            sinks: Arc::new(OnceLock::new()),
+            acks: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    /// The checkpointer of one worker in a data-parallel dataflow. Workers
+    /// share acknowledgements, so a snapshot completes once it has reached
+    /// the sinks of every worker.
+    pub(crate) fn worker(&self, index: usize) -> Self {
+        Self {
+            prefix: format!("worker-{index}/"),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn name(&self, name: String) -> String {
+        format!("{}{}", self.prefix, name)
+    }
+
+    /// Sets the number of sinks which acknowledge each snapshot, across all
+    /// workers. It must be set before the dataflow starts, and is only set
+    /// once, so a data-parallel runner sets it for all of its workers.
+    pub(crate) fn expect_sinks(&self, sinks: usize) {
+        let _ = self.sinks.set(sinks);
+    }
+
+    /// A snapshot is complete once the barrier has reached every sink.
+    fn ack(&self, snapshot: usize) {
+        let sinks = *self.sinks.get().expect("Sinks must be set before the dataflow starts");
+        let mut acks = self.acks.lock().unwrap();
+        let n = acks.entry(snapshot).or_default();
+        *n += 1;
+        if *n == sinks {
+            acks.remove(&snapshot);
+            self.db.commit(snapshot);
+        }
+    }
+}
+
+/// Handle through which an operator writes its state when a snapshot barrier
+/// arrives. All methods are no-ops if checkpointing is disabled.
+#[derive(Clone)]
+pub struct OperatorState {
+    name: String,
+    checkpointer: Option<Checkpointer>,
+}
+
+impl OperatorState {
+    pub(crate) fn new(name: String, checkpointer: Option<Checkpointer>) -> Self {
+        let name = match &checkpointer {
+            Some(checkpointer) => checkpointer.name(name),
+            None => name,
+        };
+        Self { name, checkpointer }
+    }
+
+    /// The snapshot which the dataflow was restored from, or `0` if it
+    /// started from scratch. Sources number their barriers from here.
+    pub fn restored(&self) -> usize {
+        self.checkpointer
+            .as_ref()
+            .and_then(|c| c.restore)
+            .unwrap_or(0)
+    }
+
+    /// Reads the state which this operator wrote for the snapshot that the
+    /// dataflow is restored from.
+    pub fn restore<S: DeserializeOwned>(&self) -> Option<S> {
+        let checkpointer = self.checkpointer.as_ref()?;
+        checkpointer.db.get(&self.name, checkpointer.restore?)
+    }
+
+    /// Wall-clock interval between snapshot barriers injected by sources.
+    pub fn interval(&self) -> Option<std::time::Duration> {
+        self.checkpointer.as_ref().map(|c| c.interval.to_std())
+    }
+
+    pub fn snapshot<S: Serialize>(&self, snapshot: usize, state: &S) {
+        if let Some(checkpointer) = &self.checkpointer {
+            checkpointer.db.put(&self.name, snapshot, state);
+        }
+    }
+
+    /// Called by sinks to acknowledge that a barrier has passed through the dataflow.
+    pub fn ack(&self, snapshot: usize) {
+        if let Some(checkpointer) = &self.checkpointer {
+            checkpointer.ack(snapshot);
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -76,7 +272,7 @@ impl<K: Data, T: Data> State<K, T> {
         self.uncommitted
             .iter()
             .filter_map(|(key, data)| {
-                let key = ser((key, snapshot_version)).ok()?;
+                let key = ser((&self.name, key, snapshot_version)).ok()?;
                 let data = ser(data).ok()?;
                 Some((key, data))
             })
@@ -102,10 +298,10 @@ impl<K: Key, T: Data> Index<K> for State<K, T> {
     }
 }
 
-pub fn ser<T: Serialize>(v: T) -> Result<Vec<u8>, serde_json::Error> {
-    serde_json::to_vec(&v)
+pub fn ser<T: Serialize>(v: T) -> Result<Vec<u8>, bincode::Error> {
+    bincode::serialize(&v)
 }
 
-pub fn deser<T: DeserializeOwned>(v: Option<Vec<u8>>) -> Result<Option<T>, serde_json::Error> {
-    v.map(|v| serde_json::from_slice(&v)).transpose()
+pub fn deser<T: DeserializeOwned>(v: Option<Vec<u8>>) -> Result<Option<T>, bincode::Error> {
+    v.map(|v| bincode::deserialize(&v)).transpose()
 }
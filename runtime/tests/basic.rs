@@ -1,3 +1,5 @@
+#![allow(clippy::let_unit_value)]
+
 use runtime::builtins::time::Time;
 use runtime::builtins::writer::Writer;
 use runtime::prelude::data;
@@ -24,6 +26,7 @@ const OUTPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/output.csv");
 
 #[test]
 fn test() {
+    std::fs::write(INPUT, "1,2,3\n4,5,6\n7,8,9\n10,11,12\n").unwrap();
     CurrentThreadRunner::run(|ctx| {
         let s0 = Stream::<Data>::source(
             ctx,
@@ -35,5 +38,6 @@ fn test() {
         );
         let s1 = Stream::<Data>::filter(s0, ctx, |data: &Data| data.y > 5);
         let _ = Stream::<Data>::sink(s1, ctx, Writer::file(Path::new(OUTPUT)), Format::csv(','));
-    });
+    })
+    .unwrap();
 }
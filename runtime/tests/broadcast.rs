@@ -0,0 +1,97 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use runtime::prelude::stream::Event;
+use runtime::prelude::*;
+use tokio::sync::oneshot;
+
+/// Sends the keys once `applied` fires. The watermark is sent first, so that
+/// the output watermark of the filter only waits for the control stream.
+fn gated(ctx: &mut Context, keys: Vec<u64>, applied: oneshot::Receiver<()>) -> Stream<u64> {
+    ctx.operator(|tx| async move {
+        tx.send(Event::Watermark(Time::from_seconds(1))).await?;
+        applied.await.ok();
+        for (i, key) in keys.into_iter().enumerate() {
+            tx.send(Event::Data(Time::from_seconds(1 + i as i64), key))
+                .await?;
+        }
+        tx.send(Event::Sentinel).await
+    })
+}
+
+/// Fires `applied` at the first watermark of `stream`. The filter only emits
+/// it once the watermark of the control stream has reached it, which is after
+/// every control element has been applied.
+fn on_watermark(
+    ctx: &mut Context,
+    mut stream: Stream<u64>,
+    applied: oneshot::Sender<()>,
+) -> Stream<u64> {
+    let mut applied = Some(applied);
+    ctx.operator(|tx| async move {
+        loop {
+            let event = stream.recv().await;
+            if let Event::Watermark(_) = event {
+                if let Some(applied) = applied.take() {
+                    applied.send(()).ok();
+                }
+            }
+            let done = matches!(event, Event::Sentinel);
+            tx.send(event).await?;
+            if done {
+                break Ok(());
+            }
+        }
+    })
+}
+
+fn dynamic_filter(ctx: &mut Context, data: Stream<u64>, control: Stream<u64>) -> Stream<u64> {
+    data.connect_broadcast(ctx, control).process(
+        ctx,
+        |blocked: &mut Vec<u64>, key| blocked.push(key),
+        |ctx, key| {
+            if !ctx.state().contains(&key) {
+                ctx.emit(key);
+            }
+        },
+    )
+}
+
+fn control(ctx: &mut Context, key: u64) -> Stream<u64> {
+    Stream::from_iter(ctx, [key], |_| Time::from_seconds(1), 1, Duration::zero())
+}
+
+#[test]
+fn test_broadcast_process() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let (applied_tx, applied_rx) = oneshot::channel();
+        let data = gated(ctx, vec![0, 1, 2, 1, 0], applied_rx);
+        let control = control(ctx, 1);
+        let output = dynamic_filter(ctx, data, control);
+        on_watermark(ctx, output, applied_tx).collect_vec(ctx, tx);
+    })
+    .unwrap();
+    assert_eq!(rx.try_recv().unwrap(), [0, 2, 0]);
+}
+
+#[test]
+fn test_broadcast_data_parallel() {
+    let (tx0, mut rx0) = tokio::sync::mpsc::channel(1);
+    let (tx1, mut rx1) = tokio::sync::mpsc::channel(1);
+    let worker = Arc::new(AtomicU64::new(1));
+    DataParallelRunner::new([tx0, tx1], move |tx, ctx| {
+        // Each worker blocks a different key, which is broadcast to both.
+        let key = worker.fetch_add(1, Ordering::SeqCst);
+        let (applied_tx, applied_rx) = oneshot::channel();
+        let data = gated(ctx, vec![0, 1, 2, 3], applied_rx);
+        let control = control(ctx, key);
+        let output = dynamic_filter(ctx, data, control);
+        on_watermark(ctx, output, applied_tx).collect_vec(ctx, tx);
+    })
+    .run()
+    .unwrap();
+    assert_eq!(rx0.try_recv().unwrap(), [0, 3]);
+    assert_eq!(rx1.try_recv().unwrap(), [0, 3]);
+}
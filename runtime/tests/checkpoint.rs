@@ -0,0 +1,166 @@
+use runtime::prelude::*;
+use runtime::state::Database;
+
+#[data]
+struct Data {
+    key: u64,
+    time: Time,
+}
+
+#[test]
+fn test_snapshot_completes() {
+    let path = std::env::temp_dir().join("runtime-test-snapshot-completes");
+    let _ = std::fs::remove_dir_all(&path);
+    let checkpoint = Checkpoint::new(&path, Duration::from_microseconds(100));
+    CurrentThreadRunner::run_with_checkpoint(checkpoint, |ctx| {
+        let events = (0..1000).map(|i| Data::new(i % 10, Time::from_seconds(i as i64)));
+        Stream::from_iter(ctx, events, |e| e.time, 100, Duration::zero())
+            .keyby(ctx, |e| e.key)
+            .time_tumbling_holistic_window(ctx, Duration::from_seconds(100), |_, data, _| {
+                data.len()
+            })
+            .drain(ctx);
+    })
+    .unwrap();
+    assert!(Database::new(&path).latest().is_some());
+}
+
+fn run(checkpoint: Checkpoint) -> Vec<(u64, usize)> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run_with_checkpoint(checkpoint, |ctx| {
+        let events = (0..1000).map(|i| Data::new(i % 10, Time::from_seconds(i as i64)));
+        Stream::from_iter(ctx, events, |e| e.time, 100, Duration::zero())
+            .keyby(ctx, |e| e.key)
+            .time_tumbling_holistic_window(ctx, Duration::from_seconds(100), |key, data, _| {
+                (*key, data.len())
+            })
+            .unkey(ctx)
+            .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    rx.try_recv().unwrap()
+}
+
+#[test]
+fn test_restore_from_latest_snapshot() {
+    let path = std::env::temp_dir().join("runtime-test-restore-from-latest-snapshot");
+    let _ = std::fs::remove_dir_all(&path);
+    let checkpoint = Checkpoint::new(&path, Duration::from_microseconds(100));
+    let all = run(checkpoint.clone());
+    let rest = run(checkpoint.resume());
+    assert!(rest.len() < all.len());
+    assert!(all.ends_with(&rest));
+}
+
+#[test]
+fn test_restore_incomplete_snapshot() {
+    let path = std::env::temp_dir().join("runtime-test-restore-incomplete-snapshot");
+    let _ = std::fs::remove_dir_all(&path);
+    let checkpoint = Checkpoint::new(&path, Duration::from_microseconds(100)).restore(1000);
+    let result = CurrentThreadRunner::run_with_checkpoint(checkpoint, |ctx| {
+        Stream::from_iter(ctx, 0..10, |i| Time::from_seconds(*i), 1, Duration::zero()).drain(ctx);
+    });
+    assert!(matches!(result, Err(Error::Custom(_))));
+}
+
+#[test]
+fn test_data_parallel_snapshot_completes() {
+    let path = std::env::temp_dir().join("runtime-test-data-parallel-snapshot-completes");
+    let _ = std::fs::remove_dir_all(&path);
+    let checkpoint = Checkpoint::new(&path, Duration::from_microseconds(100));
+    DataParallelRunner::new_with_checkpoint(checkpoint, [0, 1], |_, ctx| {
+        Stream::from_iter(
+            ctx,
+            0..1000,
+            |i| Time::from_seconds(*i),
+            1,
+            Duration::zero(),
+        )
+        .drain(ctx);
+    })
+    .unwrap()
+    .run()
+    .unwrap();
+    assert!(Database::new(&path).latest().is_some());
+}
+
+#[data]
+struct Line {
+    i: u64,
+}
+
+fn read(checkpoint: Checkpoint, input: &std::path::Path) -> Vec<u64> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    let input = input.to_path_buf();
+    CurrentThreadRunner::run_with_checkpoint(checkpoint, |ctx| {
+        Stream::<Line>::source(
+            ctx,
+            Reader::file(input, false),
+            Format::csv(','),
+            |_, t| t,
+            Duration::zero(),
+            Duration::from_milliseconds(10),
+        )
+        .map(ctx, |line| line.i)
+        .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    rx.try_recv().unwrap()
+}
+
+#[test]
+fn test_restore_file_source_after_growth() {
+    let path = std::env::temp_dir().join("runtime-test-restore-file-source");
+    let input = std::env::temp_dir().join("runtime-test-restore-file-source.csv");
+    let _ = std::fs::remove_dir_all(&path);
+    let lines = |range: std::ops::Range<u64>| range.map(|i| format!("{}\n", i)).collect::<String>();
+    std::fs::write(&input, lines(0..1000)).unwrap();
+    let checkpoint = Checkpoint::new(&path, Duration::from_milliseconds(1));
+    let all = read(checkpoint.clone(), &input);
+    assert_eq!(all, (0..1000).collect::<Vec<_>>());
+    // Records appended after the snapshot are read by the restored source.
+    let mut file = std::fs::OpenOptions::new()
+        .append(true)
+        .open(&input)
+        .unwrap();
+    std::io::Write::write_all(&mut file, lines(1000..1010).as_bytes()).unwrap();
+    let rest = read(checkpoint.resume(), &input);
+    let (old, new) = rest.split_at(rest.len() - 10);
+    assert_eq!(new, (1000..1010).collect::<Vec<_>>());
+    assert!(old.len() < all.len());
+    assert!(all.ends_with(old));
+}
+
+fn copy(checkpoint: Checkpoint, input: &std::path::Path, output: &std::path::Path) {
+    let input = input.to_path_buf();
+    let output = output.to_path_buf();
+    CurrentThreadRunner::run_with_checkpoint(checkpoint, |ctx| {
+        Stream::<Line>::source(
+            ctx,
+            Reader::file(input, false),
+            Format::csv(','),
+            |_, t| t,
+            Duration::zero(),
+            Duration::from_milliseconds(10),
+        )
+        .sink(ctx, Writer::file(output), Format::csv(','));
+    })
+    .unwrap();
+}
+
+#[test]
+fn test_restore_file_sink() {
+    let path = std::env::temp_dir().join("runtime-test-restore-file-sink");
+    let input = std::env::temp_dir().join("runtime-test-restore-file-sink-input.csv");
+    let output = std::env::temp_dir().join("runtime-test-restore-file-sink-output.csv");
+    let _ = std::fs::remove_dir_all(&path);
+    let lines = (0..1000).map(|i| format!("{}\n", i)).collect::<String>();
+    std::fs::write(&input, &lines).unwrap();
+    let checkpoint = Checkpoint::new(&path, Duration::from_milliseconds(1));
+    copy(checkpoint.clone(), &input, &output);
+    assert_eq!(std::fs::read_to_string(&output).unwrap(), lines);
+    // The restored sink keeps the output written before the snapshot, and
+    // drops the output written after it, which the restored source reads again.
+    copy(checkpoint.resume(), &input, &output);
+    assert_eq!(std::fs::read_to_string(&output).unwrap(), lines);
+}
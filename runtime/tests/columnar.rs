@@ -0,0 +1,147 @@
+#![cfg(feature = "columnar")]
+
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow::datatypes::FieldRef;
+use arrow::datatypes::Schema;
+use arrow::ipc::writer::FileWriter;
+use parquet::arrow::ArrowWriter;
+use runtime::prelude::*;
+use serde_arrow::schema::SchemaLike;
+use serde_arrow::schema::TracingOptions;
+
+#[data]
+struct Data {
+    x: u64,
+    s: String,
+}
+
+fn rows() -> Vec<Data> {
+    (0..10).map(|x| Data::new(x, format!("{x}"))).collect()
+}
+
+fn tuples(rows: &[Data]) -> Vec<(u64, String)> {
+    rows.iter().map(|d| (d.x, d.s.clone())).collect()
+}
+
+fn write(path: &Path, parquet: bool) {
+    let fields = Vec::<FieldRef>::from_type::<Data>(TracingOptions::default()).unwrap();
+    let schema = Arc::new(Schema::new(fields.clone()));
+    let file = File::create(path).unwrap();
+    let rows = rows();
+    let batches = rows
+        .chunks(5)
+        .map(|chunk| serde_arrow::to_record_batch(&fields, &chunk).unwrap());
+    if parquet {
+        let mut writer = ArrowWriter::try_new(file, schema, None).unwrap();
+        batches.for_each(|batch| writer.write(&batch).unwrap());
+        writer.close().unwrap();
+    } else {
+        let mut writer = FileWriter::try_new(file, &schema).unwrap();
+        batches.for_each(|batch| writer.write(&batch).unwrap());
+        writer.finish().unwrap();
+    }
+}
+
+fn read(path: &Path) -> Vec<Data> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    let path = path.to_owned();
+    CurrentThreadRunner::run(move |ctx| {
+        Stream::<Data>::from_columnar(
+            ctx,
+            path,
+            |d| Time::from_seconds(d.x as i64),
+            1,
+            Duration::zero(),
+        )
+        .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    rx.try_recv().unwrap()
+}
+
+fn read_batches(path: &Path) -> Vec<Vec<Data>> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    let path = path.to_owned();
+    CurrentThreadRunner::run(move |ctx| {
+        Stream::<Vec<Data>>::from_columnar_batches(
+            ctx,
+            path,
+            |d| Time::from_seconds(d.x as i64),
+            1,
+            Duration::zero(),
+        )
+        .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    rx.try_recv().unwrap()
+}
+
+#[test]
+fn test_parquet() {
+    let path = std::env::temp_dir().join("runtime-test-parquet.parquet");
+    write(&path, true);
+    assert_eq!(tuples(&read(&path)), tuples(&rows()));
+    assert_eq!(tuples(&read_batches(&path).concat()), tuples(&rows()));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_arrow() {
+    let path = std::env::temp_dir().join("runtime-test-arrow.arrow");
+    write(&path, false);
+    assert_eq!(tuples(&read(&path)), tuples(&rows()));
+    assert_eq!(tuples(&read_batches(&path).concat()), tuples(&rows()));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[data]
+struct Other {
+    y: f64,
+}
+
+fn drain<T: runtime::traits::Data + serde::de::DeserializeOwned>(path: &Path) -> Result<(), Error> {
+    let path = path.to_owned();
+    CurrentThreadRunner::run(move |ctx| {
+        Stream::<T>::from_columnar(ctx, path, |_| Time::zero(), 1, Duration::zero()).drain(ctx);
+    })
+}
+
+#[test]
+fn test_columnar_missing_file() {
+    let path = std::env::temp_dir().join("runtime-test-columnar-missing.parquet");
+    let result = drain::<Data>(&path);
+    assert!(matches!(result, Err(Error::Io(_))));
+}
+
+#[test]
+fn test_columnar_mismatched_schema() {
+    let path = std::env::temp_dir().join("runtime-test-columnar-mismatched.parquet");
+    write(&path, true);
+    let result = drain::<Other>(&path);
+    std::fs::remove_file(path).unwrap();
+    assert!(matches!(result, Err(Error::Custom(_))));
+}
+
+#[test]
+fn test_columnar_find() {
+    let dir = std::env::temp_dir().join("runtime-test-columnar-find");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    write(&dir.join("data.arrow"), false);
+    let found = runtime::formats::columnar::find::<Data>(&dir, "data")
+        .unwrap()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(tuples(&found), tuples(&rows()));
+    assert!(runtime::formats::columnar::find::<Data>(&dir, "other").is_none());
+    // A row which does not match the type is an error, instead of a panic.
+    let mut other = runtime::formats::columnar::find::<Other>(&dir, "data")
+        .unwrap()
+        .unwrap();
+    assert!(matches!(other.next(), Some(Err(Error::Custom(_)))));
+    std::fs::remove_dir_all(dir).unwrap();
+}
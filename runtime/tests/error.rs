@@ -0,0 +1,92 @@
+use runtime::prelude::*;
+
+#[data]
+struct Data {
+    x: i32,
+}
+
+#[test]
+fn test_try_map_error() {
+    let result = CurrentThreadRunner::run(|ctx| {
+        Stream::from_iter(
+            ctx,
+            0..1000,
+            |i| Time::from_seconds(*i),
+            1,
+            Duration::zero(),
+        )
+        .try_map(ctx, |i| match i {
+            10 => Err(format!("Bad record {}", i)),
+            i => Ok(i),
+        })
+        .drain(ctx);
+    });
+    let Err(Error::Custom(e)) = result else {
+        panic!("Unexpected result {:?}", result)
+    };
+    assert_eq!(e.to_string(), "Bad record 10");
+}
+
+#[test]
+fn test_missing_file() {
+    let result = CurrentThreadRunner::run(|ctx| {
+        Stream::<Data>::source(
+            ctx,
+            Reader::file("does-not-exist.csv", false),
+            Format::csv(','),
+            |_, t| t,
+            Duration::zero(),
+            Duration::from_milliseconds(10),
+        )
+        .drain(ctx);
+    });
+    assert!(matches!(result, Err(Error::Io(_))));
+}
+
+#[test]
+fn test_truncated_file() {
+    // The length prefix announces more bytes than the file holds.
+    let path = std::env::temp_dir().join("runtime-test-truncated-file");
+    std::fs::write(&path, [0, 0, 0, 10, 1, 2]).unwrap();
+    let result = CurrentThreadRunner::run(|ctx| {
+        Stream::<Data>::source(
+            ctx,
+            Reader::file(path.clone(), false),
+            Format::bincode(),
+            |_, t| t,
+            Duration::zero(),
+            Duration::from_milliseconds(10),
+        )
+        .drain(ctx);
+    });
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(result, Err(Error::Io(_))));
+}
+
+#[test]
+fn test_panic() {
+    let result = CurrentThreadRunner::run(|ctx| {
+        Stream::from_iter(ctx, 0..10, |i| Time::from_seconds(*i), 1, Duration::zero())
+            .map(ctx, |i| if i == 5 { panic!("Oops") } else { i })
+            .drain(ctx);
+    });
+    let Err(Error::Panic(msg)) = result else {
+        panic!("Unexpected result {:?}", result)
+    };
+    assert_eq!(msg, "Oops");
+}
+
+#[test]
+fn test_error_cancels_workers() {
+    // The source of the second worker never terminates on its own.
+    let result = DataParallelRunner::new([0, 1], |worker, ctx| {
+        Stream::from_iter(ctx, 0.., |i| Time::from_seconds(*i), 1, Duration::zero())
+            .try_map(ctx, move |i| match (worker, i) {
+                (0, 10) => Err("Bad record"),
+                (_, i) => Ok(i),
+            })
+            .drain(ctx);
+    })
+    .run();
+    assert!(matches!(result, Err(Error::Custom(_))));
+}
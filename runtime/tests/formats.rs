@@ -0,0 +1,116 @@
+use runtime::prelude::*;
+
+#[data]
+struct Data {
+    x: i32,
+    s: String,
+}
+
+fn roundtrip(format: Format, name: &str) {
+    let path = std::env::temp_dir().join(format!("runtime-formats-{name}"));
+    let path1 = path.clone();
+    let format1 = format.clone();
+    CurrentThreadRunner::run(move |ctx| {
+        // The newline must not be mistaken for a record boundary.
+        let events = (0..3).map(|x| Data::new(x, format!("a\n{x}")));
+        Stream::from_iter(ctx, events, |_| Time::zero(), 1, Duration::zero()).sink(
+            ctx,
+            Writer::file(path1),
+            format1,
+        );
+    })
+    .unwrap();
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    let path2 = path.clone();
+    CurrentThreadRunner::run(move |ctx| {
+        Stream::<Data>::source(
+            ctx,
+            Reader::file(path2, false),
+            format,
+            |_, t| t,
+            Duration::zero(),
+            Duration::from_milliseconds(10),
+        )
+        .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    std::fs::remove_file(path).unwrap();
+    let result = rx
+        .try_recv()
+        .unwrap()
+        .into_iter()
+        .map(|d| (d.x, d.s))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        result,
+        [
+            (0, "a\n0".to_string()),
+            (1, "a\n1".to_string()),
+            (2, "a\n2".to_string())
+        ]
+    );
+}
+
+#[test]
+fn test_json() {
+    roundtrip(Format::json(), "json");
+}
+
+#[test]
+fn test_json_encode() {
+    use runtime::formats::Encode;
+    let mut encoder = runtime::formats::json::ser::Writer::new();
+    let mut buf = vec![0; 1024];
+    let n = encoder
+        .encode(&Data::new(1, "a".to_string()), &mut buf)
+        .unwrap();
+    assert_eq!(&buf[..n], b"{\"x\":1,\"s\":\"a\"}\n");
+    // The buffer is reused for the next record.
+    let n = encoder
+        .encode(&Data::new(2, "b".to_string()), &mut buf)
+        .unwrap();
+    assert_eq!(&buf[..n], b"{\"x\":2,\"s\":\"b\"}\n");
+}
+
+#[test]
+fn test_msgpack() {
+    roundtrip(Format::msgpack(), "msgpack");
+}
+
+#[test]
+fn test_cbor() {
+    roundtrip(Format::cbor(), "cbor");
+}
+
+#[test]
+fn test_bincode() {
+    roundtrip(Format::bincode(), "bincode");
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Rect { w: u8, h: u8 },
+}
+
+#[test]
+fn test_cbor_data_model() {
+    use runtime::formats::Decode;
+    use runtime::formats::Encode;
+    let value = (
+        vec![Shape::Empty, Shape::Circle(1.5), Shape::Rect { w: 2, h: 3 }],
+        Some(-1i64),
+        None::<String>,
+        u128::MAX,
+    );
+    let mut buf = Vec::new();
+    let n = runtime::formats::cbor::ser::Writer::new()
+        .encode(&value, &mut buf)
+        .unwrap();
+    let result: (Vec<Shape>, Option<i64>, Option<String>, u128) =
+        runtime::formats::cbor::de::Reader::new()
+            .decode(&buf[..n])
+            .unwrap();
+    assert_eq!(result, value);
+}
@@ -0,0 +1,221 @@
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+
+use runtime::prelude::*;
+
+#[data]
+struct Data {
+    x: i32,
+    y: i32,
+}
+
+fn post(addr: SocketAddr, body: &str) -> String {
+    let mut stream = loop {
+        match TcpStream::connect(addr) {
+            Ok(stream) => break stream,
+            Err(_) => std::thread::sleep(std::time::Duration::from_millis(10)),
+        }
+    };
+    write!(
+        stream,
+        "POST / HTTP/1.1\r\nHost: {addr}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+// Reserves a port for a source to listen on.
+fn free_addr() -> SocketAddr {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+}
+
+fn read_http(addr: SocketAddr, format: Format, n: i32) -> Vec<Data> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        Stream::<Data>::source(
+            ctx,
+            Reader::http(addr),
+            format,
+            |_, t| t,
+            Duration::zero(),
+            Duration::from_milliseconds(10),
+        )
+        .take(ctx, n)
+        .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    rx.try_recv().unwrap()
+}
+
+#[test]
+fn test_http_source() {
+    let addr = free_addr();
+    let client = std::thread::spawn(move || {
+        let a = post(addr, "1,2\n3,4\n");
+        let b = post(addr, "5,6");
+        (a, b)
+    });
+    let result = read_http(addr, Format::csv(','), 3);
+    let (a, b) = client.join().unwrap();
+    assert!(a.starts_with("HTTP/1.1 200"));
+    assert!(b.starts_with("HTTP/1.1 200"));
+    let result = result.into_iter().map(|d| (d.x, d.y)).collect::<Vec<_>>();
+    assert_eq!(result, [(1, 2), (3, 4), (5, 6)]);
+}
+
+#[test]
+fn test_http_source_pretty_json() {
+    let addr = free_addr();
+    let client = std::thread::spawn(move || {
+        post(addr, "{\n  \"x\": 1,\n  \"y\": 2\n}\n{\"x\": 3, \"y\": 4}")
+    });
+    let result = read_http(addr, Format::json(), 2);
+    assert!(client.join().unwrap().starts_with("HTTP/1.1 200"));
+    let result = result.into_iter().map(|d| (d.x, d.y)).collect::<Vec<_>>();
+    assert_eq!(result, [(1, 2), (3, 4)]);
+}
+
+#[test]
+fn test_http_source_large_request() {
+    // More records than the source can buffer at once.
+    let addr = free_addr();
+    let body = (0..100).map(|i| format!("{i},{i}\n")).collect::<String>();
+    let client = std::thread::spawn(move || post(addr, &body));
+    let result = read_http(addr, Format::csv(','), 100);
+    assert!(client.join().unwrap().starts_with("HTTP/1.1 200"));
+    let result = result.into_iter().map(|d| d.x).collect::<Vec<_>>();
+    assert_eq!(result, (0..100).collect::<Vec<_>>());
+}
+
+// Responds to the i-th request with `status(i)`, and forwards the content
+// types and bodies of successful requests.
+fn mock_server(
+    listener: TcpListener,
+    tx: std::sync::mpsc::Sender<(String, String)>,
+    status: impl Fn(usize) -> &'static str,
+) {
+    let mut i = 0;
+    for stream in listener.incoming() {
+        let mut reader = BufReader::new(stream.unwrap());
+        loop {
+            let mut length = 0;
+            let mut content_type = String::new();
+            let mut line = String::new();
+            loop {
+                line.clear();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    break;
+                }
+                if let Some(n) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = n.trim().parse().unwrap();
+                }
+                if let Some(t) = line.to_lowercase().strip_prefix("content-type:") {
+                    content_type = t.trim().to_string();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            if line.is_empty() {
+                break;
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            let status = status(i);
+            if status.starts_with("200") {
+                tx.send((content_type, String::from_utf8(body).unwrap()))
+                    .unwrap();
+            }
+            i += 1;
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {status}\r\nContent-Length: 0\r\n\r\n"
+            )
+            .unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_http_sink() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    let (tx, rx) = std::sync::mpsc::channel();
+    // The first request is retried.
+    std::thread::spawn(move || {
+        mock_server(listener, tx, |i| match i {
+            0 => "503 Service Unavailable",
+            _ => "200 OK",
+        })
+    });
+    CurrentThreadRunner::run(|ctx| {
+        let events = (0..3).map(|x| Data::new(x, x));
+        Stream::from_iter(ctx, events, |_| Time::zero(), 1, Duration::zero()).sink(
+            ctx,
+            Writer::http(url.parse().unwrap()),
+            Format::csv(','),
+        );
+    })
+    .unwrap();
+    let (content_type, body) = rx.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+    assert_eq!(content_type, "text/csv");
+    assert_eq!(body, "0,0\n1,1\n2,2\n");
+}
+
+#[test]
+fn test_http_sink_json() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || mock_server(listener, tx, |_| "200 OK"));
+    CurrentThreadRunner::run(|ctx| {
+        let events = (0..3).map(|x| Data::new(x, x));
+        Stream::from_iter(ctx, events, |_| Time::zero(), 1, Duration::zero()).sink(
+            ctx,
+            Writer::http(url.parse().unwrap()),
+            Format::json(),
+        );
+    })
+    .unwrap();
+    let (content_type, body) = rx.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+    assert_eq!(content_type, "application/json");
+    let result = serde_json::Deserializer::from_str(&body)
+        .into_iter::<Data>()
+        .map(|d| {
+            let d = d.unwrap();
+            (d.x, d.y)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(result, [(0, 0), (1, 1), (2, 2)]);
+}
+
+#[test]
+fn test_http_sink_rejected() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    let (tx, _rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || mock_server(listener, tx, |_| "400 Bad Request"));
+    let result = CurrentThreadRunner::run(|ctx| {
+        let events = (0..3).map(|x| Data::new(x, x));
+        Stream::from_iter(ctx, events, |_| Time::zero(), 1, Duration::zero()).sink(
+            ctx,
+            Writer::http(url.parse().unwrap()),
+            Format::csv(','),
+        );
+    });
+    let Err(Error::Custom(e)) = result else {
+        panic!("Unexpected result {:?}", result)
+    };
+    assert_eq!(e.to_string(), "Server responded with 400 Bad Request");
+}
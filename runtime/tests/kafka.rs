@@ -0,0 +1,116 @@
+#![cfg(feature = "kafka")]
+
+use std::net::SocketAddr;
+use std::ops::Range;
+
+use rdkafka::mocking::MockCluster;
+use rdkafka::producer::DefaultProducerContext;
+use rdkafka::types::RDKafkaApiKey;
+use rdkafka::types::RDKafkaRespErr;
+use runtime::prelude::*;
+
+#[data]
+struct Data {
+    x: i32,
+}
+
+fn cluster(
+    topic: &str,
+    partitions: i32,
+) -> (MockCluster<'static, DefaultProducerContext>, SocketAddr) {
+    let cluster = MockCluster::new(1).unwrap();
+    cluster.create_topic(topic, partitions, 1).unwrap();
+    let addr = cluster.bootstrap_servers().parse().unwrap();
+    (cluster, addr)
+}
+
+fn write(addr: SocketAddr, topic: &str, xs: Range<i32>) -> Result<(), Error> {
+    CurrentThreadRunner::run(|ctx| {
+        let events = xs.map(Data::new);
+        Stream::from_iter(
+            ctx,
+            events,
+            |e| Time::from_seconds(e.x as i64),
+            1,
+            Duration::zero(),
+        )
+        .sink(ctx, Writer::kafka(addr, topic.to_string()), Format::json());
+    })
+}
+
+fn read(
+    checkpoint: Checkpoint,
+    addr: SocketAddr,
+    topic: &str,
+    n: i32,
+    f: impl Fn(i32) + Send + 'static,
+) -> Vec<i32> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run_with_checkpoint(checkpoint, |ctx| {
+        Stream::<Data>::source(
+            ctx,
+            Reader::kafka(addr, topic.to_string()),
+            Format::json(),
+            |e, _| Time::from_seconds(e.x as i64),
+            Duration::zero(),
+            Duration::from_milliseconds(10),
+        )
+        .map(ctx, move |e| {
+            f(e.x);
+            e.x
+        })
+        .take(ctx, n)
+        .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    rx.try_recv().unwrap()
+}
+
+#[test]
+fn test_kafka_roundtrip() {
+    let topic = "runtime-test-kafka-roundtrip";
+    let (_cluster, addr) = cluster(topic, 2);
+    write(addr, topic, 0..10).unwrap();
+    let path = std::env::temp_dir().join("runtime-test-kafka-roundtrip");
+    let _ = std::fs::remove_dir_all(&path);
+    let checkpoint = Checkpoint::new(&path, Duration::from_seconds(100));
+    let mut result = read(checkpoint, addr, topic, 10, |_| ());
+    result.sort();
+    assert_eq!(result, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_kafka_delivery_error() {
+    let topic = "runtime-test-kafka-delivery-error";
+    let (cluster, addr) = cluster(topic, 1);
+    let errors = [RDKafkaRespErr::RD_KAFKA_RESP_ERR_MSG_SIZE_TOO_LARGE; 10];
+    cluster.request_errors(RDKafkaApiKey::Produce, &errors);
+    let result = write(addr, topic, 0..10);
+    assert!(matches!(result, Err(Error::Custom(_))));
+}
+
+#[test]
+fn test_kafka_restore() {
+    let topic = "runtime-test-kafka-restore";
+    let (_cluster, addr) = cluster(topic, 1);
+    write(addr, topic, 0..10).unwrap();
+    let path = std::env::temp_dir().join("runtime-test-kafka-restore");
+    let _ = std::fs::remove_dir_all(&path);
+    let checkpoint = Checkpoint::new(&path, Duration::from_milliseconds(1));
+    // Once the first ten records have been read, and snapshotted, another
+    // record ends the first run.
+    let all = read(checkpoint.clone(), addr, topic, 11, move |x| {
+        if x == 9 {
+            std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(100));
+                write(addr, topic, 10..11).unwrap();
+            });
+        }
+    });
+    assert_eq!(all, (0..11).collect::<Vec<_>>());
+    // The restored source resumes after the last record of its snapshot,
+    // instead of from the earliest offset, and `take` resumes its count. The
+    // mock broker only lets the consumer rejoin after a session timeout.
+    let rest = read(checkpoint.resume(), addr, topic, 11, |_| ());
+    assert_eq!(rest, [10]);
+}
@@ -0,0 +1,234 @@
+use std::convert::TryFrom;
+
+use runtime::builtins::keyed_stream::incr_window::Properties;
+use runtime::prelude::*;
+
+#[data]
+struct Data {
+    key: u64,
+    time: Time,
+}
+
+fn events(events: &[(u64, i64)]) -> Vec<Data> {
+    events
+        .iter()
+        .map(|(key, time)| Data::new(*key, Time::from_seconds(*time)))
+        .collect()
+}
+
+#[test]
+fn test_source_lateness() {
+    let (tx0, mut rx0) = tokio::sync::mpsc::channel(1);
+    let (tx1, mut rx1) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = events(&[(0, 10), (0, 20), (0, 12), (0, 30), (0, 15)]);
+        let (stream, late) = Stream::from_iter_with_lateness(
+            ctx,
+            events,
+            |e| e.time,
+            1,
+            Duration::zero(),
+            Lateness::allowed(Duration::from_seconds(10)),
+        );
+        stream.map(ctx, |e| e.time).collect_vec(ctx, tx0);
+        late.map(ctx, |e| e.time).collect_vec(ctx, tx1);
+    })
+    .unwrap();
+    let seconds = [10, 20, 12, 30].map(Time::from_seconds);
+    assert_eq!(rx0.try_recv().unwrap(), seconds);
+    assert_eq!(rx1.try_recv().unwrap(), [Time::from_seconds(15)]);
+}
+
+#[test]
+fn test_window_allowed_lateness() {
+    let (tx0, mut rx0) = tokio::sync::mpsc::channel(1);
+    let (tx1, mut rx1) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = events(&[
+            (0, 1),
+            (0, 2),
+            (0, 11),
+            (0, 21),
+            (0, 3),
+            (0, 31),
+            (0, 4),
+            (0, 12),
+        ]);
+        let (stream, _) = Stream::from_iter_with_lateness(
+            ctx,
+            events,
+            |e| e.time,
+            1,
+            Duration::zero(),
+            Lateness::allowed(Duration::from_seconds(100)),
+        );
+        let (stream, late) = stream.time_tumbling_holistic_window_with_lateness(
+            ctx,
+            Duration::from_seconds(10),
+            Lateness::allowed(Duration::from_seconds(15)),
+            |data, wr| (wr.t0, data.len()),
+        );
+        stream.collect_vec(ctx, tx0);
+        late.map(ctx, |e| e.time).collect_vec(ctx, tx1);
+    })
+    .unwrap();
+    // Windows which receive events within the allowed lateness fire again.
+    let result =
+        [(0, 2), (10, 1), (0, 3), (20, 1), (10, 2)].map(|(t, n)| (Time::from_seconds(t), n));
+    assert_eq!(rx0.try_recv().unwrap(), result);
+    assert_eq!(rx1.try_recv().unwrap(), [Time::from_seconds(4)]);
+}
+
+#[test]
+fn test_keyed_window_side_output() {
+    let (tx0, mut rx0) = tokio::sync::mpsc::channel(1);
+    let (tx1, mut rx1) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = events(&[(0, 1), (1, 2), (0, 12), (1, 5), (0, 25)]);
+        let (stream, _) = Stream::from_iter_with_lateness(
+            ctx,
+            events,
+            |e| e.time,
+            1,
+            Duration::zero(),
+            Lateness::allowed(Duration::from_seconds(100)),
+        );
+        let (stream, late) = stream
+            .keyby(ctx, |e| e.key)
+            .time_tumbling_holistic_window_with_lateness(
+                ctx,
+                Duration::from_seconds(10),
+                Lateness::side_output(),
+                |key, data, wr| (*key, wr.t0, data.len()),
+            );
+        stream.unkey(ctx).collect_vec(ctx, tx0);
+        late.unkey(ctx)
+            .map(ctx, |e| (e.key, e.time))
+            .collect_vec(ctx, tx1);
+    })
+    .unwrap();
+    let mut result = rx0.try_recv().unwrap();
+    result.sort();
+    let expected =
+        [(0, 0, 1), (0, 10, 1), (1, 0, 1)].map(|(k, t, n)| (k, Time::from_seconds(t), n));
+    assert_eq!(result, expected);
+    assert_eq!(rx1.try_recv().unwrap(), [(1, Time::from_seconds(5))]);
+}
+
+#[test]
+fn test_session_window_side_output() {
+    let (tx0, mut rx0) = tokio::sync::mpsc::channel(1);
+    let (tx1, mut rx1) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = events(&[(0, 1), (0, 2), (0, 20), (0, 4), (0, 15), (0, 30)]);
+        let (stream, _) = Stream::from_iter_with_lateness(
+            ctx,
+            events,
+            |e| e.time,
+            1,
+            Duration::zero(),
+            Lateness::allowed(Duration::from_seconds(100)),
+        );
+        let (stream, late) = stream.time_session_holistic_window_with_lateness(
+            ctx,
+            Duration::from_seconds(5),
+            FireOnceLateness::side_output(),
+            |data, wr| (wr.t0, data.len()),
+        );
+        stream.collect_vec(ctx, tx0);
+        late.map(ctx, |e| e.time).collect_vec(ctx, tx1);
+    })
+    .unwrap();
+    // The event at 15 is behind the watermark, but extends an open session.
+    let result = [(1, 2), (15, 2)].map(|(t, n)| (Time::from_seconds(t), n));
+    assert_eq!(rx0.try_recv().unwrap(), result);
+    assert_eq!(rx1.try_recv().unwrap(), [Time::from_seconds(4)]);
+}
+
+#[test]
+fn test_sliding_invertible_window_side_output() {
+    let (tx0, mut rx0) = tokio::sync::mpsc::channel(1);
+    let (tx1, mut rx1) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = events(&[(0, 1), (0, 2), (0, 25), (0, 3)]);
+        let (stream, _) = Stream::from_iter_with_lateness(
+            ctx,
+            events,
+            |e| e.time,
+            1,
+            Duration::zero(),
+            Lateness::allowed(Duration::from_seconds(100)),
+        );
+        let (stream, late) = stream.time_sliding_commutative_invertible_window_with_lateness(
+            ctx,
+            Duration::from_seconds(10),
+            Duration::from_seconds(5),
+            FireOnceLateness::side_output(),
+            0,
+            |_| 1,
+            |a, b| a + b,
+            |n, wr| (wr.t0, *n),
+            |a, b| a - b,
+        );
+        stream.collect_vec(ctx, tx0);
+        late.map(ctx, |e| e.time).collect_vec(ctx, tx1);
+    })
+    .unwrap();
+    // The window of the event at 25 stays open past the last watermark.
+    assert_eq!(rx0.try_recv().unwrap(), [(Time::zero(), 2)]);
+    assert_eq!(rx1.try_recv().unwrap(), [Time::from_seconds(3)]);
+}
+
+#[test]
+fn test_incr_window_side_output() {
+    let (tx0, mut rx0) = tokio::sync::mpsc::channel(1);
+    let (tx1, mut rx1) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = events(&[(0, 1), (1, 2), (0, 25), (1, 3), (0, 22)]);
+        let (stream, _) = Stream::from_iter_with_lateness(
+            ctx,
+            events,
+            |e| e.time,
+            1,
+            Duration::zero(),
+            Lateness::allowed(Duration::from_seconds(100)),
+        );
+        let properties = Properties {
+            associative: true,
+            commutative: true,
+            inverse: None,
+        };
+        let (stream, late) = stream.keyby(ctx, |e| e.key).incr_window_with_lateness(
+            ctx,
+            Window::Tumbling {
+                length: Duration::from_seconds(10),
+            },
+            FireOnceLateness::side_output(),
+            |_| 1,
+            |a, b| a + b,
+            |key, n, range| (key, range.start, *n),
+            properties,
+        );
+        stream.unkey(ctx).collect_vec(ctx, tx0);
+        late.unkey(ctx)
+            .map(ctx, |e| (e.key, e.time))
+            .collect_vec(ctx, tx1);
+    })
+    .unwrap();
+    let mut result = rx0.try_recv().unwrap();
+    result.sort();
+    assert_eq!(result, [(0, Time::zero(), 1), (1, Time::zero(), 1)]);
+    assert_eq!(rx1.try_recv().unwrap(), [(1, Time::from_seconds(3))]);
+}
+
+#[test]
+fn test_fire_once_lateness() {
+    let lateness = FireOnceLateness::try_from(Lateness::side_output()).unwrap();
+    assert_eq!(lateness, FireOnceLateness::SideOutput);
+    assert_eq!(Lateness::from(lateness), Lateness::SideOutput);
+    let allowed = Lateness::allowed(Duration::from_seconds(10));
+    assert!(matches!(
+        FireOnceLateness::try_from(allowed),
+        Err(Error::Custom(_))
+    ));
+}
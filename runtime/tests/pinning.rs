@@ -0,0 +1,77 @@
+use runtime::prelude::*;
+use runtime::runner::pinning::numa_nodes;
+
+#[test]
+fn test_placement() {
+    let nodes = numa_nodes();
+    let available = nodes.concat();
+    let node = |core: &usize| nodes.iter().position(|n| n.contains(core)).unwrap();
+    // Compact fills up each node before the next.
+    let compact = Placement::Compact.cores(available.len()).unwrap();
+    assert_eq!(compact, available);
+    // Spread places the first worker of each node before the second of any.
+    let spread = Placement::Spread.cores(nodes.len()).unwrap();
+    let mut used = spread.iter().map(node).collect::<Vec<_>>();
+    used.sort();
+    assert_eq!(used, (0..nodes.len()).collect::<Vec<_>>());
+    // Both use every core once if there are as many workers as cores.
+    let mut spread = Placement::Spread.cores(available.len()).unwrap();
+    spread.sort();
+    let mut compact = compact;
+    compact.sort();
+    assert_eq!(spread, compact);
+}
+
+#[test]
+fn test_placement_cores() {
+    let core = numa_nodes().concat()[0];
+    // Cores are reused round-robin, and unavailable cores are skipped.
+    let cores = Placement::Cores(vec![core, 100_000]).cores(3).unwrap();
+    assert_eq!(cores, [core, core, core]);
+    assert!(Placement::Cores(vec![100_000]).cores(1).is_err());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_pinned_workers() {
+    let (tx0, mut rx0) = tokio::sync::mpsc::channel(1);
+    let (tx1, mut rx1) = tokio::sync::mpsc::channel(1);
+    let core = *numa_nodes().concat().last().unwrap();
+    DataParallelRunner::new_pinned(Placement::Cores(vec![core]), [tx0, tx1], |tx, ctx| {
+        let cores = std::thread::available_parallelism().unwrap().get();
+        Stream::from_iter(ctx, [cores], |_| Time::zero(), 1, Duration::zero()).collect_vec(ctx, tx);
+    })
+    .unwrap()
+    .run()
+    .unwrap();
+    // A thread pinned to one core may only run on that core.
+    assert_eq!(rx0.try_recv().unwrap(), [1]);
+    assert_eq!(rx1.try_recv().unwrap(), [1]);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_pinned_workers_with_checkpoint() {
+    let path = std::env::temp_dir().join("runtime-test-pinned-workers-with-checkpoint");
+    let _ = std::fs::remove_dir_all(&path);
+    let checkpoint = Checkpoint::new(&path, Duration::from_microseconds(100));
+    DataParallelRunner::new_pinned_with_checkpoint(
+        Placement::Compact,
+        checkpoint,
+        [0, 1],
+        |_, ctx| {
+            Stream::from_iter(
+                ctx,
+                0..1000,
+                |i| Time::from_seconds(*i),
+                1,
+                Duration::zero(),
+            )
+            .drain(ctx);
+        },
+    )
+    .unwrap()
+    .run()
+    .unwrap();
+    assert!(runtime::state::Database::new(&path).latest().is_some());
+}
@@ -0,0 +1,75 @@
+use runtime::builtins::keyed_stream::process::MapState;
+use runtime::builtins::keyed_stream::process::Timer;
+use runtime::builtins::keyed_stream::process::ValueState;
+use runtime::prelude::stream::Event;
+use runtime::prelude::*;
+
+#[data]
+struct Data {
+    key: u64,
+    time: Time,
+}
+
+#[test]
+fn test_event_time_timers() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = [(0, 1), (0, 2), (1, 3), (0, 12), (1, 25)]
+            .map(|(key, t)| Data::new(key, Time::from_seconds(t)));
+        let size = Duration::from_seconds(10);
+        Stream::from_iter(ctx, events, |e| e.time, 1, Duration::zero())
+            .keyby(ctx, |e| e.key)
+            .process(
+                ctx,
+                move |ctx, e: Data| {
+                    // Counts the records of each key per window of ten seconds.
+                    let end = e.time.div_floor(size) * size + size;
+                    let state: &mut MapState<Time, usize> = ctx.state();
+                    let count = state.get(&end).unwrap_or(&0) + 1;
+                    state.put(end, count);
+                    ctx.register_event_timer(end);
+                },
+                |ctx, timer| {
+                    let Timer::EventTime(end) = timer else {
+                        panic!("Unexpected timer {:?}", timer)
+                    };
+                    let count = ctx.state().remove(&end).unwrap();
+                    ctx.emit((*ctx.key(), count));
+                },
+            )
+            .unkey(ctx)
+            .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    // The timer of key 1 at 30 is never reached by the watermark.
+    assert_eq!(rx.try_recv().unwrap(), [(0, 2), (1, 1), (0, 1)]);
+}
+
+#[test]
+fn test_processing_time_timers() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        ctx.operator(|tx| async move {
+            tx.send(Event::Data(Time::zero(), Data::new(0, Time::zero())))
+                .await?;
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            tx.send(Event::Sentinel).await
+        })
+        .keyby(ctx, |e| e.key)
+        .process(
+            ctx,
+            |ctx, _| {
+                ctx.register_processing_timer(Time::now() + Duration::from_milliseconds(50));
+            },
+            |ctx, timer| {
+                assert!(matches!(timer, Timer::ProcessingTime(_)));
+                let _: &mut ValueState<()> = ctx.state();
+                ctx.emit(*ctx.key());
+            },
+        )
+        .unkey(ctx)
+        .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    assert_eq!(rx.try_recv().unwrap(), [0]);
+}
@@ -0,0 +1,54 @@
+use runtime::prelude::*;
+
+#[data]
+struct Data {
+    key: u64,
+    time: Time,
+}
+
+#[test]
+fn test_shuffle() {
+    let (tx0, mut rx0) = tokio::sync::mpsc::channel(1);
+    let (tx1, mut rx1) = tokio::sync::mpsc::channel(1);
+    DataParallelRunner::new([tx0, tx1], |tx, ctx| {
+        // Both workers see every key.
+        let events = (0..100).map(|i| Data::new(i % 4, Time::from_seconds(i as i64)));
+        Stream::from_iter(ctx, events, |e| e.time, 10, Duration::zero())
+            .keyby(ctx, |e| e.key)
+            .shuffle(ctx)
+            .time_tumbling_holistic_window(ctx, Duration::from_seconds(50), |key, data, _| {
+                (*key, data.len())
+            })
+            .unkey(ctx)
+            .collect_vec(ctx, tx);
+    })
+    .run().unwrap();
+    let mut result0 = rx0.try_recv().unwrap();
+    let mut result1 = rx1.try_recv().unwrap();
+    // Each key is owned by one worker, which counts its records from both. Only
+    // the first window is closed by a watermark.
+    assert!(result0.iter().all(|(k, _)| !result1.iter().any(|(k1, _)| k == k1)));
+    result0.append(&mut result1);
+    result0.sort();
+    assert_eq!(result0, [(0, 26), (1, 26), (2, 24), (3, 24)]);
+}
+
+#[test]
+fn test_shuffle_current_thread() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = (0..100).map(|i| Data::new(i % 4, Time::from_seconds(i as i64)));
+        Stream::from_iter(ctx, events, |e| e.time, 10, Duration::zero())
+            .keyby(ctx, |e| e.key)
+            .shuffle(ctx)
+            .time_tumbling_holistic_window(ctx, Duration::from_seconds(50), |key, data, _| {
+                (*key, data.len())
+            })
+            .unkey(ctx)
+            .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    let mut result = rx.try_recv().unwrap();
+    result.sort();
+    assert_eq!(result, [(0, 13), (1, 13), (2, 12), (3, 12)]);
+}
@@ -0,0 +1,28 @@
+use runtime::prelude::*;
+
+#[data]
+struct Data {
+    key: u64,
+    time: Time,
+}
+
+#[test]
+fn test_task_parallel() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    let runner = TaskParallelRunner::new_with_workers(4, |ctx| {
+        let events = (0..100).map(|i| Data::new(i % 4, Time::from_seconds(i as i64)));
+        Stream::from_iter(ctx, events, |e| e.time, 10, Duration::zero())
+            .keyby(ctx, |e| e.key)
+            .time_tumbling_holistic_window(ctx, Duration::from_seconds(50), |key, data, _| {
+                (*key, data.len())
+            })
+            .unkey(ctx)
+            .collect_vec(ctx, tx);
+    });
+    // The dataflow does not start before `run`.
+    assert!(rx.try_recv().is_err());
+    runner.run().unwrap();
+    let mut result = rx.try_recv().unwrap();
+    result.sort();
+    assert_eq!(result, [(0, 13), (1, 13), (2, 12), (3, 12)]);
+}
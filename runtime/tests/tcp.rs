@@ -0,0 +1,121 @@
+use std::io::Read;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::net::TcpStream;
+
+use runtime::prelude::*;
+
+#[data]
+struct Data {
+    x: i32,
+    y: i32,
+}
+
+fn free_addr() -> SocketAddr {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+}
+
+fn connect(addr: SocketAddr) -> TcpStream {
+    loop {
+        match TcpStream::connect(addr) {
+            Ok(stream) => break stream,
+            Err(_) => std::thread::sleep(std::time::Duration::from_millis(10)),
+        }
+    }
+}
+
+fn read_tcp(addr: SocketAddr, watch: bool, n: i32) -> Vec<(i32, i32)> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        Stream::<Data>::source(
+            ctx,
+            Reader::tcp(addr, watch),
+            Format::csv(','),
+            |_, t| t,
+            Duration::zero(),
+            Duration::from_milliseconds(10),
+        )
+        .take(ctx, n)
+        .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    let mut result = rx
+        .try_recv()
+        .unwrap()
+        .into_iter()
+        .map(|d| (d.x, d.y))
+        .collect::<Vec<_>>();
+    result.sort();
+    result
+}
+
+#[test]
+fn test_tcp_source_many_producers() {
+    let addr = free_addr();
+    let producers = (0..2)
+        .map(|i| {
+            std::thread::spawn(move || {
+                let mut stream = connect(addr);
+                write!(stream, "{i},0\n{i},1\n").unwrap();
+            })
+        })
+        .collect::<Vec<_>>();
+    let result = read_tcp(addr, true, 4);
+    producers.into_iter().for_each(|p| p.join().unwrap());
+    assert_eq!(result, [(0, 0), (0, 1), (1, 0), (1, 1)]);
+}
+
+#[test]
+fn test_tcp_source_ends_after_producers() {
+    let addr = free_addr();
+    let producer = std::thread::spawn(move || {
+        let mut stream = connect(addr);
+        write!(stream, "0,0\n0,1\n").unwrap();
+    });
+    let result = read_tcp(addr, false, 10);
+    producer.join().unwrap();
+    assert_eq!(result, [(0, 0), (0, 1)]);
+}
+
+#[test]
+fn test_tcp_sink_reconnect() {
+    // Reserve a port, but only start listening once the sink has failed to connect.
+    let addr = free_addr();
+    let consumer = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(300));
+        let listener = TcpListener::bind(addr).unwrap();
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut output = String::new();
+        stream.read_to_string(&mut output).unwrap();
+        output
+    });
+    CurrentThreadRunner::run(|ctx| {
+        let events = (0..3).map(|x| Data::new(x, x));
+        Stream::from_iter(ctx, events, |_| Time::zero(), 1, Duration::zero()).sink(
+            ctx,
+            Writer::tcp(addr),
+            Format::csv(','),
+        );
+    })
+    .unwrap();
+    assert_eq!(consumer.join().unwrap(), "0,0\n1,1\n2,2\n");
+}
+
+#[test]
+fn test_tcp_sink_overflow() {
+    // Nothing listens on the port, so every record is buffered.
+    let addr = free_addr();
+    let result = CurrentThreadRunner::run(|ctx| {
+        let events = (0..100_001).map(|x| Data::new(x, x));
+        Stream::from_iter(ctx, events, |_| Time::zero(), 1000, Duration::zero()).sink(
+            ctx,
+            Writer::tcp(addr),
+            Format::csv(','),
+        );
+    });
+    assert!(matches!(result, Err(Error::Custom(_))));
+}
@@ -0,0 +1,67 @@
+use runtime::prelude::*;
+
+fn from_seconds(ctx: &mut Context, seconds: &[i64]) -> Stream<i64> {
+    let seconds = seconds.to_vec();
+    Stream::from_iter(
+        ctx,
+        seconds,
+        |s| Time::from_seconds(*s),
+        1,
+        Duration::zero(),
+    )
+}
+
+#[test]
+fn test_union() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let inputs = vec![
+            from_seconds(ctx, &[1, 11, 21]),
+            from_seconds(ctx, &[2, 12]),
+            from_seconds(ctx, &[3]),
+        ];
+        Stream::union(ctx, inputs)
+            .time_tumbling_holistic_window(ctx, Duration::from_seconds(10), |data, wr| {
+                let mut data = data.to_vec();
+                data.sort();
+                (wr.t0, data)
+            })
+            .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    // Finished inputs no longer hold back the watermark, which ends at 21.
+    let result =
+        [(0, vec![1, 2, 3]), (10, vec![11, 12])].map(|(t, data)| (Time::from_seconds(t), data));
+    assert_eq!(rx.try_recv().unwrap(), result);
+}
+
+#[test]
+fn test_connect_process() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let left = from_seconds(ctx, &[1, 2, 3]);
+        let right = from_seconds(ctx, &[11, 12]).map(ctx, |s| s.to_string());
+        left.connect(right)
+            .process(
+                ctx,
+                |ctx, n| {
+                    let count: &mut usize = ctx.state();
+                    *count += 1;
+                    ctx.emit(n);
+                },
+                |ctx, s| {
+                    *ctx.state() += 1;
+                    ctx.emit(s.parse::<i64>().unwrap());
+                },
+            )
+            .time_tumbling_holistic_window(ctx, Duration::from_seconds(10), |data, wr| {
+                let mut data = data.to_vec();
+                data.sort();
+                (wr.t0, data)
+            })
+            .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    let result = [(0, vec![1, 2, 3])].map(|(t, data)| (Time::from_seconds(t), data));
+    assert_eq!(rx.try_recv().unwrap(), result);
+}
@@ -0,0 +1,205 @@
+#![cfg(feature = "wasm")]
+
+use runtime::prelude::*;
+
+const COMPONENT: &str = r#"
+(component
+  (core module $m
+    (memory (export "memory") 1)
+    (func (export "double") (param i64) (result i64)
+      (i64.mul (local.get 0) (i64.const 2)))
+    (func (export "is-even") (param i64) (result i32)
+      (i64.eqz (i64.rem_u (local.get 0) (i64.const 2))))
+    (func (export "half") (param i64) (result i32)
+      (if (i64.eqz (i64.rem_u (local.get 0) (i64.const 2)))
+        (then
+          (i32.store8 (i32.const 0) (i32.const 1))
+          (i64.store (i32.const 8) (i64.div_u (local.get 0) (i64.const 2))))
+        (else
+          (i32.store8 (i32.const 0) (i32.const 0))))
+      (i32.const 0))
+    (func (export "trap") (param i64) (result i64)
+      unreachable))
+  (core instance $i (instantiate $m))
+  (func (export "double") (param "x" u64) (result u64)
+    (canon lift (core func $i "double")))
+  (func (export "is-even") (param "x" u64) (result bool)
+    (canon lift (core func $i "is-even")))
+  (func (export "half") (param "x" u64) (result (option u64))
+    (canon lift (core func $i "half") (memory $i "memory")))
+  (func (export "trap") (param "x" u64) (result u64)
+    (canon lift (core func $i "trap")))
+)
+"#;
+
+fn numbers(ctx: &mut Context) -> Stream<u64> {
+    Stream::from_iter(
+        ctx,
+        0..6,
+        |i| Time::from_seconds(*i as i64),
+        1,
+        Duration::zero(),
+    )
+}
+
+#[test]
+fn test_wasm_operators() {
+    let component = WasmComponent::new(COMPONENT).unwrap();
+    let (tx0, mut rx0) = tokio::sync::mpsc::channel(1);
+    let (tx1, mut rx1) = tokio::sync::mpsc::channel(1);
+    let (tx2, mut rx2) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        numbers(ctx)
+            .wasm_map::<u64>(ctx, &component, "double")
+            .unwrap()
+            .collect_vec(ctx, tx0);
+        numbers(ctx)
+            .wasm_filter(ctx, &component, "is-even")
+            .unwrap()
+            .collect_vec(ctx, tx1);
+        numbers(ctx)
+            .wasm_filter_map::<u64>(ctx, &component, "half")
+            .unwrap()
+            .collect_vec(ctx, tx2);
+    })
+    .unwrap();
+    assert_eq!(rx0.try_recv().unwrap(), [0, 2, 4, 6, 8, 10]);
+    assert_eq!(rx1.try_recv().unwrap(), [0, 2, 4]);
+    assert_eq!(rx2.try_recv().unwrap(), [0, 1, 2]);
+}
+
+#[test]
+fn test_wasm_type_check() {
+    let component = WasmComponent::new(COMPONENT).unwrap();
+    CurrentThreadRunner::run(|ctx| {
+        // The signature is checked when the dataflow is built.
+        assert!(numbers(ctx)
+            .wasm_map::<String>(ctx, &component, "double")
+            .is_err());
+        assert!(numbers(ctx)
+            .wasm_map::<u64>(ctx, &component, "missing")
+            .is_err());
+    })
+    .unwrap();
+}
+
+#[test]
+fn test_wasm_trap() {
+    let component = WasmComponent::new(COMPONENT).unwrap();
+    let result = CurrentThreadRunner::run(|ctx| {
+        numbers(ctx)
+            .wasm_map::<u64>(ctx, &component, "trap")
+            .unwrap()
+            .drain(ctx);
+    });
+    assert!(matches!(result, Err(Error::Custom(_))));
+}
+
+const DYN_COMPONENT: &str = r#"
+(component
+  (core module $m
+    (memory (export "memory") 1)
+    (func (export "price") (param i64 i64) (result i64)
+      (local.get 1))
+    (func (export "expensive") (param i64 i64) (result i32)
+      (i64.gt_u (local.get 1) (i64.const 100)))
+    (func (export "double-price") (param i64 i64) (result i32)
+      (i64.store (i32.const 0) (local.get 0))
+      (i64.store (i32.const 8) (i64.mul (local.get 1) (i64.const 2)))
+      (i32.const 0))
+    (func (export "add") (param i64 i64) (result i64)
+      (i64.add (local.get 0) (local.get 1))))
+  (core instance $i (instantiate $m))
+  (type $bid' (record (field "auction" u64) (field "bid-price" u64)))
+  (export $bid "bid" (type $bid'))
+  (func (export "price") (param "b" $bid) (result u64)
+    (canon lift (core func $i "price")))
+  (func (export "expensive") (param "b" $bid) (result bool)
+    (canon lift (core func $i "expensive")))
+  (func (export "double-price") (param "b" $bid) (result $bid)
+    (canon lift (core func $i "double-price") (memory $i "memory")))
+  (func (export "add") (param "a" u64) (param "b" u64) (result u64)
+    (canon lift (core func $i "add")))
+)
+"#;
+
+#[data]
+struct Bid {
+    auction: u64,
+    bid_price: u64,
+}
+
+fn bids(ctx: &mut Context) -> Stream<Bid> {
+    Stream::from_iter(
+        ctx,
+        [Bid::new(1, 50), Bid::new(2, 150)],
+        |b| Time::from_seconds(b.auction as i64),
+        1,
+        Duration::zero(),
+    )
+}
+
+#[test]
+fn test_wasm_dyn_operators() {
+    let component = WasmComponent::new(DYN_COMPONENT).unwrap();
+    let (tx0, mut rx0) = tokio::sync::mpsc::channel(1);
+    let (tx1, mut rx1) = tokio::sync::mpsc::channel(1);
+    let (tx2, mut rx2) = tokio::sync::mpsc::channel(1);
+    let (tx3, mut rx3) = tokio::sync::mpsc::channel(1);
+    let (tx4, mut rx4) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        bids(ctx)
+            .wasm_dyn_map::<u64>(ctx, &component, "price")
+            .unwrap()
+            .collect_vec(ctx, tx0);
+        bids(ctx)
+            .wasm_dyn_map::<Bid>(ctx, &component, "double-price")
+            .unwrap()
+            .collect_vec(ctx, tx1);
+        bids(ctx)
+            .wasm_dyn_filter(ctx, &component, "expensive")
+            .unwrap()
+            .collect_vec(ctx, tx2);
+        bids(ctx)
+            .map(ctx, |b| (b.auction, b.bid_price))
+            .wasm_dyn_map::<u64>(ctx, &component, "add")
+            .unwrap()
+            .collect_vec(ctx, tx3);
+        let component = WasmComponent::new(COMPONENT).unwrap();
+        numbers(ctx)
+            .wasm_dyn_filter_map::<u64>(ctx, &component, "half")
+            .unwrap()
+            .collect_vec(ctx, tx4);
+    })
+    .unwrap();
+    assert_eq!(rx0.try_recv().unwrap(), [50, 150]);
+    let prices = |bids: Vec<Bid>| {
+        bids.iter()
+            .map(|b| (b.auction, b.bid_price))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(prices(rx1.try_recv().unwrap()), [(1, 100), (2, 300)]);
+    assert_eq!(prices(rx2.try_recv().unwrap()), [(2, 150)]);
+    assert_eq!(rx3.try_recv().unwrap(), [51, 152]);
+    assert_eq!(rx4.try_recv().unwrap(), [0, 1, 2]);
+}
+
+#[test]
+fn test_wasm_dyn_type_check() {
+    let component = WasmComponent::new(DYN_COMPONENT).unwrap();
+    let result = CurrentThreadRunner::run(|ctx| {
+        assert!(bids(ctx).wasm_dyn_filter(ctx, &component, "price").is_err());
+        assert!(bids(ctx)
+            .wasm_dyn_filter_map::<u64>(ctx, &component, "price")
+            .is_err());
+        // The records are only checked when they are converted.
+        numbers(ctx)
+            .wasm_dyn_map::<u64>(ctx, &component, "price")
+            .unwrap()
+            .drain(ctx);
+    });
+    let Err(Error::Custom(e)) = result else {
+        panic!("Unexpected result {:?}", result)
+    };
+    assert_eq!(e.to_string(), "Expected record, found `0`");
+}
@@ -0,0 +1,109 @@
+use runtime::prelude::*;
+
+#[data]
+struct Data {
+    key: u64,
+    time: Time,
+}
+
+#[data]
+struct Marked {
+    time: Time,
+    marker: bool,
+}
+
+#[test]
+fn test_punctuated() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = [
+            (1, false),
+            (2, false),
+            (11, false),
+            (10, true),
+            (5, false),
+            (12, false),
+            (25, false),
+            (20, true),
+        ]
+        .map(|(t, marker)| Marked::new(Time::from_seconds(t), marker));
+        let watermarks = WatermarkStrategy::punctuated(|e: &Marked| e.marker.then_some(e.time));
+        Stream::from_iter(ctx, events, |e| e.time, 100, watermarks)
+            .time_tumbling_holistic_window(ctx, Duration::from_seconds(10), |data, wr| {
+                (wr.t0, data.len())
+            })
+            .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    // The record at 5 is behind the marker at 10, and the window at 20 is never closed.
+    let result = [(0, 2), (10, 3)].map(|(t, n)| (Time::from_seconds(t), n));
+    assert_eq!(rx.try_recv().unwrap(), result);
+}
+
+fn late_events(watermarks: WatermarkStrategy<Data>) -> Vec<Time> {
+    let (tx0, _rx0) = tokio::sync::mpsc::channel(1);
+    let (tx1, mut rx1) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = [
+            (1, 1),
+            (0, 10),
+            (0, 20),
+            (1, 5),
+            (0, 30),
+            (1, 12),
+            (0, 31),
+            (1, 25),
+        ]
+        .map(|(key, t)| Data::new(key, Time::from_seconds(t)));
+        let (stream, late) = Stream::from_iter_with_lateness(
+            ctx,
+            events,
+            |e| e.time,
+            1,
+            watermarks,
+            Lateness::side_output(),
+        );
+        stream.collect_vec(ctx, tx0);
+        late.map(ctx, |e| e.time).collect_vec(ctx, tx1);
+    })
+    .unwrap();
+    rx1.try_recv().unwrap()
+}
+
+#[test]
+fn test_per_partition() {
+    // A slow partition holds back the watermark of the whole source.
+    let late = late_events(WatermarkStrategy::per_partition(
+        |e: &Data| e.key,
+        Duration::zero(),
+    ));
+    assert!(late.is_empty());
+    let late = late_events(Duration::zero().into());
+    assert_eq!(late, [5, 12, 25].map(Time::from_seconds));
+}
+
+#[test]
+fn test_idleness() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = vec![(1, 1), (0, 10), (0, 20), (0, 30)]
+            .into_iter()
+            .map(|(key, t)| Data::new(key, Time::from_seconds(t)))
+            .inspect(|e| {
+                if e.time == Time::from_seconds(30) {
+                    std::thread::sleep(std::time::Duration::from_millis(200));
+                }
+            });
+        let watermarks = WatermarkStrategy::per_partition(|e: &Data| e.key, Duration::zero())
+            .with_idleness(Duration::from_milliseconds(50));
+        Stream::from_iter(ctx, events, |e| e.time, 1, watermarks)
+            .time_tumbling_holistic_window(ctx, Duration::from_seconds(10), |data, wr| {
+                (wr.t0, data.len())
+            })
+            .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    // Partition 1 goes idle, so it no longer holds back the watermark.
+    let result = [(0, 1), (10, 1), (20, 1)].map(|(t, n)| (Time::from_seconds(t), n));
+    assert_eq!(rx.try_recv().unwrap(), result);
+}
@@ -1,3 +1,4 @@
+use runtime::builtins::keyed_stream::incr_window::Properties;
 use runtime::prelude::stream::Event;
 use runtime::prelude::*;
 
@@ -28,5 +29,292 @@ fn test_tumbling_holistic0() {
                     Event::Data(Time::zero(), (0, 0, 0)),
                 ],
             );
-    });
+    })
+    .unwrap();
+}
+
+#[test]
+fn test_session_holistic_merge() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = [0, 20, 10, 100].map(|t| Data::new(t, Time::from_seconds(t)));
+        Stream::from_iter(ctx, events, |e| e.time, 1, Duration::from_seconds(50))
+            .time_session_holistic_window(ctx, Duration::from_seconds(10), |data, wr| {
+                (data.len(), wr.t0, wr.t1)
+            })
+            .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    let result = rx.try_recv().unwrap();
+    assert_eq!(result, [(3, Time::zero(), Time::from_seconds(30))]);
+}
+
+#[test]
+fn test_session_incremental() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = [0, 20, 10, 100].map(|t| Data::new(t, Time::from_seconds(t)));
+        let properties = Properties {
+            associative: true,
+            commutative: false,
+            inverse: None,
+        };
+        Stream::from_iter(ctx, events, |e| e.time, 1, Duration::from_seconds(50))
+            .keyby(ctx, |_| ())
+            .incr_window(
+                ctx,
+                Window::Session {
+                    gap: Duration::from_seconds(10),
+                },
+                |e| vec![e.value],
+                |a, b| [a.as_slice(), b.as_slice()].concat(),
+                |_, p, _| p.clone(),
+                properties,
+            )
+            .unkey(ctx)
+            .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    let result = rx.try_recv().unwrap();
+    assert_eq!(result, [vec![0, 10, 20]]);
+}
+
+#[test]
+fn test_session_incremental_commutative() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = [0, 20, 10, 100].map(|t| Data::new(t, Time::from_seconds(t)));
+        let properties = Properties {
+            associative: true,
+            commutative: true,
+            inverse: None,
+        };
+        Stream::from_iter(ctx, events, |e| e.time, 1, Duration::from_seconds(50))
+            .keyby(ctx, |_| ())
+            .incr_window(
+                ctx,
+                Window::Session {
+                    gap: Duration::from_seconds(10),
+                },
+                |e| e.value,
+                |a, b| a + b,
+                |_, p, wr| (*p, wr),
+                properties,
+            )
+            .unkey(ctx)
+            .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    let result = rx.try_recv().unwrap();
+    assert_eq!(result, [(30, Time::zero()..Time::from_seconds(30))]);
+}
+
+fn incr_sliding(properties: Properties<i64>) -> Vec<(u64, i64)> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = (0..100).map(|i| Data::new(i, Time::from_seconds(i)));
+        let assigner = Window::Sliding {
+            duration: Duration::from_seconds(10),
+            step: Duration::from_seconds(3),
+        };
+        Stream::from_iter(ctx, events, |e| e.time, 10, Duration::zero())
+            .keyby(ctx, |e| (e.value % 2) as u64)
+            .incr_window(
+                ctx,
+                assigner,
+                |e| e.value,
+                |a, b| a + b,
+                |k, p, _| (k, *p),
+                properties,
+            )
+            .unkey(ctx)
+            .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    let mut result = rx.try_recv().unwrap();
+    result.sort();
+    result
+}
+
+#[test]
+fn test_incr_sliding_strategies() {
+    let properties = |associative, commutative| Properties {
+        associative,
+        commutative,
+        inverse: None,
+    };
+    let expected = incr_sliding(properties(false, false));
+    assert!(expected.contains(&(0, 2 + 4 + 6 + 8)));
+    assert!(expected.contains(&(1, 1 + 3 + 5 + 7 + 9)));
+    assert_eq!(incr_sliding(properties(true, false)), expected);
+    assert_eq!(incr_sliding(properties(true, true)), expected);
+    let invertible = Properties {
+        inverse: Some(|a: &i64, b: &i64| a - b),
+        ..properties(true, true)
+    };
+    assert_eq!(incr_sliding(invertible), expected);
+}
+
+#[test]
+fn test_incr_tumbling_boundaries() {
+    for commutative in [false, true] {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+        CurrentThreadRunner::run(|ctx| {
+            let events = [0, 9, 10, 19, 20, 100].map(|t| Data::new(t, Time::from_seconds(t)));
+            let properties = Properties {
+                associative: true,
+                commutative,
+                inverse: None,
+            };
+            Stream::from_iter(ctx, events, |e| e.time, 1, Duration::zero())
+                .keyby(ctx, |_| ())
+                .incr_window(
+                    ctx,
+                    Window::Tumbling {
+                        length: Duration::from_seconds(10),
+                    },
+                    |e| e.value,
+                    |a, b| a + b,
+                    |_, p, wr| (*p, wr),
+                    properties,
+                )
+                .unkey(ctx)
+                .collect_vec(ctx, tx);
+        })
+        .unwrap();
+        let s = Time::from_seconds;
+        let result = rx.try_recv().unwrap();
+        assert_eq!(
+            result,
+            [(9, s(0)..s(10)), (29, s(10)..s(20)), (20, s(20)..s(30))]
+        );
+    }
+}
+
+#[test]
+fn test_incr_moving() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = (0..10).map(|i| Data::new(i, Time::from_seconds(i)));
+        let properties = Properties {
+            associative: true,
+            commutative: false,
+            inverse: None,
+        };
+        Stream::from_iter(ctx, events, |e| e.time, 1, Duration::zero())
+            .keyby(ctx, |_| ())
+            .incr_window(
+                ctx,
+                Window::Moving { length: 3, step: 2 },
+                |e| vec![e.value],
+                |a, b| [a.as_slice(), b.as_slice()].concat(),
+                |_, p, _| p.clone(),
+                properties,
+            )
+            .unkey(ctx)
+            .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    let result = rx.try_recv().unwrap();
+    assert_eq!(
+        result,
+        [vec![0, 1, 2], vec![2, 3, 4], vec![4, 5, 6], vec![6, 7, 8]]
+    );
+}
+
+#[test]
+fn test_incr_moving_step_exceeds_length() {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    CurrentThreadRunner::run(|ctx| {
+        let events = (0..10).map(|i| Data::new(i, Time::from_seconds(i)));
+        let properties = Properties {
+            associative: true,
+            commutative: false,
+            inverse: None,
+        };
+        Stream::from_iter(ctx, events, |e| e.time, 1, Duration::zero())
+            .keyby(ctx, |_| ())
+            .incr_window(
+                ctx,
+                Window::Moving { length: 2, step: 3 },
+                |e| vec![e.value],
+                |a, b| [a.as_slice(), b.as_slice()].concat(),
+                |_, p, _| p.clone(),
+                properties,
+            )
+            .unkey(ctx)
+            .collect_vec(ctx, tx);
+    })
+    .unwrap();
+    let result = rx.try_recv().unwrap();
+    assert_eq!(result, [vec![0, 1], vec![3, 4], vec![6, 7]]);
+}
+
+#[test]
+#[should_panic(expected = "Counting window length must be positive")]
+fn test_incr_counting_zero_length() {
+    CurrentThreadRunner::run(|ctx| {
+        let properties = Properties {
+            associative: true,
+            commutative: true,
+            inverse: None,
+        };
+        Stream::from_iter(
+            ctx,
+            [Data::new(0, Time::zero())],
+            |e| e.time,
+            1,
+            Duration::zero(),
+        )
+        .keyby(ctx, |_| ())
+        .incr_window(
+            ctx,
+            Window::Counting { length: 0 },
+            |e| e.value,
+            |a, b| a + b,
+            |_, p, _| *p,
+            properties,
+        )
+        .unkey(ctx)
+        .drain(ctx);
+    })
+    .unwrap();
+}
+
+#[test]
+fn test_incr_watermarks() {
+    CurrentThreadRunner::run(|ctx| {
+        let events = [0, 5, 10, 25].map(|t| Data::new(t, Time::from_seconds(t)));
+        let properties = Properties {
+            associative: true,
+            commutative: true,
+            inverse: None,
+        };
+        Stream::from_iter(ctx, events, |e| e.time, 1, Duration::zero())
+            .keyby(ctx, |_| ())
+            .incr_window(
+                ctx,
+                Window::Tumbling {
+                    length: Duration::from_seconds(10),
+                },
+                |e| e.value,
+                |a, b| a + b,
+                |_, p, _| *p,
+                properties,
+            )
+            .unkey(ctx)
+            .assert(
+                ctx,
+                [
+                    Event::Watermark(Time::from_seconds(5)),
+                    Event::Watermark(Time::from_seconds(10)),
+                    // The windows closed by a watermark precede it.
+                    Event::Data(Time::from_seconds(10), 5),
+                    Event::Data(Time::from_seconds(20), 10),
+                    Event::Watermark(Time::from_seconds(25)),
+                    Event::Sentinel,
+                ],
+            );
+    })
+    .unwrap();
 }
@@ -1,6 +0,0 @@
-pub mod time_tumbling_holistic;
-pub mod count_sliding_holistic;
-pub mod time_tumbling_holistic_vec;
-// pub mod time_sliding_holistic;
-pub mod time_sliding_aligned_commutative_associative;
-pub mod time_sliding_aligned_holistic;
@@ -1,9 +0,0 @@
-pub mod window;
-pub mod duration;
-pub mod format;
-pub mod keyed_stream;
-pub mod option;
-pub mod reader;
-pub mod stream;
-pub mod time;
-pub mod writer;
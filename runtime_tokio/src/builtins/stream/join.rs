@@ -1,4 +0,0 @@
-pub mod interval_join;
-pub mod tumbling_window_join;
-pub mod tumbling_window_join_distinct;
-pub mod interval_join_forward;
@@ -1,4 +0,0 @@
-pub struct Context {
-    join_set: tokio::task::JoinSet<()>,
-    start: tokio::sync::broadcast::Receiver<()>,
-}
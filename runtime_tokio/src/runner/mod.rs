@@ -1,8 +0,0 @@
-pub mod data_parallel;
-pub mod task_parallel;
-pub mod context;
-
-// #[cfg(feature = "thread-pinning")]
-// pub mod pinned_data_parallel;
-
-pub mod current_thread;